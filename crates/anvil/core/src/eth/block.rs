@@ -1,6 +1,6 @@
 use super::transaction::{TransactionInfo, TypedReceipt};
 use alloy_consensus::{EMPTY_OMMER_ROOT_HASH, Header, proofs::calculate_transaction_root};
-use alloy_primitives::{Address, B64, B256, Bloom, Bytes, U256};
+use alloy_primitives::{Address, B64, B256, Bloom, Bytes, Signature, U256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 
 // Type alias to optionally support impersonated transactions
@@ -116,6 +116,46 @@ fn from(value: Header) -> Self {
     }
 }
 
+/// Length of the vanity prefix in the `extraData` of clique sealed headers
+pub const CLIQUE_EXTRA_VANITY: usize = 32;
+
+/// Length of the signer seal at the end of the `extraData` of clique sealed headers
+const CLIQUE_EXTRA_SEAL: usize = 65;
+
+/// Recovers the signer of a clique (PoA) sealed header from the seal in its `extraData`.
+///
+/// The seal signs the hash of the header with the seal stripped from the `extraData`, see
+/// [EIP-225](https://eips.ethereum.org/EIPS/eip-225).
+///
+/// Returns `None` if the header carries no seal or the seal is not a valid signature.
+pub fn clique_signer(header: &Header) -> Option<Address> {
+    let extra_data = &header.extra_data;
+    if extra_data.len() < CLIQUE_EXTRA_VANITY + CLIQUE_EXTRA_SEAL {
+        return None;
+    }
+    let (unsealed, seal) = extra_data.split_at(extra_data.len() - CLIQUE_EXTRA_SEAL);
+    let signature = Signature::from_raw(seal).ok()?;
+    let seal_hash =
+        Header { extra_data: Bytes::copy_from_slice(unsealed), ..header.clone() }.hash_slow();
+    signature.recover_address_from_prehash(&seal_hash).ok()
+}
+
+/// Seals the header the way clique (PoA) does: its `extraData` is replaced with an empty vanity
+/// followed by the signature `sign` returns for the hash of the header without the seal.
+pub fn clique_seal<E>(
+    header: &mut Header,
+    sign: impl FnOnce(B256) -> Result<Signature, E>,
+) -> Result<(), E> {
+    header.extra_data = Bytes::from(vec![0; CLIQUE_EXTRA_VANITY]);
+    let signature = sign(header.hash_slow())?;
+    let mut extra_data = header.extra_data.to_vec();
+    extra_data.extend_from_slice(&signature.r().to_be_bytes::<32>());
+    extra_data.extend_from_slice(&signature.s().to_be_bytes::<32>());
+    extra_data.push(signature.v() as u8);
+    header.extra_data = extra_data.into();
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_primitives::{
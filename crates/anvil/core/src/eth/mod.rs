@@ -1,5 +1,8 @@
-use crate::{eth::subscription::SubscriptionId, types::ReorgOptions};
-use alloy_primitives::{Address, B64, B256, Bytes, TxHash, U256};
+use crate::{
+    eth::subscription::SubscriptionId,
+    types::{OverrideScope, ReorgOptions, ResetOptions, StorageType, TransactionHashOrRequest},
+};
+use alloy_primitives::{Address, B64, B256, Bytes, Selector, TxHash, U256};
 use alloy_rpc_types::{
     BlockId, BlockNumberOrTag as BlockNumber, BlockOverrides, Filter, Index,
     anvil::{Forking, MineOptions},
@@ -403,6 +406,15 @@ pub enum EthRequest {
         #[serde(deserialize_with = "deserialize_number")] U256,
     ),
 
+    /// Modifies the ERC20 balance of an account and sets its allowance for a spender
+    #[serde(rename = "anvil_dealERC20AndApprove", alias = "anvil_dealErc20AndApprove")]
+    DealERC20AndApprove(
+        Address,
+        Address,
+        #[serde(deserialize_with = "deserialize_number")] U256,
+        Address,
+    ),
+
     /// Sets the code of a contract
     #[serde(rename = "anvil_setCode", alias = "hardhat_setCode")]
     SetCode(Address, Bytes),
@@ -668,6 +680,327 @@ pub enum EthRequest {
     #[serde(rename = "anvil_rollback", with = "sequence")]
     Rollback(Option<u64>),
 
+    /// Returns the history of chain resets and reorgs
+    #[serde(rename = "anvil_reorgHistory", with = "empty_params")]
+    ReorgHistory(()),
+
+    /// Returns the hashes of all transactions that occupied a sender/nonce slot in the pool
+    #[serde(rename = "anvil_txReplacements")]
+    TxReplacements(Address, #[serde(deserialize_with = "deserialize_number")] U256),
+
+    /// Returns the code size, code hash and proxy information of a contract
+    #[serde(rename = "anvil_contractInfo", with = "sequence")]
+    ContractInfo(Address),
+
+    /// Returns in how many blocks a pending transaction is expected to be included
+    #[serde(rename = "anvil_inclusionEstimate", with = "sequence")]
+    InclusionEstimate(TxHash),
+
+    /// Decodes the logs of a mined transaction against the given JSON ABI
+    #[serde(rename = "anvil_decodeReceiptLogs")]
+    DecodeReceiptLogs(TxHash, String),
+
+    /// Returns the bounds used to estimate the gas of a transaction
+    #[serde(rename = "anvil_estimateGasRange")]
+    EstimateGasRange(WithOtherFields<TransactionRequest>, #[serde(default)] Option<BlockId>),
+
+    /// Returns the pre-merge reward paid for the uncle at the given index of a block
+    #[serde(rename = "anvil_uncleReward")]
+    UncleReward(
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        Index,
+    ),
+
+    /// Queues an uncle mined by the given address at the given height for the next block
+    #[serde(rename = "anvil_injectUncle")]
+    InjectUncle(Address, u64),
+
+    /// Executes a call with gas metering disabled
+    #[serde(rename = "anvil_callNoGas")]
+    CallNoGas(
+        WithOtherFields<TransactionRequest>,
+        #[serde(default)] Option<BlockId>,
+        #[serde(default)] Option<StateOverride>,
+        #[serde(default)] Option<Box<BlockOverrides>>,
+    ),
+
+    /// Returns the author of a block
+    #[serde(
+        rename = "anvil_blockAuthor",
+        deserialize_with = "lenient_block_number::lenient_block_number_seq"
+    )]
+    BlockAuthor(BlockNumber),
+
+    /// Returns the gas a mined transaction spent per opcode category
+    #[serde(rename = "anvil_gasByOpcode", with = "sequence")]
+    GasByOpcode(TxHash),
+
+    /// Returns the state of an account right before the transaction at the given index of a block
+    #[serde(rename = "anvil_stateAtTransaction")]
+    StateAtTransaction(
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        Index,
+        Address,
+    ),
+
+    /// Sets the addresses that can't be called, calls to them revert
+    #[serde(rename = "anvil_setCallDenylist", with = "sequence")]
+    SetCallDenylist(Vec<Address>),
+
+    /// Returns the block the fork is based on
+    #[serde(rename = "anvil_forkBaseBlock", with = "empty_params")]
+    ForkBaseBlock(()),
+
+    /// Returns the net balance change per account caused by a mined transaction
+    #[serde(rename = "anvil_balanceDiff", with = "sequence")]
+    BalanceDiff(TxHash),
+
+    /// Returns the hashes of locally mined transactions discarded by the most recent reset or
+    /// reorg
+    #[serde(rename = "anvil_orphanedTransactions", with = "empty_params")]
+    OrphanedTransactions(()),
+
+    /// Returns the base fee of the next mined block
+    #[serde(
+        rename = "anvil_nextBaseFee",
+        alias = "anvil_getPendingBlockBaseFee",
+        with = "empty_params"
+    )]
+    NextBaseFee(()),
+
+    /// Sets the init code size limit (EIP-3860), `null` restores the default
+    #[serde(rename = "anvil_setInitcodeSizeLimit", with = "sequence")]
+    SetInitcodeSizeLimit(Option<U256>),
+
+    /// Returns the balances of multiple accounts
+    #[serde(rename = "anvil_balances")]
+    Balances(Vec<Address>, Option<BlockId>),
+
+    /// Returns the RLP encoded block
+    #[serde(
+        rename = "anvil_blockRlp",
+        deserialize_with = "lenient_block_number::lenient_block_number_seq"
+    )]
+    BlockRlp(BlockNumber),
+
+    /// Returns the runtime code of a contract as of a mined block
+    #[serde(rename = "anvil_codeAt")]
+    CodeAt(
+        Address,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Returns the total number of transactions in a range of blocks
+    #[serde(rename = "anvil_txCountInRange")]
+    TxCountInRange(
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Executes a call like `eth_call` with the given address as the block's coinbase
+    #[serde(rename = "anvil_callWithCoinbase")]
+    CallWithCoinbase(
+        WithOtherFields<TransactionRequest>,
+        Address,
+        #[serde(default)] Option<BlockId>,
+        #[serde(default)] Option<StateOverride>,
+    ),
+
+    /// Returns the mined transactions in a range of blocks that call the given function selector
+    #[serde(rename = "anvil_txsBySelector")]
+    TxsBySelector(
+        Selector,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Returns the storage slots of a contract that changed since the given cursor
+    #[serde(rename = "anvil_storageChangesSince")]
+    StorageChangesSince(Address, #[serde(default)] Option<B256>),
+
+    /// Returns whether a block's logs bloom may contain logs of the address and topics
+    #[serde(rename = "anvil_bloomMatches")]
+    BloomMatches(
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(default)] Option<Address>,
+        #[serde(default)] Vec<B256>,
+    ),
+
+    /// Sets the nonce the next deployment of an account uses and returns the address the
+    /// deployed contract will have
+    #[serde(rename = "anvil_resetNonceForDeployment")]
+    ResetNonceForDeployment(Address, U256),
+
+    /// Returns the account proof of an address at a block
+    #[serde(rename = "anvil_accountProof")]
+    AccountProof(Address, BlockId),
+
+    /// Returns the min, average and max time between the last locally mined blocks
+    #[serde(rename = "anvil_blockTimeStats", with = "sequence")]
+    BlockTimeStats(u64),
+
+    /// Registers a view function called after every mined block, mining halts if it reverts or
+    /// returns false
+    #[serde(rename = "anvil_setInvariantCheck")]
+    SetInvariantCheck(Address, Selector),
+
+    /// Removes the registered invariant check and resumes mining
+    #[serde(rename = "anvil_clearInvariantCheck", with = "empty_params")]
+    ClearInvariantCheck(()),
+
+    /// Returns the failed invariant check that halted mining, if any
+    #[serde(rename = "anvil_invariantViolation", with = "empty_params")]
+    InvariantViolation(()),
+
+    /// Returns the receipt of a mined transaction together with its trace
+    #[serde(rename = "anvil_receiptWithTrace")]
+    ReceiptWithTrace(B256, #[serde(default)] GethDebugTracingOptions),
+
+    /// Reads storage slots of a contract and decodes each as the given Solidity type
+    #[serde(rename = "anvil_decodeStorage")]
+    DecodeStorage(Address, Vec<(B256, StorageType)>),
+
+    /// Returns the genesis hash and chain config of the forked chain
+    #[serde(rename = "anvil_forkedChainGenesis", with = "empty_params")]
+    ForkedChainGenesis(()),
+
+    /// Returns the value of a storage slot at each block in the given range
+    #[serde(rename = "anvil_slotHistory")]
+    SlotHistory(
+        Address,
+        U256,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Returns the gas limit that applied to a block
+    #[serde(
+        rename = "anvil_blockGasLimit",
+        deserialize_with = "lenient_block_number::lenient_block_number_seq"
+    )]
+    BlockGasLimit(BlockNumber),
+
+    /// Returns the block and index a transaction was mined at
+    #[serde(rename = "anvil_txIndex", with = "sequence")]
+    TxIndex(B256),
+
+    /// Returns the address, derivation path and, if enabled, private key of every dev wallet
+    #[serde(rename = "anvil_walletInfo", with = "empty_params")]
+    WalletInfo(()),
+
+    /// Mines a block with the pending transactions that fit within the given amount of gas
+    #[serde(rename = "anvil_mineToTargetGas", with = "sequence")]
+    MineToTargetGas(u64),
+
+    /// Returns the dependency graph of the given transactions based on the state they access
+    #[serde(rename = "anvil_txDependencies", with = "sequence")]
+    TxDependencies(Vec<TransactionHashOrRequest>),
+
+    /// Sets whether state overrides are reverted after the next block or kept
+    #[serde(rename = "anvil_setOverrideScope", with = "sequence")]
+    SetOverrideScope(OverrideScope),
+
+    /// Returns the latest block number of the forked remote endpoint
+    #[serde(rename = "anvil_remoteBlockNumber", with = "empty_params")]
+    RemoteBlockNumber(()),
+
+    /// Returns the init code the contract at the given address was deployed with
+    #[serde(rename = "anvil_creationCode", with = "sequence")]
+    CreationCode(Address),
+
+    /// Sets the highest gas limit a single transaction may request, zero removes the cap
+    #[serde(rename = "anvil_setTransactionGasCap", with = "sequence")]
+    SetTransactionGasCap(U256),
+
+    /// Returns the balance of the address in the latest block mined at or before the timestamp
+    #[serde(rename = "anvil_balanceAtTimestamp")]
+    BalanceAtTimestamp(Address, u64),
+
+    /// Returns the address a CREATE2 deployment with the given deployer, salt and init code hash
+    /// lands at
+    #[serde(rename = "anvil_predictCreate2Address")]
+    PredictCreate2Address(Address, B256, B256),
+
+    /// Returns the approximate memory taken by the state of the node
+    #[serde(rename = "anvil_memoryUsage", with = "empty_params")]
+    MemoryUsage(()),
+
+    /// Returns the receipt of the transaction at the given index of the given block
+    #[serde(rename = "anvil_receiptByBlockAndIndex")]
+    ReceiptByBlockAndIndex(BlockId, Index),
+
+    /// Returns the EVM spec calls against the given block are executed with
+    #[serde(
+        rename = "anvil_specAtBlock",
+        deserialize_with = "lenient_block_number::lenient_block_number_seq"
+    )]
+    SpecAtBlock(BlockNumber),
+
+    /// Returns the storage trie root of the account at the given block
+    #[serde(rename = "anvil_storageRoot")]
+    StorageRoot(Address, Option<BlockId>),
+
+    /// Re-executes the transactions of a remote block on top of the state of its parent
+    #[serde(rename = "anvil_replayBlock")]
+    ReplayBlock(u64, #[serde(default)] Option<StateOverride>),
+
+    /// Writes the values to consecutive slots of the account's storage
+    #[serde(rename = "anvil_setStorageAtRange")]
+    SetStorageAtRange(
+        Address,
+        /// first slot
+        U256,
+        /// values
+        Vec<B256>,
+    ),
+
+    /// Returns the fee the transaction paid, split into its execution and L1 data fee
+    #[serde(rename = "anvil_effectiveFee", with = "sequence")]
+    EffectiveFee(B256),
+
+    /// Resets the fork like `anvil_reset` and re-applies the selected local overrides on top
+    #[serde(rename = "anvil_resetWithOptions")]
+    ResetWithOptions(Forking, #[serde(default)] ResetOptions),
+
+    /// Returns the difficulty and total difficulty of each block in the given range
+    #[serde(rename = "anvil_difficultyHistory")]
+    DifficultyHistory(
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Transfers an ERC721 token to an account by rewriting the token's storage
+    #[serde(rename = "anvil_dealERC721", alias = "anvil_dealErc721")]
+    DealERC721(Address, Address, U256),
+
+    /// Classifies an account as EOA, contract or empty
+    #[serde(rename = "anvil_accountType", with = "sequence")]
+    AccountType(Address),
+
+    /// Sets the offset added to the block number the `NUMBER` opcode reports
+    #[serde(rename = "anvil_setBlockNumberOffset", with = "sequence")]
+    SetBlockNumberOffset(i64),
+
+    /// Returns all logs whose first topic matches the given event signature hash in the range
+    #[serde(rename = "anvil_logsByTopic0")]
+    LogsByTopic0(
+        B256,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+        #[serde(deserialize_with = "lenient_block_number::lenient_block_number")] BlockNumber,
+    ),
+
+    /// Deploys the init code from the first account, returning the revert reason of the
+    /// constructor instead of failing
+    #[serde(rename = "anvil_deployAllowRevert")]
+    DeployAllowRevert(Bytes, #[serde(default)] U256),
+
+    /// Explains how the base fee of the block following the given block is calculated
+    #[serde(
+        rename = "anvil_baseFeeExplain",
+        deserialize_with = "lenient_block_number::lenient_block_number_seq"
+    )]
+    BaseFeeExplain(BlockNumber),
+
     /// Wallet
     #[serde(rename = "wallet_getCapabilities", with = "empty_params")]
     WalletGetCapabilities(()),
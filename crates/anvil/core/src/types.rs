@@ -1,6 +1,8 @@
-use alloy_primitives::Bytes;
-use alloy_rpc_types::TransactionRequest;
-use serde::Deserialize;
+use crate::eth::transaction::ReceiptResponse;
+use alloy_primitives::{Address, B256, Bytes, U256};
+use alloy_rpc_types::{TransactionRequest, anvil::NodeInfo, trace::geth::GethTrace};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::Deref};
 
 /// Represents the options used in `anvil_reorg`
 #[derive(Debug, Clone, Deserialize)]
@@ -18,3 +20,439 @@ pub enum TransactionData {
     JSON(TransactionRequest),
     Raw(Bytes),
 }
+
+/// What caused an entry of the history returned by `anvil_reorgHistory`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReorgKind {
+    /// The chain was reset via `anvil_reset`
+    Reset,
+    /// The chain was reorged via `anvil_reorg`
+    Reorg,
+}
+
+/// A single head change recorded in the history returned by `anvil_reorgHistory`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgHistoryEntry {
+    /// What triggered the head change
+    pub kind: ReorgKind,
+    /// Number of the head block before the change
+    pub old_block_number: u64,
+    /// Hash of the head block before the change
+    pub old_block_hash: B256,
+    /// Number of the head block after the change
+    pub new_block_number: u64,
+    /// Hash of the head block after the change
+    pub new_block_hash: B256,
+    /// Unix timestamp (seconds) at which the change happened
+    pub timestamp: u64,
+}
+
+/// The bounds of the gas estimation returned by `anvil_estimateGasRange`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimateRange {
+    /// Gas actually used when executing with the upper bound, without any search margin
+    pub lower: u64,
+    /// The estimated gas limit, as returned by `eth_estimateGas`
+    pub estimate: u64,
+    /// The highest gas limit the estimation started from
+    pub upper: u64,
+}
+
+impl GasEstimateRange {
+    /// Returns a range where both bounds equal the estimate
+    pub fn exact(estimate: u64) -> Self {
+        Self { lower: estimate, estimate, upper: estimate }
+    }
+}
+
+/// The response of `anvil_nodeInfo`: [`NodeInfo`] extended with anvil specific details
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnvilNodeInfo {
+    #[serde(flatten)]
+    pub info: NodeInfo,
+    /// Block environment fields that were adjusted for the forked chain, e.g. `number` on
+    /// Arbitrum
+    pub chain_specific_env_changes: Vec<String>,
+}
+
+impl Deref for AnvilNodeInfo {
+    type Target = NodeInfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.info
+    }
+}
+
+/// A log decoded by `anvil_decodeReceiptLogs`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedLog {
+    /// Address of the contract that emitted the log
+    pub address: Address,
+    /// Index of the log in the block
+    pub log_index: Option<u64>,
+    /// Name of the matching event
+    pub event: String,
+    /// The decoded event fields, in declaration order
+    pub params: Vec<DecodedLogParam>,
+}
+
+/// A single named field of a [`DecodedLog`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedLogParam {
+    /// Name of the field as declared in the ABI
+    pub name: String,
+    /// Whether the field is an indexed topic
+    pub indexed: bool,
+    /// The decoded value, formatted so that it can be parsed again
+    pub value: String,
+}
+
+/// Information about a deployed contract returned by `anvil_contractInfo`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractInfo {
+    /// Size of the deployed code in bytes
+    pub code_size: u64,
+    /// Keccak hash of the deployed code
+    pub code_hash: B256,
+    /// Whether the EIP-1967 implementation slot is populated
+    pub is_proxy: bool,
+    /// The implementation address stored in the EIP-1967 slot, if any
+    pub implementation: Option<Address>,
+}
+
+/// Gas consumed by a transaction per opcode category, returned by `anvil_gasByOpcode`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasByOpcode {
+    /// Gas spent on storage access: `SLOAD`, `SSTORE`, `TLOAD` and `TSTORE`
+    pub storage: u64,
+    /// Gas spent on memory and data copying opcodes
+    pub memory: u64,
+    /// Gas spent on message calls, contract creations and self destructs, excluding the gas
+    /// forwarded to the new frame and the stipend of value transfers
+    pub calls: u64,
+    /// Gas spent on all other opcodes
+    pub compute: u64,
+    /// Gas refunded at the end of the transaction, already deducted from the categories above in
+    /// proportion to the refunds their opcodes earned
+    pub refunded: u64,
+}
+
+/// The block a fork is based on, captured when forking or resetting, returned by
+/// `anvil_forkBaseBlock`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkBaseBlock {
+    /// Number of the forked block
+    pub number: u64,
+    /// Hash of the forked block
+    pub hash: B256,
+    /// Timestamp of the forked block
+    pub timestamp: u64,
+    /// Base fee of the forked block, if any
+    pub base_fee: Option<u128>,
+    /// State root of the forked block
+    pub state_root: B256,
+}
+
+/// Genesis and chain config of the forked chain, returned by `anvil_forkedChainGenesis`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkedChainGenesis {
+    /// Chain id of the forked chain
+    pub chain_id: u64,
+    /// Name of the forked chain, if it is a known network
+    pub chain: Option<String>,
+    /// Whether the forked chain is a known testnet
+    pub is_testnet: bool,
+    /// Hash of the forked chain's genesis block
+    pub genesis_hash: B256,
+}
+
+/// Position of a mined transaction, returned by `anvil_txIndex`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionIndex {
+    /// Number of the block the transaction was mined in
+    pub block_number: u64,
+    /// Hash of the block the transaction was mined in
+    pub block_hash: B256,
+    /// Index of the transaction within the block
+    pub index: u64,
+}
+
+/// A dev wallet of the node, returned by `anvil_walletInfo`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletInfo {
+    /// Address of the wallet
+    pub address: Address,
+    /// Derivation path of the wallet, if it was derived from the node's mnemonic
+    pub derivation_path: Option<String>,
+    /// Private key of the wallet, only exposed if the node is configured to
+    pub private_key: Option<B256>,
+}
+
+/// Storage slots of a contract that changed since a cursor, returned by
+/// `anvil_storageChangesSince`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChanges {
+    /// Cursor to pass to the next call to only receive the changes made after this one
+    pub cursor: B256,
+    /// The changed slots and their current values
+    pub changes: BTreeMap<B256, B256>,
+}
+
+/// Value of a storage slot at a block, returned by `anvil_slotHistory`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotHistoryEntry {
+    /// Number of the block
+    pub block_number: u64,
+    /// Value of the slot at the end of the block
+    pub value: B256,
+}
+
+/// Time between consecutive locally mined blocks, returned by `anvil_blockTimeStats`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTimeStats {
+    /// Number of block intervals the stats are computed over
+    pub intervals: u64,
+    /// Shortest time between two blocks, in seconds
+    pub min: u64,
+    /// Average time between two blocks, in seconds
+    pub avg: f64,
+    /// Longest time between two blocks, in seconds
+    pub max: u64,
+}
+
+/// A failed invariant check registered via `anvil_setInvariantCheck`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantViolation {
+    /// Number of the block after which the invariant failed
+    pub block_number: u64,
+    /// Hash of the block after which the invariant failed
+    pub block_hash: B256,
+    /// Why the check failed
+    pub reason: String,
+}
+
+/// A transaction receipt together with the transaction's trace, returned by
+/// `anvil_receiptWithTrace`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptWithTrace {
+    /// The receipt of the transaction
+    pub receipt: ReceiptResponse,
+    /// The trace of the transaction
+    pub trace: GethTrace,
+}
+
+/// Solidity type a storage slot is decoded as by `anvil_decodeStorage`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageType {
+    /// `uint256`
+    Uint256,
+    /// `address`, stored in the low 20 bytes of the slot
+    Address,
+    /// `bool`, `true` for any non-zero slot
+    Bool,
+}
+
+/// A storage slot decoded as a [StorageType], returned by `anvil_decodeStorage`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageValue {
+    /// A decoded `uint256`
+    Uint256(U256),
+    /// A decoded `address`
+    Address(Address),
+    /// A decoded `bool`
+    Bool(bool),
+}
+
+impl StorageValue {
+    /// Decodes the raw value of a storage slot as the given type
+    pub fn decode(value: B256, ty: StorageType) -> Self {
+        match ty {
+            StorageType::Uint256 => Self::Uint256(value.into()),
+            StorageType::Address => Self::Address(Address::from_word(value)),
+            StorageType::Bool => Self::Bool(!value.is_zero()),
+        }
+    }
+}
+
+/// A transaction passed to `anvil_txDependencies`, either by hash or as a request
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+#[expect(clippy::large_enum_variant)]
+pub enum TransactionHashOrRequest {
+    Hash(B256),
+    Request(TransactionRequest),
+}
+
+/// An edge of the graph returned by `anvil_txDependencies`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDependency {
+    /// Position of the transaction in the given list
+    pub index: usize,
+    /// Position of an earlier transaction that writes state this one accesses, or accesses
+    /// state this one writes
+    pub depends_on: usize,
+}
+
+/// How long state overrides set via `anvil_set*` calls last, see `anvil_setOverrideScope`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideScope {
+    /// Overrides only apply to the execution of the next mined block, neither the state of that
+    /// block nor later states include them
+    Block,
+    /// Overrides stay until they are changed
+    #[default]
+    Permanent,
+}
+
+/// Approximate memory taken by the state of the node, returned by `anvil_memoryUsage`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryUsage {
+    /// Number of accounts in the current state
+    pub accounts: usize,
+    /// Number of storage slots in the current state
+    pub storage_slots: usize,
+    /// Number of active state snapshots, each holding a copy of the state
+    pub state_snapshots: usize,
+    /// Number of historic block states kept in memory
+    pub historic_states: usize,
+    /// Rough estimate of the bytes taken by all of the above
+    pub approximate_bytes: u64,
+}
+
+/// Outcome of a transaction re-executed by `anvil_replayBlock`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedTransaction {
+    /// Hash of the replayed transaction
+    pub transaction_hash: B256,
+    /// Whether the transaction executed successfully, false if it reverted or was invalid against
+    /// the replayed state
+    pub success: bool,
+    /// Gas used by the transaction, zero if it was invalid
+    pub gas_used: u64,
+    /// Output of the transaction
+    pub output: Option<Bytes>,
+}
+
+/// Fee a transaction paid, returned by `anvil_effectiveFee`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveFee {
+    /// Fee paid for the execution of the transaction on the chain itself
+    pub execution_fee: U256,
+    /// Fee paid for posting the transaction data to L1, only reported by OP stack and Arbitrum
+    /// chains
+    pub l1_fee: Option<U256>,
+    /// Sum of all fee components
+    pub total_fee: U256,
+}
+
+impl EffectiveFee {
+    /// Creates the fee from its components
+    pub fn new(execution_fee: U256, l1_fee: Option<U256>) -> Self {
+        let total_fee = execution_fee.saturating_add(l1_fee.unwrap_or_default());
+        Self { execution_fee, l1_fee, total_fee }
+    }
+}
+
+/// Options for `anvil_resetWithOptions`, selecting which local overrides survive the reset
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResetOptions {
+    /// Re-apply balances set via `anvil_setBalance`, `anvil_addBalance` and `anvil_dealERC20`
+    pub preserve_balances: bool,
+    /// Keep impersonating the accounts that were impersonated before the reset
+    pub preserve_impersonations: bool,
+    /// Re-apply code set via `anvil_setCode`
+    pub preserve_code: bool,
+}
+
+/// Difficulty of a block, returned by `anvil_difficultyHistory`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DifficultyHistoryEntry {
+    /// Number of the block
+    pub number: u64,
+    /// Difficulty of the block
+    pub difficulty: U256,
+    /// Total difficulty of the chain up to and including the block, if known
+    pub total_difficulty: Option<U256>,
+}
+
+/// Kind of an account, see [AccountType]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountKind {
+    /// Account without code that holds a balance or has sent transactions
+    Eoa,
+    /// Account with code deployed
+    Contract,
+    /// Account without code, balance and nonce
+    Empty,
+}
+
+/// Classification of an account, returned by `anvil_accountType`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountType {
+    /// Kind of the account
+    pub kind: AccountKind,
+    /// Address calls are forwarded to: the implementation of an EIP-1967 or EIP-1167 proxy, or
+    /// the delegate of an EIP-7702 delegated EOA
+    pub implementation: Option<Address>,
+}
+
+/// Outcome of `anvil_deployAllowRevert`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployResult {
+    /// Address of the deployed contract, unset if the constructor reverted
+    pub address: Option<Address>,
+    /// Hash of the deployment transaction, unset if the constructor reverted
+    pub transaction_hash: Option<B256>,
+    /// Decoded revert reason of the constructor
+    pub revert_reason: Option<String>,
+    /// Raw revert data of the constructor
+    pub revert_data: Option<Bytes>,
+}
+
+/// Inputs and result of the base fee calculation of the block following a parent block, returned
+/// by `anvil_baseFeeExplain`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseFeeExplanation {
+    /// Number of the parent block
+    pub parent_number: u64,
+    /// Gas used by the parent block
+    pub parent_gas_used: u64,
+    /// Gas limit of the parent block
+    pub parent_gas_limit: u64,
+    /// Gas target of the parent block, the base fee rises if more gas was used and falls if less
+    pub gas_target: u64,
+    /// Base fee of the parent block
+    pub parent_base_fee: u64,
+    /// Base fee of the next block
+    pub next_base_fee: u64,
+}
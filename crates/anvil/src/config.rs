@@ -9,7 +9,7 @@
             mem::fork_db::ForkedDatabase,
             time::duration_since_unix_epoch,
         },
-        fees::{INITIAL_BASE_FEE, INITIAL_GAS_PRICE},
+        fees::{GasPriceOracle, INITIAL_BASE_FEE, INITIAL_GAS_PRICE},
         pool::transactions::{PoolTransaction, TransactionOrder},
     },
     hardfork::{ChainHardfork, ethereum_hardfork_from_block_tag, spec_id_from_ethereum_hardfork},
@@ -20,7 +20,7 @@
 use alloy_genesis::Genesis;
 use alloy_network::{AnyNetwork, TransactionResponse};
 use alloy_op_hardforks::OpHardfork;
-use alloy_primitives::{BlockNumber, TxHash, U256, hex, map::HashMap, utils::Unit};
+use alloy_primitives::{Address, B256, BlockNumber, TxHash, U256, hex, map::HashMap, utils::Unit};
 use alloy_provider::Provider;
 use alloy_rpc_types::{Block, BlockNumberOrTag};
 use alloy_signer::Signer;
@@ -29,17 +29,21 @@
     coins_bip39::{English, Mnemonic},
 };
 use alloy_transport::TransportError;
+use anvil_core::types::WalletInfo;
 use anvil_server::ServerConfig;
 use eyre::{Context, Result};
 use foundry_common::{
     ALCHEMY_FREE_TIER_CUPS, NON_ARCHIVE_NODE_WARNING, REQUEST_TIMEOUT,
-    provider::{ProviderBuilder, RetryProvider},
+    provider::{ProviderBuilder, RetryProvider, get_offline_provider},
 };
 use foundry_config::Config;
 use foundry_evm::{
     backend::{BlockchainDb, BlockchainDbMeta, SharedBackend},
     constants::DEFAULT_CREATE2_DEPLOYER,
-    utils::{apply_chain_and_block_specific_env_changes, get_blob_base_fee_update_fraction},
+    fork::RetryConfig,
+    utils::{
+        apply_chain_and_block_specific_env_changes_for_chain, get_blob_base_fee_update_fraction,
+    },
 };
 use foundry_evm_core::AsEnvMut;
 use itertools::Itertools;
@@ -57,11 +61,12 @@
     fs::File,
     io,
     net::{IpAddr, Ipv4Addr},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
 use tokio::sync::RwLock as TokioRwLock;
+use url::Url;
 use yansi::Paint;
 
 pub use foundry_common::version::SHORT_VERSION as VERSION_MESSAGE;
@@ -198,6 +203,94 @@ pub struct NodeConfig {
     pub silent: bool,
     /// The path where states are cached.
     pub cache_path: Option<PathBuf>,
+    /// If set, the full state is dumped to the given path at the given interval.
+    pub state_autosave: Option<(Duration, PathBuf)>,
+    /// Autosaved state to resume from, if the file exists
+    pub state_autosave_load: Option<PathBuf>,
+    /// If set, the prevrandao of every mined block is derived from this seed and the block number
+    /// instead of being random.
+    pub prevrandao_seed: Option<u64>,
+    /// Forked accounts that always appear empty, regardless of the remote state.
+    pub masked_accounts: Vec<Address>,
+    /// Disables the chain and block specific adjustments of the forked environment.
+    pub disable_chain_specific_env: bool,
+    /// If set, at most this many transactions are included per block, the rest is left in the
+    /// pool for the following blocks.
+    pub max_txs_per_block: Option<usize>,
+    /// Whether to check that the state of a pinned block `anvil_reset` forks from is consistent
+    /// with its state root.
+    pub verify_state_root_on_reset: bool,
+    /// If set, `eth_getLogs` rejects queries spanning more than this many blocks.
+    pub max_log_block_range: Option<u64>,
+    /// Whether transactions with a future nonce are queued until they become executable, or
+    /// rejected.
+    pub queue_future_nonce: bool,
+    /// The maximum number of concurrent requests to the fork endpoint, if limited.
+    pub fork_max_concurrent_requests: Option<usize>,
+    /// Whether to use HTTP/2 with prior knowledge for requests to an `http://` fork endpoint
+    pub fork_http2: bool,
+    /// If set, calls and transactions returning more than this many bytes revert.
+    pub max_returndata_size: Option<usize>,
+    /// Whether the access lists of transactions are applied, i.e. charged for and their entries
+    /// pre-warmed (EIP-2930).
+    pub access_list_gas: bool,
+    /// Whether impersonated accounts stay impersonated after `anvil_reset`, defaults to `true`.
+    pub persist_impersonation: bool,
+    /// Maximum size of the init code of contract creations (EIP-3860), defaults to twice the
+    /// code size limit.
+    pub initcode_size_limit: Option<usize>,
+    /// If set, transactions that weren't mined within this duration are evicted from the pool.
+    pub tx_ttl: Option<Duration>,
+    /// Whether every sent transaction is mined right away in a block of its own, before the send
+    /// call returns.
+    pub mine_on_send: bool,
+    /// Whether interval mining skips blocks while there are no transactions to include.
+    pub skip_empty_blocks: bool,
+    /// The EIP-2718 transaction types accepted by the node, all if unset.
+    pub allowed_tx_types: Option<Vec<u8>>,
+    /// How `eth_gasPrice` computes its suggestion.
+    pub gas_price_oracle: GasPriceOracle,
+    /// Whether fork requests fail fast with an offline error once the remote is unreachable,
+    /// while reads served from the fork cache keep working.
+    pub offline_fork_fallback: bool,
+    /// Whether results of `eth_call`s are cached until the next block or state change.
+    pub call_result_cache: bool,
+    /// Whether legacy transactions without EIP-155 replay protection are rejected.
+    pub require_eip155: bool,
+    /// Whether executing on a forked L2 charges the L2's L1 data fee on top of the execution fee.
+    ///
+    /// Only supported on OP stack chains.
+    pub l2_gas_accounting: bool,
+    /// Whether gas estimation ignores the block gas limit.
+    pub estimate_ignore_block_limit: bool,
+    /// Whether `anvil_walletInfo` includes the private keys of the dev wallets.
+    pub expose_private_keys: bool,
+    /// Number of blocks behind the remote head to pin the fork to when forking latest.
+    pub fork_confirmation_depth: u64,
+    /// How long sent transactions are held before they become eligible for mining.
+    pub inclusion_delay: Option<Duration>,
+    /// How many `eth_call`s may execute at the same time, unbounded if unset.
+    pub max_concurrent_calls: Option<usize>,
+    /// Highest total fee (`gas_limit * max_fee_per_gas`) a transaction may pay, uncapped if unset.
+    pub max_fee_per_tx: Option<U256>,
+    /// Highest number of accounts the state may hold, unbounded if unset.
+    pub max_state_accounts: Option<usize>,
+    /// How the requests made while the fork is set up are retried, on top of the retries of the
+    /// fork provider
+    pub fork_retry_config: RetryConfig,
+    /// Fork cache file that is preloaded when the node starts
+    pub warm_cache_from: Option<PathBuf>,
+    /// Timestamp the fork block is resolved from if no fork block is set, the latest block mined
+    /// at or before it is forked
+    pub fork_timestamp: Option<u64>,
+    /// Endpoint that is notified whenever a transaction is received or mined
+    pub tx_webhook: Option<Url>,
+    /// State dump to fork from instead of a remote endpoint
+    pub fork_state_file: Option<PathBuf>,
+    /// Whether the pre-merge block and uncle rewards are paid when mining
+    pub block_rewards: bool,
+    /// Signer sealing mined blocks the way clique (PoA) does
+    pub block_signer: Option<PrivateKeySigner>,
 }
 
 impl NodeConfig {
@@ -491,6 +584,44 @@ fn default() -> Self {
             odyssey: false,
             silent: false,
             cache_path: None,
+            state_autosave: None,
+            state_autosave_load: None,
+            prevrandao_seed: None,
+            masked_accounts: vec![],
+            disable_chain_specific_env: false,
+            max_txs_per_block: None,
+            verify_state_root_on_reset: false,
+            max_log_block_range: None,
+            queue_future_nonce: true,
+            fork_max_concurrent_requests: None,
+            fork_http2: false,
+            max_returndata_size: None,
+            access_list_gas: true,
+            persist_impersonation: true,
+            initcode_size_limit: None,
+            tx_ttl: None,
+            mine_on_send: false,
+            skip_empty_blocks: false,
+            allowed_tx_types: None,
+            gas_price_oracle: GasPriceOracle::default(),
+            offline_fork_fallback: false,
+            call_result_cache: false,
+            require_eip155: false,
+            l2_gas_accounting: false,
+            estimate_ignore_block_limit: false,
+            expose_private_keys: false,
+            fork_confirmation_depth: 0,
+            inclusion_delay: None,
+            max_concurrent_calls: None,
+            max_fee_per_tx: None,
+            max_state_accounts: None,
+            fork_retry_config: Default::default(),
+            warm_cache_from: None,
+            fork_timestamp: None,
+            tx_webhook: None,
+            fork_state_file: None,
+            block_rewards: false,
+            block_signer: None,
         }
     }
 }
@@ -574,6 +705,24 @@ pub fn with_init_state_path(mut self, path: impl AsRef<std::path::Path>) -> Self
         self
     }
 
+    /// Periodically dumps the full state to `path` every `interval`, so it can be recovered
+    /// after a crash via [`Self::with_state_autosave_load`]
+    #[must_use]
+    pub fn with_state_autosave(mut self, interval: Duration, path: PathBuf) -> Self {
+        self.state_autosave = Some((interval, path));
+        self
+    }
+
+    /// Resumes from a state previously written by [`Self::with_state_autosave`], if the file
+    /// exists
+    ///
+    /// Spawning the node fails if the file can't be loaded.
+    #[must_use]
+    pub fn with_state_autosave_load(mut self, path: impl AsRef<Path>) -> Self {
+        self.state_autosave_load = Some(path.as_ref().to_path_buf());
+        self
+    }
+
     /// Sets the chain ID
     #[must_use]
     pub fn with_chain_id<U: Into<u64>>(mut self, chain_id: Option<U>) -> Self {
@@ -771,6 +920,22 @@ pub fn with_slots_in_an_epoch(mut self, slots_in_an_epoch: u64) -> Self {
         self
     }
 
+    /// Makes the prevrandao of every mined block a deterministic function of the given seed and the
+    /// block number, `keccak256(seed || number)`
+    #[must_use]
+    pub fn with_deterministic_prevrandao_seed(mut self, seed: u64) -> Self {
+        self.prevrandao_seed = Some(seed);
+        self
+    }
+
+    /// Limits the number of transactions included in a block, any remaining ready transactions
+    /// spill over to the following blocks
+    #[must_use]
+    pub fn with_max_txs_per_block(mut self, max_txs_per_block: usize) -> Self {
+        self.max_txs_per_block = Some(max_txs_per_block);
+        self
+    }
+
     /// Sets the port to use
     #[must_use]
     pub fn with_port(mut self, port: u16) -> Self {
@@ -852,6 +1017,338 @@ pub fn with_fork_headers(mut self, headers: Vec<String>) -> Self {
         self
     }
 
+    /// Sets the forked accounts that should appear empty (no balance, code or storage),
+    /// regardless of their state on the remote chain
+    #[must_use]
+    pub fn with_masked_accounts(mut self, accounts: Vec<Address>) -> Self {
+        self.masked_accounts = accounts;
+        self
+    }
+
+    /// If set to `true`, the forked environment is used as is, without the chain and block specific
+    /// adjustments, e.g. using the L1 block number on Arbitrum
+    #[must_use]
+    pub fn with_disable_chain_specific_env(mut self, disable_chain_specific_env: bool) -> Self {
+        self.disable_chain_specific_env = disable_chain_specific_env;
+        self
+    }
+
+    /// If set to `true`, `anvil_reset` to a pinned block first proves a sample of the block's
+    /// accounts with `eth_getProof` and fails, leaving the node untouched, if the proofs don't
+    /// verify against the block's state root. After the reset, the proven accounts are compared
+    /// with what the fork database serves, and the reset fails if they differ, e.g. because the
+    /// fork cache is corrupted
+    #[must_use]
+    pub fn with_verify_state_root_on_reset(mut self, verify_state_root_on_reset: bool) -> Self {
+        self.verify_state_root_on_reset = verify_state_root_on_reset;
+        self
+    }
+
+    /// Sets the maximum number of blocks an `eth_getLogs` query may span
+    #[must_use]
+    pub fn with_max_log_block_range(mut self, max_log_block_range: u64) -> Self {
+        self.max_log_block_range = Some(max_log_block_range);
+        self
+    }
+
+    /// Sets whether transactions with a future nonce are queued until the nonce gap is filled
+    /// (default), or rejected immediately
+    #[must_use]
+    pub fn with_queue_future_nonce(mut self, queue_future_nonce: bool) -> Self {
+        self.queue_future_nonce = queue_future_nonce;
+        self
+    }
+
+    /// Sets the maximum number of concurrent requests to the fork endpoint
+    #[must_use]
+    pub fn with_fork_max_concurrent_requests(
+        mut self,
+        fork_max_concurrent_requests: usize,
+    ) -> Self {
+        self.fork_max_concurrent_requests = Some(fork_max_concurrent_requests);
+        self
+    }
+
+    /// Sets whether to use HTTP/2 with prior knowledge for requests to an `http://` fork endpoint,
+    /// multiplexing concurrent requests over a single connection. The endpoint must support
+    /// HTTP/2. `https://` endpoints negotiate the protocol via ALPN regardless.
+    #[must_use]
+    pub fn with_fork_http2(mut self, fork_http2: bool) -> Self {
+        self.fork_http2 = fork_http2;
+        self
+    }
+
+    /// Sets the maximum size of the data a call frame may return or revert with, larger outputs
+    /// revert instead
+    #[must_use]
+    pub fn with_max_returndata_size(mut self, max_returndata_size: usize) -> Self {
+        self.max_returndata_size = Some(max_returndata_size);
+        self
+    }
+
+    /// Sets whether the EIP-2930 access lists of transactions are applied (default). If disabled,
+    /// access lists are ignored during execution: they're neither charged for nor are their
+    /// entries pre-warmed
+    #[must_use]
+    pub fn with_access_list_gas(mut self, access_list_gas: bool) -> Self {
+        self.access_list_gas = access_list_gas;
+        self
+    }
+
+    /// Sets whether impersonated accounts stay impersonated after `anvil_reset`, by default they
+    /// do. Passing `false` clears the impersonation set on every reset
+    #[must_use]
+    pub fn with_persist_impersonation(mut self, persist_impersonation: bool) -> Self {
+        self.persist_impersonation = persist_impersonation;
+        self
+    }
+
+    /// Sets a custom init code size limit (EIP-3860). If unset, the limit is twice the code size
+    /// limit
+    #[must_use]
+    pub fn with_initcode_size_limit(mut self, initcode_size_limit: Option<usize>) -> Self {
+        self.initcode_size_limit = initcode_size_limit;
+        self
+    }
+
+    /// Sets the time after which transactions that weren't mined are evicted from the pool
+    #[must_use]
+    pub fn with_tx_ttl(mut self, tx_ttl: Duration) -> Self {
+        self.tx_ttl = Some(tx_ttl);
+        self
+    }
+
+    /// Sets whether `eth_sendTransaction` and `eth_sendRawTransaction` mine a block containing
+    /// just the sent transaction before returning.
+    ///
+    /// This replaces instant mining, an interval miner keeps mining blocks in between.
+    #[must_use]
+    pub fn with_mine_on_send(mut self, mine_on_send: bool) -> Self {
+        self.mine_on_send = mine_on_send;
+        self
+    }
+
+    /// Sets whether the miner skips blocks that would be empty, e.g. interval mining only
+    /// produces a block once there are transactions to include.
+    ///
+    /// Blocks mined explicitly, e.g. with `evm_mine`, are not affected.
+    #[must_use]
+    pub fn with_skip_empty_blocks(mut self, skip_empty_blocks: bool) -> Self {
+        self.skip_empty_blocks = skip_empty_blocks;
+        self
+    }
+
+    /// Sets the EIP-2718 transaction types the node accepts, e.g. `vec![2]` to only accept
+    /// EIP-1559 transactions. Legacy transactions are type `0`.
+    #[must_use]
+    pub fn with_allowed_tx_types(mut self, allowed_tx_types: Vec<u8>) -> Self {
+        self.allowed_tx_types = Some(allowed_tx_types);
+        self
+    }
+
+    /// Sets how `eth_gasPrice` computes its suggestion, either fixed to the next block's base fee
+    /// or derived from the gas prices paid in recent blocks.
+    #[must_use]
+    pub fn with_gas_price_oracle(mut self, gas_price_oracle: GasPriceOracle) -> Self {
+        self.gas_price_oracle = gas_price_oracle;
+        self
+    }
+
+    /// Sets whether the node keeps serving cached fork state if the remote becomes unreachable.
+    ///
+    /// Fork requests are then not retried, and once a request couldn't reach the remote, reads that
+    /// miss the cache fail right away with an error stating that the remote is offline instead of
+    /// waiting for the request timeout. This lasts until the fork is reset.
+    #[must_use]
+    pub fn with_offline_fork_fallback(mut self, offline_fork_fallback: bool) -> Self {
+        self.offline_fork_fallback = offline_fork_fallback;
+        self
+    }
+
+    /// Sets whether identical `eth_call`s are served from an in-memory cache, e.g. for tooling
+    /// that polls the same view function.
+    ///
+    /// Cached results are dropped once a new block is mined or any request changes state.
+    #[must_use]
+    pub fn with_call_result_cache(mut self, call_result_cache: bool) -> Self {
+        self.call_result_cache = call_result_cache;
+        self
+    }
+
+    /// Sets whether legacy transactions signed without a chain id, i.e. without EIP-155 replay
+    /// protection, are rejected.
+    #[must_use]
+    pub fn with_require_eip155(mut self, require_eip155: bool) -> Self {
+        self.require_eip155 = require_eip155;
+        self
+    }
+
+    /// Sets whether transactions on a forked L2 are charged the L2's L1 data fee on top of the
+    /// execution fee, so that fees match the forked network.
+    ///
+    /// Only OP stack chains are modeled, the L1 fee parameters are read from their `L1Block`
+    /// predeploy. Forking any other chain, e.g. Arbitrum, with this enabled is an error.
+    #[must_use]
+    pub fn with_l2_gas_accounting(mut self, l2_gas_accounting: bool) -> Self {
+        self.l2_gas_accounting = l2_gas_accounting;
+        self
+    }
+
+    /// Sets whether `eth_estimateGas` estimates against an unbounded gas limit instead of the
+    /// block gas limit, e.g. to size transactions for a node with a higher limit.
+    #[must_use]
+    pub fn with_estimate_ignore_block_limit(mut self, estimate_ignore_block_limit: bool) -> Self {
+        self.estimate_ignore_block_limit = estimate_ignore_block_limit;
+        self
+    }
+
+    /// Sets whether `anvil_walletInfo` includes the private keys of the dev wallets.
+    #[must_use]
+    pub fn with_expose_private_keys(mut self, expose_private_keys: bool) -> Self {
+        self.expose_private_keys = expose_private_keys;
+        self
+    }
+
+    /// Sets how many blocks behind the remote head the fork is pinned to when forking latest, so
+    /// the forked state is less likely to be reorged away.
+    #[must_use]
+    pub fn with_fork_confirmation_depth(mut self, fork_confirmation_depth: u64) -> Self {
+        self.fork_confirmation_depth = fork_confirmation_depth;
+        self
+    }
+
+    /// Sets how long sent transactions are held before they become eligible for mining, to
+    /// simulate propagation latency.
+    #[must_use]
+    pub fn with_inclusion_delay(mut self, inclusion_delay: Duration) -> Self {
+        self.inclusion_delay = Some(inclusion_delay);
+        self
+    }
+
+    /// Sets how many `eth_call`s may execute at the same time, further calls are queued until a
+    /// running one finishes. This keeps a burst of calls from flooding the fork endpoint.
+    #[must_use]
+    pub fn with_max_concurrent_calls(mut self, max_concurrent_calls: usize) -> Self {
+        self.max_concurrent_calls = Some(max_concurrent_calls);
+        self
+    }
+
+    /// Sets the highest total fee a transaction may pay, transactions whose worst case fee
+    /// (`gas_limit * max_fee_per_gas`) exceeds it are rejected.
+    #[must_use]
+    pub fn with_max_fee_per_tx(mut self, max_fee_per_tx: U256) -> Self {
+        self.max_fee_per_tx = Some(max_fee_per_tx);
+        self
+    }
+
+    /// Sets the highest number of accounts the state may hold. Requests that would add another
+    /// account once the limit is reached are rejected, and so are transactions that would create
+    /// one when they're mined, which keeps the memory of long running nodes in check.
+    #[must_use]
+    pub fn with_max_state_accounts(mut self, max_state_accounts: usize) -> Self {
+        self.max_state_accounts = Some(max_state_accounts);
+        self
+    }
+
+    /// Sets how the requests made while the fork is set up are retried.
+    ///
+    /// Unlike the retries of the fork provider (see [Self::fork_request_retries]), which only
+    /// retry transport errors the provider deems retryable and apply to every request of the
+    /// node, this retries any failed setup request and a fork block the remote doesn't return yet,
+    /// for example because it hasn't propagated to every node behind a load balanced endpoint. By
+    /// default every setup request is made once.
+    #[must_use]
+    pub fn with_fork_retry_config(mut self, fork_retry_config: RetryConfig) -> Self {
+        self.fork_retry_config = fork_retry_config;
+        self
+    }
+
+    /// Sets a fork cache file, e.g. one written by an earlier run, to preload when the node starts
+    /// so previously fetched state doesn't have to be fetched again.
+    ///
+    /// The file is used as the cache of the initial fork, state fetched by this node is written
+    /// back to it.
+    #[must_use]
+    pub fn with_warm_cache_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.warm_cache_from = Some(path.into());
+        self
+    }
+
+    /// Sets the timestamp to fork at, the fork is pinned to the latest block of the remote chain
+    /// mined at or before it. A timestamp in the future resolves to the latest block.
+    ///
+    /// Ignored if a fork block number or transaction hash is set.
+    #[must_use]
+    pub fn with_fork_timestamp(mut self, fork_timestamp: Option<u64>) -> Self {
+        self.fork_timestamp = fork_timestamp;
+        self
+    }
+
+    /// Sets the endpoint a JSON payload is POSTed to whenever a transaction is added to the pool
+    /// or mined.
+    #[must_use]
+    pub fn with_tx_webhook(mut self, url: Url) -> Self {
+        self.tx_webhook = Some(url);
+        self
+    }
+
+    /// Forks from a state dump in the format `anvil_dumpState` produces instead of a remote
+    /// endpoint, so no RPC is ever contacted.
+    ///
+    /// The dumped state is treated as the state of the forked block, which must be part of the
+    /// dump, and the dumped chain id as the chain id of the forked chain. Reads of data that isn't
+    /// part of the dump, e.g. blocks before the fork, fail.
+    #[must_use]
+    pub fn with_fork_state_file(mut self, path: PathBuf) -> Self {
+        self.fork_state_file = Some(path);
+        self
+    }
+
+    /// Sets whether mined blocks pay the static block reward to the coinbase and the uncle
+    /// rewards to the beneficiaries of included uncles.
+    ///
+    /// Rewards are only paid if the configured hardfork predates the merge.
+    #[must_use]
+    pub fn with_block_rewards(mut self, block_rewards: bool) -> Self {
+        self.block_rewards = block_rewards;
+        self
+    }
+
+    /// Seals every mined block with the given signer the way clique (PoA) does: the `extraData`
+    /// of the block is an empty 32 byte vanity followed by the signer's signature of the header,
+    /// see [EIP-225](https://eips.ethereum.org/EIPS/eip-225).
+    #[must_use]
+    pub fn with_block_signer(mut self, signer: PrivateKeySigner) -> Self {
+        self.block_signer = Some(signer);
+        self
+    }
+
+    /// Returns the address and derivation path of every dev wallet, and its private key if
+    /// exposing private keys is enabled.
+    ///
+    /// The derivation path is only known for wallets derived from the configured mnemonic, or the
+    /// default one.
+    pub fn wallet_info(&self) -> Vec<WalletInfo> {
+        let generator = self.account_generator.clone().unwrap_or_else(|| {
+            AccountGenerator::new(self.signer_accounts.len()).phrase(DEFAULT_MNEMONIC)
+        });
+        let derived = generator.generate().unwrap_or_default();
+        self.signer_accounts
+            .iter()
+            .enumerate()
+            .map(|(idx, wallet)| WalletInfo {
+                address: wallet.address(),
+                derivation_path: derived
+                    .get(idx)
+                    .filter(|derived| derived.address() == wallet.address())
+                    .map(|_| format!("{}{idx}", generator.get_derivation_path())),
+                private_key: self
+                    .expose_private_keys
+                    .then(|| B256::from_slice(&wallet.credential().to_bytes())),
+            })
+            .collect()
+    }
+
     /// Sets the `fork_request_timeout` to use for requests
     #[must_use]
     pub fn fork_request_timeout(mut self, fork_request_timeout: Option<Duration>) -> Self {
@@ -1038,6 +1535,7 @@ pub(crate) async fn setup(&mut self) -> Result<mem::Backend> {
 
         cfg.chain_id = self.get_chain_id();
         cfg.limit_contract_code_size = self.code_size_limit;
+        cfg.limit_contract_initcode_size = self.initcode_size_limit;
         // EIP-3607 rejects transactions from senders with deployed code.
         // If EIP-3607 is enabled it can cause issues during fuzz/invariant tests if the
         // caller is a contract. So we disable the check by default.
@@ -1074,6 +1572,8 @@ pub(crate) async fn setup(&mut self) -> Result<mem::Backend> {
         let (db, fork): (Arc<TokioRwLock<Box<dyn Db>>>, Option<ClientFork>) =
             if let Some(eth_rpc_url) = self.eth_rpc_url.clone() {
                 self.setup_fork_db(eth_rpc_url, &mut env, &fees).await?
+            } else if let Some(path) = self.fork_state_file.clone() {
+                self.setup_fork_db_from_state_file(&path, &mut env, &fees)?
             } else {
                 (Arc::new(TokioRwLock::new(Box::<MemDb>::default())), None)
             };
@@ -1135,13 +1635,22 @@ pub(crate) async fn setup(&mut self) -> Result<mem::Backend> {
 
         // Writes the default create2 deployer to the backend,
         // if the option is not disabled and we are not forking.
-        if !self.disable_default_create2_deployer && self.eth_rpc_url.is_none() {
+        if !self.disable_default_create2_deployer
+            && self.eth_rpc_url.is_none()
+            && self.fork_state_file.is_none()
+        {
             backend
                 .set_create2_deployer(DEFAULT_CREATE2_DEPLOYER)
                 .await
                 .wrap_err("failed to create default create2 deployer")?;
         }
 
+        if let Some(path) = self.state_autosave_load.clone().filter(|path| path.exists()) {
+            let state = SerializableState::load(&path)
+                .wrap_err_with(|| format!("failed to load autosaved state {}", path.display()))?;
+            self.init_state = Some(state);
+        }
+
         if let Some(state) = self.init_state.clone() {
             backend.load_state(state).await.wrap_err("failed to load init state")?;
         }
@@ -1167,6 +1676,93 @@ pub async fn setup_fork_db(
         Ok((db, Some(fork)))
     }
 
+    /// Configures a fork from the state dump at `path` without a remote endpoint:
+    ///  - returning an in-memory database and a [ClientFork] at the dumped block, the dumped
+    ///    accounts are loaded into the database as the init state
+    ///  - modifying some parameters of the passed `env`
+    ///  - mutating some members of `self`
+    pub fn setup_fork_db_from_state_file(
+        &mut self,
+        path: &Path,
+        env: &mut Env,
+        fees: &FeeManager,
+    ) -> Result<(Arc<TokioRwLock<Box<dyn Db>>>, Option<ClientFork>)> {
+        debug!(target: "node", ?path, "setting up fork db from state file");
+        let state = SerializableState::load(path)
+            .wrap_err_with(|| format!("failed to load fork state file {}", path.display()))?;
+        let Some(block) = state.block.clone() else {
+            eyre::bail!("fork state file {} is missing the `block` field", path.display())
+        };
+        let Some(rpc_chain_id) = state.chain_id else {
+            eyre::bail!("fork state file {} is missing the `chain_id` field", path.display())
+        };
+        let block_number = state.best_block_number.unwrap_or(block.number.saturating_to());
+        let Some(block_hash) = state
+            .blocks
+            .iter()
+            .find(|block| block.header.number == block_number)
+            .map(|block| block.header.hash_slow())
+        else {
+            eyre::bail!(
+                "fork state file {} doesn't contain the fork block {block_number}",
+                path.display()
+            )
+        };
+
+        let gas_limit = self.gas_limit.unwrap_or(block.gas_limit);
+        self.gas_limit = Some(gas_limit);
+        if self.base_fee.is_none() {
+            self.base_fee = Some(block.basefee);
+            fees.set_base_fee(block.basefee);
+        }
+        env.evm_env.block_env = BlockEnv { gas_limit, ..block.clone() };
+
+        // there is no remote, requests that would be sent to it fail instead
+        let eth_rpc_url = path.display().to_string();
+        let provider = Arc::new(get_offline_provider(format!(
+            "the fork is loaded from the state file {eth_rpc_url}"
+        )));
+        let chain_id = if let Some(chain_id) = self.chain_id {
+            chain_id
+        } else {
+            // need to update the dev signers and env with the chain id
+            self.set_chain_id(Some(rpc_chain_id));
+            env.evm_env.cfg_env.chain_id = rpc_chain_id;
+            env.tx.base.chain_id = rpc_chain_id.into();
+            rpc_chain_id
+        };
+        let config = ClientForkConfig {
+            eth_rpc_url,
+            block_number,
+            block_hash,
+            transaction_hash: None,
+            provider,
+            chain_id,
+            override_chain_id: self.chain_id,
+            rpc_chain_id,
+            timestamp: block.timestamp.saturating_to(),
+            base_fee: Some(block.basefee as u128),
+            state_root: B256::ZERO,
+            timeout: self.fork_request_timeout,
+            retries: 0,
+            backoff: self.fork_retry_backoff,
+            compute_units_per_second: self.compute_units_per_second,
+            max_concurrent_requests: self.fork_max_concurrent_requests,
+            http2: self.fork_http2,
+            fail_fast_when_offline: false,
+            total_difficulty: U256::ZERO,
+            blob_gas_used: None,
+            blob_excess_gas_and_price: block.blob_excess_gas_and_price,
+            force_transactions: None,
+            chain_specific_env_changes: Vec::new(),
+        };
+
+        let db: Arc<TokioRwLock<Box<dyn Db>>> = Arc::new(TokioRwLock::new(Box::<MemDb>::default()));
+        let fork = ClientFork::new(config, Arc::clone(&db));
+        self.init_state = Some(state);
+        Ok((db, Some(fork)))
+    }
+
     /// Configures everything related to forking based on the passed `eth_rpc_url`:
     ///  - returning a tuple of a [ForkedDatabase] and [ClientForkConfig] which can be used to build
     ///    a [ClientFork] to fork from.
@@ -1179,74 +1775,115 @@ pub async fn setup_fork_db_config(
         fees: &FeeManager,
     ) -> Result<(ForkedDatabase, ClientForkConfig)> {
         debug!(target: "node", ?eth_rpc_url, "setting up fork db");
+        // don't retry requests against an unreachable remote if cached state is served instead
+        let fork_request_retries =
+            if self.offline_fork_fallback { 0 } else { self.fork_request_retries };
+        let retry = if self.offline_fork_fallback {
+            RetryConfig { max_retries: 0, ..self.fork_retry_config }
+        } else {
+            self.fork_retry_config
+        };
         let provider = Arc::new(
             ProviderBuilder::new(&eth_rpc_url)
                 .timeout(self.fork_request_timeout)
                 .initial_backoff(self.fork_retry_backoff.as_millis() as u64)
                 .compute_units_per_second(self.compute_units_per_second)
-                .max_retry(self.fork_request_retries)
+                .max_retry(fork_request_retries)
                 .initial_backoff(1000)
                 .headers(self.fork_headers.clone())
+                .maybe_max_concurrent_requests(self.fork_max_concurrent_requests)
+                .http2(self.fork_http2)
+                .fail_fast_when_offline(self.offline_fork_fallback)
                 .build()
                 .wrap_err("failed to establish provider to fork url")?,
         );
 
-        let (fork_block_number, fork_chain_id, force_transactions) = if let Some(fork_choice) =
-            &self.fork_choice
+        if self.fork_choice.is_none()
+            && let Some(timestamp) = self.fork_timestamp
         {
-            let (fork_block_number, force_transactions) =
-                derive_block_and_transactions(fork_choice, &provider).await.wrap_err(
+            let number = retry
+                .run(|| find_fork_block_by_timestamp(&provider, timestamp))
+                .await
+                .wrap_err("failed to resolve fork block from timestamp")?;
+            self.fork_choice = Some(ForkChoice::Block(number as i128));
+        }
+
+        let (fork_block_number, fork_chain_id, force_transactions) =
+            if let Some(fork_choice) = &self.fork_choice {
+                let derive = || derive_block_and_transactions(fork_choice, &provider);
+                let (fork_block_number, force_transactions) = retry.run(derive).await.wrap_err(
                     "failed to derive fork block number and force transactions from fork choice",
                 )?;
-            let chain_id = if let Some(chain_id) = self.fork_chain_id {
-                Some(chain_id)
-            } else if self.hardfork.is_none() {
-                // Auto-adjust hardfork if not specified, but only if we're forking mainnet.
-                let chain_id =
-                    provider.get_chain_id().await.wrap_err("failed to fetch network chain ID")?;
-                if alloy_chains::NamedChain::Mainnet == chain_id {
-                    let hardfork: EthereumHardfork =
-                        ethereum_hardfork_from_block_tag(fork_block_number);
-
-                    env.evm_env.cfg_env.spec = spec_id_from_ethereum_hardfork(hardfork);
-                    self.hardfork = Some(ChainHardfork::Ethereum(hardfork));
-                }
-                Some(U256::from(chain_id))
+                let chain_id = if let Some(chain_id) = self.fork_chain_id {
+                    Some(chain_id)
+                } else if self.hardfork.is_none() {
+                    // Auto-adjust hardfork if not specified, but only if we're forking mainnet.
+                    let chain_id = retry
+                        .run(|| async { provider.get_chain_id().await })
+                        .await
+                        .wrap_err("failed to fetch network chain ID")?;
+                    if alloy_chains::NamedChain::Mainnet == chain_id {
+                        let hardfork: EthereumHardfork =
+                            ethereum_hardfork_from_block_tag(fork_block_number);
+
+                        env.evm_env.cfg_env.spec = spec_id_from_ethereum_hardfork(hardfork);
+                        self.hardfork = Some(ChainHardfork::Ethereum(hardfork));
+                    }
+                    Some(U256::from(chain_id))
+                } else {
+                    None
+                };
+
+                (fork_block_number, chain_id, force_transactions)
             } else {
-                None
+                let bn = if self.fork_confirmation_depth > 0 {
+                    // stay the configured number of blocks behind the head, which is never pending
+                    let latest = retry
+                        .run(|| async { provider.get_block_number().await })
+                        .await
+                        .wrap_err("failed to get latest block")?;
+                    latest.saturating_sub(self.fork_confirmation_depth)
+                } else {
+                    // pick the last block number but also ensure it's not pending anymore
+                    retry
+                        .run(|| find_latest_fork_block(&provider))
+                        .await
+                        .wrap_err("failed to get fork block number")?
+                };
+                (bn, None, None)
             };
 
-            (fork_block_number, chain_id, force_transactions)
-        } else {
-            // pick the last block number but also ensure it's not pending anymore
-            let bn = find_latest_fork_block(&provider)
-                .await
-                .wrap_err("failed to get fork block number")?;
-            (bn, None, None)
-        };
-
-        let block = provider
-            .get_block(BlockNumberOrTag::Number(fork_block_number).into())
-            .await
-            .wrap_err("failed to get fork block")?;
-
-        let block = if let Some(block) = block {
-            block
-        } else {
-            if let Ok(latest_block) = provider.get_block_number().await {
-                let mut message = format!(
-                    "Failed to get block for block number: {fork_block_number}\n\
+        // a missing block is retried as well since it may not have propagated to every node behind
+        // the endpoint yet
+        let block = retry
+            .run(|| async {
+                provider
+                    .get_block(BlockNumberOrTag::Number(fork_block_number).into())
+                    .await
+                    .map_err(Some)
+                    .and_then(|block| block.ok_or(None))
+            })
+            .await;
+
+        let block = match block {
+            Ok(block) => block,
+            Err(Some(err)) => return Err(err).wrap_err("failed to get fork block"),
+            Err(None) => {
+                if let Ok(latest_block) = provider.get_block_number().await {
+                    let mut message = format!(
+                        "Failed to get block for block number: {fork_block_number}\n\
 latest block number: {latest_block}"
-                );
-                // If the `eth_getBlockByNumber` call succeeds, but returns null instead of
-                // the block, and the block number is less than equal the latest block, then
-                // the user is forking from a non-archive node with an older block number.
-                if fork_block_number <= latest_block {
-                    message.push_str(&format!("\n{NON_ARCHIVE_NODE_WARNING}"));
+                    );
+                    // If the `eth_getBlockByNumber` call succeeds, but returns null instead of
+                    // the block, and the block number is less than equal the latest block, then
+                    // the user is forking from a non-archive node with an older block number.
+                    if fork_block_number <= latest_block {
+                        message.push_str(&format!("\n{NON_ARCHIVE_NODE_WARNING}"));
+                    }
+                    eyre::bail!("{message}");
                 }
-                eyre::bail!("{message}");
+                eyre::bail!("failed to get block for block number: {fork_block_number}")
             }
-            eyre::bail!("failed to get block for block number: {fork_block_number}")
         };
 
         let gas_limit = self.fork_gas_limit(&block);
@@ -1308,7 +1945,7 @@ pub async fn setup_fork_db_config(
 
         // use remote gas price
         if self.gas_price.is_none()
-            && let Ok(gas_price) = provider.get_gas_price().await
+            && let Ok(gas_price) = retry.run(|| async { provider.get_gas_price().await }).await
         {
             self.gas_price = Some(gas_price);
             fees.set_gas_price(gas_price);
@@ -1316,30 +1953,59 @@ pub async fn setup_fork_db_config(
 
         let block_hash = block.header.hash;
 
+        // the chain id of the remote, which may differ from the configured one
+        let rpc_chain_id = if let Some(fork_chain_id) = fork_chain_id {
+            fork_chain_id.to()
+        } else {
+            retry
+                .run(|| async { provider.get_chain_id().await })
+                .await
+                .wrap_err("failed to fetch network chain ID")?
+        };
         let chain_id = if let Some(chain_id) = self.chain_id {
             chain_id
         } else {
-            let chain_id = if let Some(fork_chain_id) = fork_chain_id {
-                fork_chain_id.to()
-            } else {
-                provider.get_chain_id().await.wrap_err("failed to fetch network chain ID")?
-            };
-
             // need to update the dev signers and env with the chain id
-            self.set_chain_id(Some(chain_id));
-            env.evm_env.cfg_env.chain_id = chain_id;
-            env.tx.base.chain_id = chain_id.into();
-            chain_id
+            self.set_chain_id(Some(rpc_chain_id));
+            env.evm_env.cfg_env.chain_id = rpc_chain_id;
+            env.tx.base.chain_id = rpc_chain_id.into();
+            rpc_chain_id
         };
         let override_chain_id = self.chain_id;
-        // apply changes such as difficulty -> prevrandao and chain specifics for current chain id
-        apply_chain_and_block_specific_env_changes::<AnyNetwork>(env.as_env_mut(), &block);
+        // OP stack chains charge an L1 data fee which is modeled by the optimism EVM, other L2s
+        // such as Arbitrum price their L1 data differently and aren't supported
+        if self.l2_gas_accounting {
+            if !alloy_chains::NamedChain::try_from(rpc_chain_id)
+                .is_ok_and(|chain| chain.is_optimism())
+            {
+                eyre::bail!(
+                    "L2 gas accounting is only supported on OP stack chains, not on chain {rpc_chain_id}"
+                );
+            }
+            env.is_optimism = true;
+        }
+        // apply changes such as difficulty -> prevrandao and chain specifics, which are those of
+        // the remote chain even if the chain id is overridden
+        let chain_specific_env_changes = if self.disable_chain_specific_env {
+            vec![]
+        } else {
+            let block_env = env.evm_env.block_env.clone();
+            apply_chain_and_block_specific_env_changes_for_chain::<AnyNetwork>(
+                rpc_chain_id,
+                env.as_env_mut(),
+                &block,
+            );
+            changed_block_env_fields(&block_env, &env.evm_env.block_env)
+        };
 
         let meta = BlockchainDbMeta::new(env.evm_env.block_env.clone(), eth_rpc_url.clone());
+        // the warm cache only applies to the fork the node starts with, not to later resets
+        let cache_path =
+            self.warm_cache_from.take().or_else(|| self.block_cache_path(fork_block_number));
         let block_chain_db = if self.fork_chain_id.is_some() {
-            BlockchainDb::new_skip_check(meta, self.block_cache_path(fork_block_number))
+            BlockchainDb::new_skip_check(meta, cache_path)
         } else {
-            BlockchainDb::new(meta, self.block_cache_path(fork_block_number))
+            BlockchainDb::new(meta, cache_path)
         };
 
         // This will spawn the background thread that will use the provider to fetch
@@ -1359,16 +2025,22 @@ pub async fn setup_fork_db_config(
             provider,
             chain_id,
             override_chain_id,
+            rpc_chain_id,
             timestamp: block.header.timestamp,
             base_fee: block.header.base_fee_per_gas.map(|g| g as u128),
+            state_root: block.header.state_root,
             timeout: self.fork_request_timeout,
-            retries: self.fork_request_retries,
+            retries: fork_request_retries,
             backoff: self.fork_retry_backoff,
             compute_units_per_second: self.compute_units_per_second,
+            max_concurrent_requests: self.fork_max_concurrent_requests,
+            http2: self.fork_http2,
+            fail_fast_when_offline: self.offline_fork_fallback,
             total_difficulty: block.header.total_difficulty.unwrap_or_default(),
             blob_gas_used: block.header.blob_gas_used.map(|g| g as u128),
             blob_excess_gas_and_price: env.evm_env.block_env.blob_excess_gas_and_price,
             force_transactions,
+            chain_specific_env_changes,
         };
 
         debug!(target: "node", fork_number=config.block_number, fork_hash=%config.block_hash, "set up fork db");
@@ -1608,6 +2280,21 @@ pub fn anvil_tmp_dir() -> Option<PathBuf> {
     anvil_dir().map(|p| p.join("tmp"))
 }
 
+/// Returns the names of the fields that differ between the two block environments
+fn changed_block_env_fields(before: &BlockEnv, after: &BlockEnv) -> Vec<String> {
+    let mut changed = vec![];
+    if before.number != after.number {
+        changed.push("number".to_string());
+    }
+    if before.difficulty != after.difficulty {
+        changed.push("difficulty".to_string());
+    }
+    if before.prevrandao != after.prevrandao {
+        changed.push("prevrandao".to_string());
+    }
+    changed
+}
+
 /// Finds the latest appropriate block to fork
 ///
 /// This fetches the "latest" block and checks whether the `Block` is fully populated (`hash` field
@@ -1632,6 +2319,43 @@ async fn find_latest_fork_block<P: Provider<AnyNetwork>>(
     Ok(num)
 }
 
+/// Finds the latest block of the remote chain that was mined at or before the timestamp, by
+/// binary searching the blocks of the chain.
+async fn find_fork_block_by_timestamp<P: Provider<AnyNetwork>>(
+    provider: P,
+    timestamp: u64,
+) -> Result<u64> {
+    async fn block_timestamp<P: Provider<AnyNetwork>>(provider: &P, number: u64) -> Result<u64> {
+        let block = provider.get_block(number.into()).await?;
+        block
+            .map(|block| block.header.timestamp)
+            .ok_or_else(|| eyre::eyre!("failed to get block for block number: {number}"))
+    }
+
+    let latest = find_latest_fork_block(&provider).await?;
+    if block_timestamp(&provider, latest).await? <= timestamp {
+        return Ok(latest);
+    }
+    let genesis_timestamp = block_timestamp(&provider, 0).await?;
+    if genesis_timestamp > timestamp {
+        eyre::bail!(
+            "fork timestamp {timestamp} is before the genesis of the remote chain at {genesis_timestamp}"
+        );
+    }
+
+    // the block at `low` was mined at or before the timestamp, the one at `high` after it
+    let (mut low, mut high) = (0, latest);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if block_timestamp(&provider, mid).await? <= timestamp {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,13 +9,16 @@
             self,
             db::SerializableState,
             mem::{MIN_CREATE_GAS, MIN_TRANSACTION_GAS},
-            notifications::NewBlockNotifications,
+            notifications::{InvariantViolationNotifications, NewBlockNotifications},
             validate::TransactionValidator,
         },
         error::{
             BlockchainError, FeeHistoryError, InvalidTransactionError, Result, ToRpcResponseResult,
         },
-        fees::{FeeDetails, FeeHistoryCache, MIN_SUGGESTED_PRIORITY_FEE},
+        fees::{
+            FeeDetails, FeeHistoryCache, GasPriceOracle, MIN_SUGGESTED_PRIORITY_FEE,
+            calculate_gas_target,
+        },
         macros::node_info,
         miner::FixedBlockTimeMiner,
         pool::{
@@ -27,21 +30,25 @@
         sign::{self, Signer},
     },
     filter::{EthFilter, Filters, LogsFilter},
+    hardfork::uncle_reward,
     mem::transaction_build,
 };
+use alloy_chains::NamedChain;
 use alloy_consensus::{
-    Account, Blob,
+    Account, Blob, EMPTY_ROOT_HASH, Transaction as _,
     transaction::{Recovered, eip4844::TxEip4844Variant},
 };
-use alloy_dyn_abi::TypedData;
-use alloy_eips::eip2718::Encodable2718;
+use alloy_dyn_abi::{EventExt, TypedData};
+use alloy_eips::{eip2718::Encodable2718, eip7702::constants::EIP7702_DELEGATION_DESIGNATOR};
 use alloy_evm::overrides::{OverrideBlockHashes, apply_state_overrides};
+use alloy_json_abi::JsonAbi;
 use alloy_network::{
     AnyRpcBlock, AnyRpcTransaction, BlockResponse, Ethereum, NetworkWallet, TransactionBuilder,
     TransactionResponse, eip2718::Decodable2718,
 };
 use alloy_primitives::{
-    Address, B64, B256, Bytes, Signature, TxHash, TxKind, U64, U256,
+    Address, B64, B256, BloomInput, Bytes, I256, Selector, Signature, TxHash, TxKind, U64, U256,
+    b256, hex, keccak256,
     map::{HashMap, HashSet},
 };
 use alloy_provider::utils::{
@@ -49,18 +56,19 @@
     eip1559_default_estimator,
 };
 use alloy_rpc_types::{
-    AccessList, AccessListResult, BlockId, BlockNumberOrTag as BlockNumber, BlockTransactions,
-    EIP1186AccountProofResponse, FeeHistory, Filter, FilteredParams, Index, Log, Work,
+    AccessList, AccessListResult, BlockId, BlockNumberOrTag as BlockNumber, BlockOverrides,
+    BlockTransactions, EIP1186AccountProofResponse, FeeHistory, Filter, FilteredParams, Index, Log,
+    Work,
     anvil::{
         ForkedNetwork, Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
     },
     request::TransactionRequest,
     simulate::{SimulatePayload, SimulatedBlock},
-    state::{AccountOverride, EvmOverrides, StateOverridesBuilder},
+    state::{AccountOverride, EvmOverrides, StateOverride, StateOverridesBuilder},
     trace::{
         filter::TraceFilter,
         geth::{GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace},
-        parity::LocalizedTransactionTrace,
+        parity::{Action, CreateAction, LocalizedTransactionTrace, TraceOutput},
     },
     txpool::{TxpoolContent, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
 };
@@ -70,23 +78,30 @@
 use anvil_core::{
     eth::{
         EthRequest,
-        block::BlockInfo,
+        block::{BlockInfo, clique_signer},
         transaction::{
             PendingTransaction, ReceiptResponse, TypedTransaction, TypedTransactionRequest,
             transaction_request_to_typed,
         },
         wallet::{WalletCapabilities, WalletError},
     },
-    types::{ReorgOptions, TransactionData},
+    types::{
+        AccountKind, AccountType, AnvilNodeInfo, BaseFeeExplanation, BlockTimeStats, DeployResult,
+        DifficultyHistoryEntry, EffectiveFee, ForkedChainGenesis, GasByOpcode, GasEstimateRange,
+        InvariantViolation, MemoryUsage, OverrideScope, ReceiptWithTrace, ReorgHistoryEntry,
+        ReorgOptions, ReplayedTransaction, ResetOptions, SlotHistoryEntry, StorageChanges,
+        StorageType, StorageValue, TransactionData, TransactionDependency,
+        TransactionHashOrRequest, TransactionIndex, WalletInfo,
+    },
 };
 use anvil_rpc::{error::RpcError, response::ResponseResult};
-use foundry_common::provider::ProviderBuilder;
+use foundry_common::{fmt::format_token_raw, provider::ProviderBuilder};
 use foundry_evm::decode::RevertDecoder;
 use futures::{
     StreamExt,
     channel::{mpsc::Receiver, oneshot},
 };
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use revm::{
     bytecode::Bytecode,
     context::BlockEnv,
@@ -95,15 +110,35 @@
     interpreter::{InstructionResult, return_ok, return_revert},
     primitives::eip7702::PER_EMPTY_ACCOUNT_COST,
 };
-use std::{sync::Arc, time::Duration};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use tokio::{
-    sync::mpsc::{UnboundedReceiver, unbounded_channel},
+    sync::{
+        Semaphore,
+        mpsc::{UnboundedReceiver, unbounded_channel},
+    },
     try_join,
 };
 
 /// The client version: `anvil/v{major}.{minor}.{patch}`
 pub const CLIENT_VERSION: &str = concat!("anvil/v", env!("CARGO_PKG_VERSION"));
 
+/// The EIP-1967 storage slot holding the implementation address of a proxy:
+/// `bytes32(uint256(keccak256('eip1967.proxy.implementation')) - 1)`
+pub const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// Code of an EIP-1167 minimal proxy preceding the implementation address
+const EIP1167_PREFIX: [u8; 10] = hex!("363d3d373d3d3d363d73");
+
+/// Code of an EIP-1167 minimal proxy following the implementation address
+const EIP1167_SUFFIX: [u8; 15] = hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// The number of recent blocks considered when suggesting a priority fee
+const MAX_TIP_SUGGESTION_BLOCKS: u64 = 20;
+
+/// The maximum number of `eth_call` results kept in the call result cache
+const MAX_CALL_RESULT_CACHE_SIZE: usize = 1024;
+
 /// The entry point for executing eth api RPC call - The Eth RPC interface.
 ///
 /// This type is cheap to clone and can be used concurrently
@@ -137,6 +172,23 @@ pub struct EthApi {
     net_listening: bool,
     /// The instance ID. Changes on every reset.
     instance_id: Arc<RwLock<B256>>,
+    /// Whether sent transactions are mined right away in a block of their own
+    mine_on_send: bool,
+    /// The EIP-2718 transaction types accepted by this node, all if unset
+    allowed_tx_types: Option<Vec<u8>>,
+    /// How `eth_gasPrice` computes its suggestion
+    gas_price_oracle: GasPriceOracle,
+    /// Results of `eth_call`s keyed by the call, with the backend's state version they were
+    /// computed at
+    call_result_cache: Option<Arc<Mutex<HashMap<String, (u64, Bytes)>>>>,
+    /// Whether gas estimation ignores the block gas limit
+    estimate_ignore_block_limit: bool,
+    /// How long sent transactions are held before they're added to the pool
+    inclusion_delay: Option<Duration>,
+    /// Bounds how many `eth_call`s execute at the same time, unbounded if unset
+    call_permits: Option<Arc<Semaphore>>,
+    /// Balance and code overrides applied via the `anvil_` methods since the last reset
+    local_overrides: Arc<Mutex<LocalOverrides>>,
 }
 
 impl EthApi {
@@ -166,9 +218,59 @@ pub fn new(
             net_listening: true,
             transaction_order: Arc::new(RwLock::new(transactions_order)),
             instance_id: Arc::new(RwLock::new(B256::random())),
+            mine_on_send: false,
+            allowed_tx_types: None,
+            gas_price_oracle: GasPriceOracle::default(),
+            call_result_cache: None,
+            estimate_ignore_block_limit: false,
+            inclusion_delay: None,
+            call_permits: None,
+            local_overrides: Default::default(),
         }
     }
 
+    /// Sets whether sent transactions are mined right away in a block of their own
+    pub fn with_mine_on_send(mut self, mine_on_send: bool) -> Self {
+        self.mine_on_send = mine_on_send;
+        self
+    }
+
+    /// Sets the EIP-2718 transaction types accepted by this node
+    pub fn with_allowed_tx_types(mut self, allowed_tx_types: Option<Vec<u8>>) -> Self {
+        self.allowed_tx_types = allowed_tx_types;
+        self
+    }
+
+    /// Sets how `eth_gasPrice` computes its suggestion
+    pub fn with_gas_price_oracle(mut self, gas_price_oracle: GasPriceOracle) -> Self {
+        self.gas_price_oracle = gas_price_oracle;
+        self
+    }
+
+    /// Sets whether `eth_call` results are cached until the next block or state change
+    pub fn with_call_result_cache(mut self, call_result_cache: bool) -> Self {
+        self.call_result_cache = call_result_cache.then(Default::default);
+        self
+    }
+
+    /// Sets whether gas estimation ignores the block gas limit
+    pub fn with_estimate_ignore_block_limit(mut self, estimate_ignore_block_limit: bool) -> Self {
+        self.estimate_ignore_block_limit = estimate_ignore_block_limit;
+        self
+    }
+
+    /// Sets how long sent transactions are held before they're added to the pool
+    pub fn with_inclusion_delay(mut self, inclusion_delay: Option<Duration>) -> Self {
+        self.inclusion_delay = inclusion_delay;
+        self
+    }
+
+    /// Sets how many `eth_call`s may execute at the same time, further calls wait for a slot
+    pub fn with_max_concurrent_calls(mut self, max_concurrent_calls: Option<usize>) -> Self {
+        self.call_permits = max_concurrent_calls.map(|max| Arc::new(Semaphore::new(max)));
+        self
+    }
+
     /// Executes the [EthRequest] and returns an RPC [ResponseResult].
     pub async fn execute(&self, request: EthRequest) -> ResponseResult {
         trace!(target: "rpc::api", "executing eth request");
@@ -198,7 +300,7 @@ pub async fn execute(&self, request: EthRequest) -> ResponseResult {
             EthRequest::NetListening(_) => self.net_listening().to_rpc_result(),
             EthRequest::EthGasPrice(_) => self.eth_gas_price().to_rpc_result(),
             EthRequest::EthMaxPriorityFeePerGas(_) => {
-                self.gas_max_priority_fee_per_gas().to_rpc_result()
+                self.gas_max_priority_fee_per_gas().await.to_rpc_result()
             }
             EthRequest::EthBlobBaseFee(_) => self.blob_base_fee().to_rpc_result(),
             EthRequest::EthAccounts(_) => self.accounts().to_rpc_result(),
@@ -331,9 +433,10 @@ pub async fn execute(&self, request: EthRequest) -> ResponseResult {
                 self.debug_trace_transaction(tx, opts).await.to_rpc_result()
             }
             // non eth-standard rpc calls
-            EthRequest::DebugTraceCall(tx, block, opts) => {
-                self.debug_trace_call(tx, block, opts).await.to_rpc_result()
-            }
+            EthRequest::DebugTraceCall(tx, block, opts) => self
+                .debug_trace_call(tx, block, EvmOverrides::default(), opts)
+                .await
+                .to_rpc_result(),
             EthRequest::DebugCodeByHash(hash, block) => {
                 self.debug_code_by_hash(hash, block).await.to_rpc_result()
             }
@@ -382,6 +485,10 @@ pub async fn execute(&self, request: EthRequest) -> ResponseResult {
                 .anvil_set_erc20_allowance(owner, spender, token_addr, val)
                 .await
                 .to_rpc_result(),
+            EthRequest::DealERC20AndApprove(holder, token_addr, val, spender) => self
+                .anvil_deal_erc20_and_approve(holder, token_addr, val, spender)
+                .await
+                .to_rpc_result(),
             EthRequest::SetCode(addr, code) => {
                 self.anvil_set_code(addr, code).await.to_rpc_result()
             }
@@ -501,6 +608,166 @@ pub async fn execute(&self, request: EthRequest) -> ResponseResult {
                 self.anvil_reorg(reorg_options).await.to_rpc_result()
             }
             EthRequest::Rollback(depth) => self.anvil_rollback(depth).await.to_rpc_result(),
+            EthRequest::ReorgHistory(()) => self.anvil_reorg_history().to_rpc_result(),
+            EthRequest::TxReplacements(address, nonce) => {
+                self.anvil_tx_replacements(address, nonce).to_rpc_result()
+            }
+            EthRequest::ContractInfo(address) => {
+                self.anvil_contract_info(address).await.to_rpc_result()
+            }
+            EthRequest::InclusionEstimate(hash) => {
+                self.anvil_inclusion_estimate(hash).to_rpc_result()
+            }
+            EthRequest::DecodeReceiptLogs(hash, abi) => {
+                self.anvil_decode_receipt_logs(hash, abi).await.to_rpc_result()
+            }
+            EthRequest::EstimateGasRange(call, block) => {
+                self.anvil_estimate_gas_range(call, block).await.to_rpc_result()
+            }
+            EthRequest::UncleReward(block, index) => {
+                self.anvil_uncle_reward(block, index).await.to_rpc_result()
+            }
+            EthRequest::InjectUncle(beneficiary, number) => {
+                self.anvil_inject_uncle(beneficiary, number).to_rpc_result()
+            }
+            EthRequest::CallNoGas(call, block, state_override, block_overrides) => self
+                .anvil_call_no_gas(call, block, EvmOverrides::new(state_override, block_overrides))
+                .await
+                .to_rpc_result(),
+            EthRequest::BlockAuthor(block) => self.anvil_block_author(block).await.to_rpc_result(),
+            EthRequest::GasByOpcode(hash) => self.anvil_gas_by_opcode(hash).to_rpc_result(),
+            EthRequest::StateAtTransaction(block, index, address) => {
+                self.anvil_state_at_transaction(block, index, address).await.to_rpc_result()
+            }
+            EthRequest::SetCallDenylist(addresses) => {
+                self.anvil_set_call_denylist(addresses).to_rpc_result()
+            }
+            EthRequest::ForkBaseBlock(()) => self.anvil_fork_base_block().to_rpc_result(),
+            EthRequest::BalanceDiff(hash) => self.anvil_balance_diff(hash).to_rpc_result(),
+            EthRequest::OrphanedTransactions(()) => {
+                self.anvil_orphaned_transactions().to_rpc_result()
+            }
+            EthRequest::NextBaseFee(()) => self.anvil_next_base_fee().to_rpc_result(),
+            EthRequest::SetInitcodeSizeLimit(limit) => {
+                self.anvil_set_initcode_size_limit(limit).to_rpc_result()
+            }
+            EthRequest::Balances(addresses, block) => {
+                self.anvil_balances(addresses, block).await.to_rpc_result()
+            }
+            EthRequest::BlockRlp(block) => self.anvil_block_rlp(block).await.to_rpc_result(),
+            EthRequest::CodeAt(address, block) => {
+                self.anvil_code_at(address, block).await.to_rpc_result()
+            }
+            EthRequest::TxCountInRange(from, to) => {
+                self.anvil_tx_count_in_range(from, to).await.to_rpc_result()
+            }
+            EthRequest::CallWithCoinbase(call, coinbase, block, state_override) => self
+                .anvil_call_with_coinbase(call, coinbase, block, state_override)
+                .await
+                .to_rpc_result(),
+            EthRequest::TxsBySelector(selector, from, to) => {
+                self.anvil_txs_by_selector(selector, from, to).await.to_rpc_result()
+            }
+            EthRequest::StorageChangesSince(address, cursor) => {
+                self.anvil_storage_changes_since(address, cursor).await.to_rpc_result()
+            }
+            EthRequest::BloomMatches(block, address, topics) => {
+                self.anvil_bloom_matches(block, address, topics).await.to_rpc_result()
+            }
+            EthRequest::ResetNonceForDeployment(deployer, nonce) => {
+                self.anvil_reset_nonce_for_deployment(deployer, nonce).await.to_rpc_result()
+            }
+            EthRequest::AccountProof(address, block) => {
+                self.anvil_account_proof(address, block).await.to_rpc_result()
+            }
+            EthRequest::BlockTimeStats(count) => self.anvil_block_time_stats(count).to_rpc_result(),
+            EthRequest::SetInvariantCheck(address, selector) => {
+                self.anvil_set_invariant_check(address, selector).to_rpc_result()
+            }
+            EthRequest::ClearInvariantCheck(()) => {
+                self.anvil_clear_invariant_check().to_rpc_result()
+            }
+            EthRequest::InvariantViolation(()) => self.anvil_invariant_violation().to_rpc_result(),
+            EthRequest::ReceiptWithTrace(hash, opts) => {
+                self.anvil_receipt_with_trace(hash, opts).await.to_rpc_result()
+            }
+            EthRequest::DecodeStorage(address, slots) => {
+                self.anvil_decode_storage(address, slots).await.to_rpc_result()
+            }
+            EthRequest::ForkedChainGenesis(()) => {
+                self.anvil_forked_chain_genesis().await.to_rpc_result()
+            }
+            EthRequest::SlotHistory(address, slot, from, to) => {
+                self.anvil_slot_history(address, slot, from, to).await.to_rpc_result()
+            }
+            EthRequest::BlockGasLimit(number) => {
+                self.anvil_block_gas_limit(number).await.to_rpc_result()
+            }
+            EthRequest::TxIndex(hash) => self.anvil_tx_index(hash).await.to_rpc_result(),
+            EthRequest::WalletInfo(()) => self.anvil_wallet_info().await.to_rpc_result(),
+            EthRequest::MineToTargetGas(target_gas) => {
+                self.anvil_mine_to_target_gas(target_gas).await.to_rpc_result()
+            }
+            EthRequest::TxDependencies(transactions) => {
+                self.anvil_tx_dependencies(transactions).await.to_rpc_result()
+            }
+            EthRequest::SetOverrideScope(scope) => {
+                self.anvil_set_override_scope(scope).await.to_rpc_result()
+            }
+            EthRequest::RemoteBlockNumber(()) => {
+                self.anvil_remote_block_number().await.to_rpc_result()
+            }
+            EthRequest::CreationCode(address) => {
+                self.anvil_creation_code(address).await.to_rpc_result()
+            }
+            EthRequest::SetTransactionGasCap(cap) => {
+                self.anvil_set_transaction_gas_cap(cap).await.to_rpc_result()
+            }
+            EthRequest::BalanceAtTimestamp(address, timestamp) => {
+                self.anvil_balance_at_timestamp(address, timestamp).await.to_rpc_result()
+            }
+            EthRequest::PredictCreate2Address(deployer, salt, init_code_hash) => {
+                self.anvil_predict_create2_address(deployer, salt, init_code_hash).to_rpc_result()
+            }
+            EthRequest::MemoryUsage(()) => self.anvil_memory_usage().await.to_rpc_result(),
+            EthRequest::ReceiptByBlockAndIndex(block, index) => {
+                self.anvil_receipt_by_block_and_index(block, index).await.to_rpc_result()
+            }
+            EthRequest::SpecAtBlock(number) => self.anvil_spec_at_block(number).to_rpc_result(),
+            EthRequest::StorageRoot(address, block) => {
+                self.anvil_storage_root(address, block).await.to_rpc_result()
+            }
+            EthRequest::ReplayBlock(number, overrides) => {
+                self.anvil_replay_block(number, overrides).await.to_rpc_result()
+            }
+            EthRequest::SetStorageAtRange(address, start_slot, values) => {
+                self.anvil_set_storage_at_range(address, start_slot, values).await.to_rpc_result()
+            }
+            EthRequest::EffectiveFee(hash) => self.anvil_effective_fee(hash).await.to_rpc_result(),
+            EthRequest::ResetWithOptions(forking, options) => {
+                self.anvil_reset_with_options(forking, options).await.to_rpc_result()
+            }
+            EthRequest::DifficultyHistory(from, to) => {
+                self.anvil_difficulty_history(from, to).await.to_rpc_result()
+            }
+            EthRequest::DealERC721(owner, token_addr, token_id) => {
+                self.anvil_deal_erc721(owner, token_addr, token_id).await.to_rpc_result()
+            }
+            EthRequest::AccountType(address) => {
+                self.anvil_account_type(address).await.to_rpc_result()
+            }
+            EthRequest::SetBlockNumberOffset(offset) => {
+                self.anvil_set_block_number_offset(offset).to_rpc_result()
+            }
+            EthRequest::LogsByTopic0(topic0, from_block, to_block) => {
+                self.anvil_logs_by_topic0(topic0, from_block, to_block).await.to_rpc_result()
+            }
+            EthRequest::DeployAllowRevert(init_code, value) => {
+                self.anvil_deploy_allow_revert(init_code, value).await.to_rpc_result()
+            }
+            EthRequest::BaseFeeExplain(block) => {
+                self.anvil_base_fee_explain(block).await.to_rpc_result()
+            }
             EthRequest::WalletGetCapabilities(()) => self.get_capabilities().to_rpc_result(),
             EthRequest::WalletSendTransaction(tx) => {
                 self.wallet_send_transaction(*tx).await.to_rpc_result()
@@ -654,6 +921,11 @@ fn eth_gas_price(&self) -> Result<U256> {
 
     /// Returns the current gas price
     pub fn gas_price(&self) -> u128 {
+        if self.gas_price_oracle == GasPriceOracle::Dynamic
+            && let Some(gas_price) = self.recent_gas_price_suggestion()
+        {
+            return gas_price;
+        }
         if self.backend.is_eip1559() {
             if self.backend.is_min_priority_fee_enforced() {
                 (self.backend.base_fee() as u128).saturating_add(self.lowest_suggestion_tip())
@@ -674,8 +946,8 @@ pub fn excess_blob_gas_and_price(&self) -> Result<Option<BlobExcessGasAndPrice>>
     /// 'tip', to get a transaction included in the current block.
     ///
     /// Handler for ETH RPC call: `eth_maxPriorityFeePerGas`
-    pub fn gas_max_priority_fee_per_gas(&self) -> Result<U256> {
-        self.max_priority_fee_per_gas()
+    pub async fn gas_max_priority_fee_per_gas(&self) -> Result<U256> {
+        self.max_priority_fee_per_gas().await
     }
 
     /// Returns the base fee per blob required to send a EIP-4844 tx.
@@ -941,13 +1213,29 @@ pub async fn get_proof(
         node_info!("eth_getProof");
         let block_request = self.block_request(block_number).await?;
 
-        // If we're in forking mode, or still on the forked block (no blocks mined yet) then we can
-        // delegate the call.
-        if let BlockRequest::Number(number) = block_request
-            && let Some(fork) = self.get_fork()
-            && fork.predates_fork_inclusive(number)
-        {
-            return Ok(fork.get_proof(address, keys, Some(number.into())).await?);
+        if let Some(fork) = self.get_fork() {
+            // blocks before the fork can't have been modified locally, so we can delegate the call
+            if let BlockRequest::Number(number) = block_request
+                && fork.predates_fork(number)
+            {
+                return Ok(fork.get_proof(address, keys, Some(number.into())).await?);
+            }
+
+            // the upstream proof at the pinned fork block is only valid for accounts that weren't
+            // touched locally, the proofs of accounts in the local overlay are synthesized below
+            // and verify against its own storage root. If upstream can't serve the proof, e.g.
+            // because it doesn't keep the state of the fork block anymore, it's synthesized
+            // locally as well
+            if self
+                .backend
+                .local_storage_root(address, Some(block_request.clone()))
+                .await?
+                .is_none()
+                && let Ok(proof) =
+                    fork.get_proof(address, keys.clone(), Some(fork.block_number().into())).await
+            {
+                return Ok(proof);
+            }
         }
 
         let proof = self.backend.prove_account_at(address, keys, Some(block_request)).await?;
@@ -1072,7 +1360,9 @@ pub async fn send_transaction(
         let provides = vec![to_marker(nonce, from)];
         debug_assert!(requires != provides);
 
-        self.add_pending_transaction(pending_transaction, requires, provides)
+        let hash = self.add_pending_transaction(pending_transaction, requires, provides)?;
+        self.mine_sent_transaction(hash).await;
+        Ok(hash)
     }
 
     /// Waits for a transaction to be included in a block and returns its receipt (no timeout).
@@ -1146,18 +1436,11 @@ pub async fn send_raw_transaction(&self, tx: Bytes) -> Result<TxHash> {
         let from = *pending_transaction.sender();
         let nonce = pending_transaction.transaction.nonce();
         let requires = required_marker(nonce, on_chain_nonce, from);
+        let provides = vec![to_marker(nonce, from)];
 
-        let priority = self.transaction_priority(&pending_transaction.transaction);
-        let pool_transaction = PoolTransaction {
-            requires,
-            provides: vec![to_marker(nonce, *pending_transaction.sender())],
-            pending_transaction,
-            priority,
-        };
-
-        let tx = self.pool.add_transaction(pool_transaction)?;
-        trace!(target: "node", "Added transaction: [{:?}] sender={:?}", tx.hash(), from);
-        Ok(*tx.hash())
+        let hash = self.add_pending_transaction(pending_transaction, requires, provides)?;
+        self.mine_sent_transaction(hash).await;
+        Ok(hash)
     }
 
     /// Sends signed transaction, returning its receipt.
@@ -1182,6 +1465,51 @@ pub async fn call(
         overrides: EvmOverrides,
     ) -> Result<Bytes> {
         node_info!("eth_call");
+        let cache_key = self
+            .call_result_cache
+            .as_ref()
+            .filter(|_| !overrides.has_state() && !overrides.has_block())
+            .and_then(|_| serde_json::to_string(&(&request, block_number)).ok());
+        if let Some(key) = &cache_key
+            && let Some(out) = self.cached_call_result(key)
+        {
+            return Ok(out);
+        }
+        // wait for a slot if the configured number of calls is already executing
+        let _permit = match &self.call_permits {
+            Some(permits) => Some(
+                permits
+                    .acquire()
+                    .await
+                    .map_err(|err| BlockchainError::Internal(err.to_string()))?,
+            ),
+            None => None,
+        };
+        let state_version = self.backend.state_version();
+        let out = self.call_uncached(request, block_number, overrides).await?;
+        if let (Some(cache), Some(key)) = (&self.call_result_cache, cache_key) {
+            let mut cache = cache.lock();
+            if cache.len() >= MAX_CALL_RESULT_CACHE_SIZE {
+                cache.clear();
+            }
+            cache.insert(key, (state_version, out.clone()));
+        }
+        Ok(out)
+    }
+
+    /// Returns the cached result of the call, if the state didn't change since it was computed
+    fn cached_call_result(&self, key: &str) -> Option<Bytes> {
+        let cache = self.call_result_cache.as_ref()?.lock();
+        let (state_version, out) = cache.get(key)?;
+        (*state_version == self.backend.state_version()).then(|| out.clone())
+    }
+
+    async fn call_uncached(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        block_number: Option<BlockId>,
+        overrides: EvmOverrides,
+    ) -> Result<Bytes> {
         let block_request = self.block_request(block_number).await?;
         // check if the number predates the fork, if in fork mode
         if let BlockRequest::Number(number) = block_request
@@ -1316,7 +1644,7 @@ pub async fn estimate_gas(
             overrides,
         )
         .await
-        .map(U256::from)
+        .map(|range| U256::from(range.estimate))
     }
 
     /// Handler for RPC call: `anvil_getBlobByHash`
@@ -1422,8 +1750,7 @@ pub async fn uncle_by_block_hash_and_index(
         {
             return Ok(fork.uncle_by_block_hash_and_index(block_hash, idx.into()).await?);
         }
-        // It's impossible to have uncles outside of fork mode
-        Ok(None)
+        Ok(self.backend.mined_uncle(block_hash, idx.into()))
     }
 
     /// Returns an uncles at given block and index.
@@ -1441,8 +1768,7 @@ pub async fn uncle_by_block_number_and_index(
         {
             return Ok(fork.uncle_by_block_number_and_index(number, idx.into()).await?);
         }
-        // It's impossible to have uncles outside of fork mode
-        Ok(None)
+        Ok(self.backend.mined_uncle(number, idx.into()))
     }
 
     /// Returns logs matching given filter object.
@@ -1589,14 +1915,78 @@ pub async fn fee_history(
     /// Introduced in EIP-1159, a Geth-specific and simplified priority fee oracle.
     /// Leverages the already existing fee history cache.
     ///
-    /// Returns a suggestion for a gas tip cap for dynamic fee transactions.
+    /// Returns a suggestion for a gas tip cap for dynamic fee transactions: the median of the
+    /// lowest tips paid in the recently mined blocks that contain transactions. If there are none
+    /// and anvil is forked, the remote endpoint's suggestion for the fork block is returned.
     ///
     /// Handler for ETH RPC call: `eth_maxPriorityFeePerGas`
-    pub fn max_priority_fee_per_gas(&self) -> Result<U256> {
+    pub async fn max_priority_fee_per_gas(&self) -> Result<U256> {
         node_info!("eth_maxPriorityFeePerGas");
+        if let Some(tip) = self.recent_tip_suggestion() {
+            return Ok(U256::from(tip));
+        }
+        if let Some(fork) = self.get_fork()
+            && let Ok(tip) = fork.max_priority_fee_per_gas().await
+        {
+            return Ok(U256::from(tip));
+        }
         Ok(U256::from(self.lowest_suggestion_tip()))
     }
 
+    /// Returns the median of the lowest tips of the last [MAX_TIP_SUGGESTION_BLOCKS] blocks that
+    /// contain transactions, at least [MIN_SUGGESTED_PRIORITY_FEE]
+    fn recent_tip_suggestion(&self) -> Option<u128> {
+        let best_number = self.backend.best_number();
+        let first = best_number.saturating_sub(MAX_TIP_SUGGESTION_BLOCKS - 1);
+        let mut tips = {
+            let cache = self.fee_history_cache.lock();
+            (first..=best_number)
+                .filter_map(|number| {
+                    let item = cache.get(&number)?;
+                    if item.gas_used_ratio == 0.0 {
+                        return None;
+                    }
+                    item.rewards.first().copied()
+                })
+                .collect::<Vec<_>>()
+        };
+        if tips.is_empty() {
+            return None;
+        }
+        tips.sort_unstable();
+        Some(tips[tips.len() / 2].max(MIN_SUGGESTED_PRIORITY_FEE))
+    }
+
+    /// Returns the median gas price paid in the last [MAX_TIP_SUGGESTION_BLOCKS] blocks that
+    /// contain transactions, at least the base fee of the next block
+    fn recent_gas_price_suggestion(&self) -> Option<u128> {
+        let best_number = self.backend.best_number();
+        let first = best_number.saturating_sub(MAX_TIP_SUGGESTION_BLOCKS - 1);
+        let mut prices = {
+            let cache = self.fee_history_cache.lock();
+            (first..=best_number)
+                .filter_map(|number| {
+                    let item = cache.get(&number)?;
+                    if item.gas_used_ratio == 0.0 {
+                        return None;
+                    }
+                    let reward = item.rewards.get(item.rewards.len() / 2).copied()?;
+                    Some(item.base_fee.saturating_add(reward))
+                })
+                .collect::<Vec<_>>()
+        };
+        if prices.is_empty() {
+            return None;
+        }
+        prices.sort_unstable();
+        let gas_price = prices[prices.len() / 2];
+        if self.backend.is_eip1559() {
+            Some(gas_price.max(self.backend.base_fee() as u128))
+        } else {
+            Some(gas_price)
+        }
+    }
+
     /// Returns the suggested fee cap.
     ///
     /// Returns at least [MIN_SUGGESTED_PRIORITY_FEE]
@@ -1734,6 +2124,7 @@ pub async fn debug_trace_call(
         &self,
         request: WithOtherFields<TransactionRequest>,
         block_number: Option<BlockId>,
+        overrides: EvmOverrides,
         opts: GethDebugTracingCallOptions,
     ) -> Result<GethTrace> {
         node_info!("debug_traceCall");
@@ -1746,8 +2137,10 @@ pub async fn debug_trace_call(
         )?
         .or_zero_fees();
 
-        let result: std::result::Result<GethTrace, BlockchainError> =
-            self.backend.call_with_tracing(request, fees, Some(block_request), opts).await;
+        let result: std::result::Result<GethTrace, BlockchainError> = self
+            .backend
+            .call_with_tracing(request, fees, Some(block_request), overrides, opts)
+            .await;
         result
     }
 
@@ -1850,7 +2243,8 @@ pub async fn anvil_set_auto_mine(&self, enable_automine: bool) -> Result<()> {
             self.miner.set_mining_mode(MiningMode::None);
         } else if enable_automine {
             let listener = self.pool.add_ready_listener();
-            let mode = MiningMode::instant(1_000, listener);
+            let max_transactions = self.backend.max_txs_per_block().unwrap_or(1_000);
+            let mode = MiningMode::instant(max_transactions, listener);
             self.miner.set_mining_mode(mode);
         }
         Ok(())
@@ -1874,7 +2268,7 @@ pub async fn anvil_mine(&self, num_blocks: Option<U256>, interval: Option<U256>)
                 if let Some(interval) = interval {
                     this.backend.time().increase_time(interval);
                 }
-                this.mine_one().await;
+                this.mine_one().await?;
             }
             Ok(())
         })
@@ -1927,6 +2321,7 @@ pub async fn anvil_drop_all_transactions(&self) -> Result<()> {
     pub async fn anvil_reset(&self, forking: Option<Forking>) -> Result<()> {
         self.reset_instance_id();
         node_info!("anvil_reset");
+        *self.local_overrides.lock() = LocalOverrides::default();
         if let Some(forking) = forking {
             // if we're resetting the fork we need to reset the instance id
             self.backend.reset_fork(forking).await
@@ -1948,7 +2343,9 @@ pub async fn anvil_set_chain_id(&self, chain_id: u64) -> Result<()> {
     /// Handler for RPC call: `anvil_setBalance`
     pub async fn anvil_set_balance(&self, address: Address, balance: U256) -> Result<()> {
         node_info!("anvil_setBalance");
+        self.backend.ensure_state_account_capacity(address).await?;
         self.backend.set_balance(address, balance).await?;
+        self.local_overrides.lock().balances.insert(address, balance);
         Ok(())
     }
 
@@ -1957,8 +2354,10 @@ pub async fn anvil_set_balance(&self, address: Address, balance: U256) -> Result
     /// Handler for RPC call: `anvil_addBalance`
     pub async fn anvil_add_balance(&self, address: Address, balance: U256) -> Result<()> {
         node_info!("anvil_addBalance");
+        self.backend.ensure_state_account_capacity(address).await?;
         let current_balance = self.backend.get_balance(address, None).await?;
         self.backend.set_balance(address, current_balance + balance).await?;
+        self.local_overrides.lock().balances.insert(address, current_balance + balance);
         Ok(())
     }
 
@@ -2062,6 +2461,10 @@ pub async fn anvil_deal_erc20(
             B256::from(balance.to_be_bytes()),
         )
         .await?;
+        self.local_overrides
+            .lock()
+            .erc20_balances
+            .insert((token_address, address), (slot, balance));
 
         Ok(())
     }
@@ -2104,12 +2507,29 @@ pub async fn anvil_set_erc20_allowance(
         Ok(())
     }
 
+    /// Deals ERC20 tokens to a holder and sets the allowance of a spender to the same amount
+    ///
+    /// Handler for RPC call: `anvil_dealERC20AndApprove`
+    pub async fn anvil_deal_erc20_and_approve(
+        &self,
+        holder: Address,
+        token_address: Address,
+        amount: U256,
+        spender: Address,
+    ) -> Result<()> {
+        node_info!("anvil_dealERC20AndApprove");
+        self.anvil_deal_erc20(holder, token_address, amount).await?;
+        self.anvil_set_erc20_allowance(holder, spender, token_address, amount).await
+    }
+
     /// Sets the code of a contract.
     ///
     /// Handler for RPC call: `anvil_setCode`
     pub async fn anvil_set_code(&self, address: Address, code: Bytes) -> Result<()> {
         node_info!("anvil_setCode");
-        self.backend.set_code(address, code).await?;
+        self.backend.ensure_state_account_capacity(address).await?;
+        self.backend.set_code(address, code.clone()).await?;
+        self.local_overrides.lock().code.insert(address, code);
         Ok(())
     }
 
@@ -2118,6 +2538,7 @@ pub async fn anvil_set_code(&self, address: Address, code: Bytes) -> Result<()>
     /// Handler for RPC call: `anvil_setNonce`
     pub async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> Result<()> {
         node_info!("anvil_setNonce");
+        self.backend.ensure_state_account_capacity(address).await?;
         self.backend.set_nonce(address, nonce).await?;
         Ok(())
     }
@@ -2132,6 +2553,7 @@ pub async fn anvil_set_storage_at(
         val: B256,
     ) -> Result<bool> {
         node_info!("anvil_setStorageAt");
+        self.backend.ensure_state_account_capacity(address).await?;
         self.backend.set_storage_at(address, slot, val).await?;
         Ok(true)
     }
@@ -2216,7 +2638,7 @@ pub async fn anvil_load_state(&self, buf: Bytes) -> Result<bool> {
     /// Retrieves the Anvil node configuration params.
     ///
     /// Handler for RPC call: `anvil_nodeInfo`
-    pub async fn anvil_node_info(&self) -> Result<NodeInfo> {
+    pub async fn anvil_node_info(&self) -> Result<AnvilNodeInfo> {
         node_info!("anvil_nodeInfo");
 
         let env = self.backend.env().read();
@@ -2224,7 +2646,7 @@ pub async fn anvil_node_info(&self) -> Result<NodeInfo> {
         let tx_order = self.transaction_order.read();
         let hard_fork: &str = env.evm_env.cfg_env.spec.into();
 
-        Ok(NodeInfo {
+        let info = NodeInfo {
             current_block_number: self.backend.best_number(),
             current_block_timestamp: env.evm_env.block_env.timestamp.saturating_to(),
             current_block_hash: self.backend.best_hash(),
@@ -2240,6 +2662,7 @@ pub async fn anvil_node_info(&self) -> Result<NodeInfo> {
                 gas_price: self.gas_price(),
             },
             fork_config: fork_config
+                .as_ref()
                 .map(|fork| {
                     let config = fork.config.read();
 
@@ -2250,7 +2673,12 @@ pub async fn anvil_node_info(&self) -> Result<NodeInfo> {
                     }
                 })
                 .unwrap_or_default(),
-        })
+        };
+        let chain_specific_env_changes = fork_config
+            .map(|fork| fork.config.read().chain_specific_env_changes.clone())
+            .unwrap_or_default();
+
+        Ok(AnvilNodeInfo { info, chain_specific_env_changes })
     }
 
     /// Retrieves metadata about the Anvil instance.
@@ -2434,112 +2862,1522 @@ pub async fn anvil_rollback(&self, depth: Option<u64>) -> Result<()> {
         Ok(())
     }
 
-    /// Snapshot the state of the blockchain at the current block.
+    /// Returns all resets and reorgs that happened on this node, oldest first.
     ///
-    /// Handler for RPC call: `evm_snapshot`
-    pub async fn evm_snapshot(&self) -> Result<U256> {
-        node_info!("evm_snapshot");
-        Ok(self.backend.create_state_snapshot().await)
+    /// Handler for RPC call: `anvil_reorgHistory`
+    pub fn anvil_reorg_history(&self) -> Result<Vec<ReorgHistoryEntry>> {
+        node_info!("anvil_reorgHistory");
+        Ok(self.backend.reorg_history())
     }
 
-    /// Revert the state of the blockchain to a previous snapshot.
-    /// Takes a single parameter, which is the snapshot id to revert to.
+    /// Returns the hashes of all transactions that occupied the given sender/nonce slot in the
+    /// pool, oldest first, ending with the transaction that replaced the others.
     ///
-    /// Handler for RPC call: `evm_revert`
-    pub async fn evm_revert(&self, id: U256) -> Result<bool> {
-        node_info!("evm_revert");
-        self.backend.revert_state_snapshot(id).await
-    }
-
-    /// Jump forward in time by the given amount of time, in seconds.
+    /// Returns an empty list if no transaction in that slot was ever replaced. Only the most recent
+    /// replacements of the most recently replaced slots are kept.
     ///
-    /// Handler for RPC call: `evm_increaseTime`
-    pub async fn evm_increase_time(&self, seconds: U256) -> Result<i64> {
-        node_info!("evm_increaseTime");
-        Ok(self.backend.time().increase_time(seconds.try_into().unwrap_or(u64::MAX)) as i64)
+    /// Handler for RPC call: `anvil_txReplacements`
+    pub fn anvil_tx_replacements(&self, address: Address, nonce: U256) -> Result<Vec<TxHash>> {
+        node_info!("anvil_txReplacements");
+        Ok(self.pool.replacements(address, nonce.saturating_to()))
     }
 
-    /// Similar to `evm_increaseTime` but takes the exact timestamp that you want in the next block
+    /// Returns the size and hash of the code deployed at `address` and whether it looks like an
+    /// EIP-1967 proxy, i.e. has its implementation slot populated.
     ///
-    /// Handler for RPC call: `evm_setNextBlockTimestamp`
-    pub fn evm_set_next_block_timestamp(&self, seconds: u64) -> Result<()> {
-        node_info!("evm_setNextBlockTimestamp");
-        self.backend.time().set_next_block_timestamp(seconds)
+    /// Handler for RPC call: `anvil_contractInfo`
+    pub async fn anvil_contract_info(&self, address: Address) -> Result<ContractInfo> {
+        node_info!("anvil_contractInfo");
+        let code = self.get_code(address, None).await?;
+        let slot = self.storage_at(address, EIP1967_IMPLEMENTATION_SLOT.into(), None).await?;
+        let implementation =
+            (!code.is_empty() && !slot.is_zero()).then(|| Address::from_word(slot));
+
+        Ok(ContractInfo {
+            code_size: code.len() as u64,
+            code_hash: keccak256(&code),
+            is_proxy: implementation.is_some(),
+            implementation,
+        })
     }
 
-    /// Sets the specific timestamp and returns the number of seconds between the given timestamp
-    /// and the current time.
-    ///
-    /// Handler for RPC call: `evm_setTime`
-    pub fn evm_set_time(&self, timestamp: u64) -> Result<u64> {
-        node_info!("evm_setTime");
-        let now = self.backend.time().current_call_timestamp();
-        self.backend.time().reset(timestamp);
+    /// Estimates in how many blocks the pending transaction will be included, `1` being the next
+    /// block.
+    ///
+    /// This packs the ready transactions into blocks in the order of the pool's ordering policy,
+    /// honoring the block gas limit and the maximum number of transactions per block. Returns
+    /// `None` if the transaction is not ready for inclusion.
+    ///
+    /// Handler for RPC call: `anvil_inclusionEstimate`
+    pub fn anvil_inclusion_estimate(&self, hash: B256) -> Result<Option<u64>> {
+        node_info!("anvil_inclusionEstimate");
+        let gas_limit = self.backend.gas_limit();
+        let max_txs_per_block = self.backend.max_txs_per_block();
+
+        let mut blocks = 1u64;
+        let mut block_gas = 0u64;
+        let mut block_txs = 0usize;
+        for tx in self.pool.ready_transactions() {
+            let tx_gas = tx.pending_transaction.transaction.gas_limit();
+            let block_full = block_gas.saturating_add(tx_gas) > gas_limit
+                || max_txs_per_block.is_some_and(|max| block_txs >= max);
+            if block_txs > 0 && block_full {
+                blocks += 1;
+                block_gas = 0;
+                block_txs = 0;
+            }
+            block_gas = block_gas.saturating_add(tx_gas);
+            block_txs += 1;
 
-        // number of seconds between the given timestamp and the current time.
-        let offset = timestamp.saturating_sub(now);
-        Ok(Duration::from_millis(offset).as_secs())
+            if tx.hash() == hash {
+                return Ok(Some(blocks));
+            }
+        }
+        Ok(None)
     }
 
-    /// Set the next block gas limit
+    /// Decodes the logs of a mined transaction against the events of the given JSON ABI.
     ///
-    /// Handler for RPC call: `evm_setBlockGasLimit`
-    pub fn evm_set_block_gas_limit(&self, gas_limit: U256) -> Result<bool> {
-        node_info!("evm_setBlockGasLimit");
-        self.backend.set_gas_limit(gas_limit.to());
-        Ok(true)
+    /// Logs that don't match any non-anonymous event of the ABI are skipped.
+    ///
+    /// Handler for RPC call: `anvil_decodeReceiptLogs`
+    pub async fn anvil_decode_receipt_logs(
+        &self,
+        hash: B256,
+        abi: String,
+    ) -> Result<Vec<DecodedLog>> {
+        node_info!("anvil_decodeReceiptLogs");
+        let abi: JsonAbi = serde_json::from_str(&abi)
+            .map_err(|err| RpcError::invalid_params(format!("invalid ABI: {err}")))?;
+        let receipt = self
+            .backend
+            .transaction_receipt(hash)
+            .await?
+            .ok_or(BlockchainError::TransactionNotFound)?;
+
+        let mut decoded = Vec::new();
+        for log in &receipt.inner.as_receipt_with_bloom().receipt.logs {
+            let Some(topic0) = log.topic0() else { continue };
+            let Some(event) =
+                abi.events().find(|event| !event.anonymous && event.selector() == *topic0)
+            else {
+                continue;
+            };
+            let Ok(values) = event.decode_log(&log.inner.data) else { continue };
+
+            let mut indexed = values.indexed.iter();
+            let mut body = values.body.iter();
+            let params = event
+                .inputs
+                .iter()
+                .filter_map(|input| {
+                    let value = if input.indexed { indexed.next() } else { body.next() }?;
+                    Some(DecodedLogParam {
+                        name: input.name.clone(),
+                        indexed: input.indexed,
+                        value: format_token_raw(value),
+                    })
+                })
+                .collect();
+
+            decoded.push(DecodedLog {
+                address: log.address(),
+                log_index: log.log_index,
+                event: event.name.clone(),
+                params,
+            });
+        }
+        Ok(decoded)
     }
 
-    /// Sets an interval for the block timestamp
+    /// Estimates the gas of the `request` like `eth_estimateGas`, but also returns the bounds the
+    /// estimation worked with: the gas actually used by the call and the highest gas limit the
+    /// binary search started from.
     ///
-    /// Handler for RPC call: `anvil_setBlockTimestampInterval`
-    pub fn evm_set_block_timestamp_interval(&self, seconds: u64) -> Result<()> {
-        node_info!("anvil_setBlockTimestampInterval");
-        self.backend.time().set_block_timestamp_interval(seconds);
-        Ok(())
+    /// For blocks predating the fork the estimate comes from the remote endpoint, so both bounds
+    /// equal the estimate.
+    ///
+    /// Handler for RPC call: `anvil_estimateGasRange`
+    pub async fn anvil_estimate_gas_range(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        block_number: Option<BlockId>,
+    ) -> Result<GasEstimateRange> {
+        node_info!("anvil_estimateGasRange");
+        self.do_estimate_gas(
+            request,
+            block_number.or_else(|| Some(BlockNumber::Pending.into())),
+            EvmOverrides::default(),
+        )
+        .await
     }
 
-    /// Sets an interval for the block timestamp
+    /// Computes the pre-merge reward paid to the miner of the uncle at the given index of a block,
+    /// `(uncle_number + 8 - block_number) * block_reward / 8`.
     ///
-    /// Handler for RPC call: `anvil_removeBlockTimestampInterval`
-    pub fn evm_remove_block_timestamp_interval(&self) -> Result<bool> {
-        node_info!("anvil_removeBlockTimestampInterval");
-        Ok(self.backend.time().remove_block_timestamp_interval())
+    /// The reward era is derived from the configured hardfork, or for blocks of a forked mainnet
+    /// from the hardfork active at that height. Returns `None` if the uncle does not exist or the
+    /// block was mined after the merge.
+    ///
+    /// Handler for RPC call: `anvil_uncleReward`
+    pub async fn anvil_uncle_reward(
+        &self,
+        block_number: BlockNumber,
+        idx: Index,
+    ) -> Result<Option<U256>> {
+        node_info!("anvil_uncleReward");
+        let number = self.backend.ensure_block_number(Some(BlockId::Number(block_number))).await?;
+        let Some(uncle) = self.uncle_by_block_number_and_index(number.into(), idx).await? else {
+            return Ok(None);
+        };
+        let spec = self.backend.spec_at_block(number);
+        Ok(uncle_reward(spec, number, uncle.header.number).map(U256::from))
     }
 
-    /// Mine blocks, instantly.
+    /// Queues an uncle mined by `beneficiary` at height `number` for inclusion in the next mined
+    /// block and returns its hash.
     ///
-    /// Handler for RPC call: `evm_mine`
+    /// If block rewards are enabled, the beneficiary is paid the uncle reward when the block is
+    /// mined.
     ///
-    /// This will mine the blocks regardless of the configured mining mode.
-    /// **Note**: ganache returns `0x0` here as placeholder for additional meta-data in the future.
-    pub async fn evm_mine(&self, opts: Option<MineOptions>) -> Result<String> {
-        node_info!("evm_mine");
-
-        self.do_evm_mine(opts).await?;
-
-        Ok("0x0".to_string())
+    /// Handler for RPC call: `anvil_injectUncle`
+    pub fn anvil_inject_uncle(&self, beneficiary: Address, number: u64) -> Result<B256> {
+        node_info!("anvil_injectUncle");
+        self.backend.inject_uncle(beneficiary, number)
     }
 
-    /// Mine blocks, instantly and return the mined blocks.
+    /// Executes a new message call like `eth_call`, but with gas metering disabled: the cost of
+    /// every opcode is ignored and no refunds are accounted, so the call never runs out of gas.
     ///
-    /// Handler for RPC call: `evm_mine_detailed`
-    ///
-    /// This will mine the blocks regardless of the configured mining mode.
+    /// Not available for blocks predating the fork.
     ///
-    /// **Note**: This behaves exactly as [Self::evm_mine] but returns different output, for
-    /// compatibility reasons, this is a separate call since `evm_mine` is not an anvil original.
-    /// and `ganache` may change the `0x0` placeholder.
-    pub async fn evm_mine_detailed(&self, opts: Option<MineOptions>) -> Result<Vec<AnyRpcBlock>> {
-        node_info!("evm_mine_detailed");
-
-        let mined_blocks = self.do_evm_mine(opts).await?;
-
-        let mut blocks = Vec::with_capacity(mined_blocks as usize);
-
-        let latest = self.backend.best_number();
-        for offset in (0..mined_blocks).rev() {
-            let block_num = latest - offset;
+    /// Handler for RPC call: `anvil_callNoGas`
+    pub async fn anvil_call_no_gas(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        block_number: Option<BlockId>,
+        overrides: EvmOverrides,
+    ) -> Result<Bytes> {
+        node_info!("anvil_callNoGas");
+        let block_request = self.block_request(block_number).await?;
+        if let BlockRequest::Number(number) = block_request
+            && let Some(fork) = self.get_fork()
+            && fork.predates_fork(number)
+        {
+            return Err(RpcError::invalid_params("not available on past forked blocks").into());
+        }
+
+        let fees = FeeDetails::new(
+            request.gas_price,
+            request.max_fee_per_gas,
+            request.max_priority_fee_per_gas,
+            request.max_fee_per_blob_gas,
+        )?
+        .or_zero_fees();
+        self.on_blocking_task(|this| async move {
+            let (exit, out, gas, _) = this
+                .backend
+                .call_without_gas_metering(request, fees, Some(block_request), overrides)
+                .await?;
+            trace!(target : "node", "Call status {:?}, gas {}", exit, gas);
+
+            ensure_return_ok(exit, &out)
+        })
+        .await
+    }
+
+    /// Returns the author of the block: the signer recovered from the clique seal in the
+    /// `extraData` for PoA sealed blocks, and the coinbase of the block otherwise.
+    ///
+    /// Blocks are considered sealed if they belong to a forked clique chain (Goerli, Rinkeby), or
+    /// were mined locally with a configured block signer.
+    ///
+    /// Handler for RPC call: `anvil_blockAuthor`
+    pub async fn anvil_block_author(&self, number: BlockNumber) -> Result<Option<Address>> {
+        node_info!("anvil_blockAuthor");
+        let Some(block) = self.block_by_number(number).await? else {
+            return Ok(None);
+        };
+        let beneficiary = block.header.beneficiary;
+        let sealed = match self.get_fork() {
+            Some(fork) if fork.predates_fork_inclusive(block.header.number) => matches!(
+                NamedChain::try_from(fork.rpc_chain_id()),
+                Ok(NamedChain::Goerli | NamedChain::Rinkeby)
+            ),
+            _ => self.backend.block_signer().is_some(),
+        };
+        if !sealed {
+            return Ok(Some(beneficiary));
+        }
+        let signer = block
+            .header
+            .inner
+            .clone()
+            .try_into_header()
+            .ok()
+            .and_then(|header| clique_signer(&header));
+        Ok(Some(signer.unwrap_or(beneficiary)))
+    }
+
+    /// Re-executes a mined transaction and returns a histogram of the gas it spent per opcode
+    /// category: storage, memory, calls and compute.
+    ///
+    /// Handler for RPC call: `anvil_gasByOpcode`
+    pub fn anvil_gas_by_opcode(&self, hash: B256) -> Result<GasByOpcode> {
+        node_info!("anvil_gasByOpcode");
+        self.backend.gas_by_opcode(hash)
+    }
+
+    /// Returns the state of the account as of right before the transaction at the given index of
+    /// the block was executed, by replaying the block up to that index.
+    ///
+    /// Handler for RPC call: `anvil_stateAtTransaction`
+    pub async fn anvil_state_at_transaction(
+        &self,
+        block_number: BlockNumber,
+        index: Index,
+        address: Address,
+    ) -> Result<alloy_rpc_types::eth::AccountInfo> {
+        node_info!("anvil_stateAtTransaction");
+        let number = self.backend.ensure_block_number(Some(BlockId::Number(block_number))).await?;
+        self.backend.account_at_transaction(number, index.into(), address)
+    }
+
+    /// Sets the addresses that can't be called: any `CALL`, `CALLCODE`, `DELEGATECALL` or
+    /// `STATICCALL` to one of them reverts. An empty list clears the denylist.
+    ///
+    /// Handler for RPC call: `anvil_setCallDenylist`
+    pub fn anvil_set_call_denylist(&self, addresses: Vec<Address>) -> Result<()> {
+        node_info!("anvil_setCallDenylist");
+        self.backend.cheats().set_call_denylist(addresses);
+        Ok(())
+    }
+
+    /// Returns the block the fork is based on, as captured when forking or on the last
+    /// `anvil_reset`, or `None` if not in fork mode.
+    ///
+    /// Handler for RPC call: `anvil_forkBaseBlock`
+    pub fn anvil_fork_base_block(&self) -> Result<Option<ForkBaseBlock>> {
+        node_info!("anvil_forkBaseBlock");
+        Ok(self.get_fork().map(|fork| ForkBaseBlock {
+            number: fork.block_number(),
+            hash: fork.block_hash(),
+            timestamp: fork.timestamp(),
+            base_fee: fork.base_fee(),
+            state_root: fork.state_root(),
+        }))
+    }
+
+    /// Re-executes a mined transaction and returns the net balance change of every account it
+    /// affected, including the gas paid by the sender and the tip credited to the coinbase. The
+    /// burnt base fee is not credited to any account.
+    ///
+    /// Handler for RPC call: `anvil_balanceDiff`
+    pub fn anvil_balance_diff(&self, hash: B256) -> Result<BTreeMap<Address, I256>> {
+        node_info!("anvil_balanceDiff");
+        self.backend.balance_diff(hash)
+    }
+
+    /// Returns the hashes of the transactions that were mined locally and then discarded by the
+    /// most recent reset or reorg, in the order they were mined. Transactions that were mined
+    /// again since are excluded.
+    ///
+    /// Handler for RPC call: `anvil_orphanedTransactions`
+    pub fn anvil_orphaned_transactions(&self) -> Result<Vec<TxHash>> {
+        node_info!("anvil_orphanedTransactions");
+        Ok(self.backend.orphaned_transactions())
+    }
+
+    /// Returns the base fee the next mined block will carry. This is derived from the latest block
+    /// per EIP-1559, unless it was overridden with `anvil_setNextBlockBaseFeePerGas`. Returns 0 if
+    /// EIP-1559 is not active.
+    ///
+    /// Handler for RPC call: `anvil_nextBaseFee`
+    pub fn anvil_next_base_fee(&self) -> Result<U256> {
+        node_info!("anvil_nextBaseFee");
+        Ok(U256::from(self.backend.base_fee()))
+    }
+
+    /// Sets the maximum init code size of contract creations (EIP-3860). `None` restores the
+    /// default, which is twice the runtime code size limit.
+    ///
+    /// Handler for RPC call: `anvil_setInitcodeSizeLimit`
+    pub fn anvil_set_initcode_size_limit(&self, limit: Option<U256>) -> Result<()> {
+        node_info!("anvil_setInitcodeSizeLimit");
+        self.backend.set_initcode_size_limit(limit.map(|limit| limit.saturating_to()));
+        Ok(())
+    }
+
+    /// Returns the balances of all given accounts at the given block, in the same order. Balances
+    /// are resolved like `eth_getBalance`, from the remote endpoint if the block predates the
+    /// fork.
+    ///
+    /// Handler for RPC call: `anvil_balances`
+    pub async fn anvil_balances(
+        &self,
+        addresses: Vec<Address>,
+        block_number: Option<BlockId>,
+    ) -> Result<Vec<U256>> {
+        node_info!("anvil_balances");
+        let block_request = self.block_request(block_number).await?;
+
+        // check if the number predates the fork, if in fork mode
+        if let BlockRequest::Number(number) = block_request
+            && let Some(fork) = self.get_fork()
+            && fork.predates_fork(number)
+        {
+            let balances = addresses.iter().map(|address| fork.get_balance(*address, number));
+            return Ok(futures::future::try_join_all(balances).await?);
+        }
+
+        self.backend.get_balances(&addresses, Some(block_request)).await
+    }
+
+    /// Returns the RLP encoded block, header and body, as it would be exchanged between clients.
+    /// Blocks that predate the fork are encoded from the remote block.
+    ///
+    /// Handler for RPC call: `anvil_blockRlp`
+    pub async fn anvil_block_rlp(&self, number: BlockNumber) -> Result<Option<Bytes>> {
+        node_info!("anvil_blockRlp");
+        self.backend.block_rlp(number).await
+    }
+
+    /// Returns the runtime code of `address` as of the end of the given mined block. Unlike
+    /// `eth_getCode`, changes made since the block was mined, e.g. via `anvil_setCode`, are never
+    /// included. Blocks up to and including the forked block are served by the remote endpoint.
+    ///
+    /// Handler for RPC call: `anvil_codeAt`
+    pub async fn anvil_code_at(&self, address: Address, block: BlockNumber) -> Result<Bytes> {
+        node_info!("anvil_codeAt");
+        let number = self.backend.ensure_block_number(Some(BlockId::Number(block))).await?;
+        if let Some(fork) = self.get_fork()
+            && fork.predates_fork_inclusive(number)
+        {
+            return Ok(fork.get_code(address, number).await?);
+        }
+        self.backend.code_at(address, number).await
+    }
+
+    /// Returns the total number of transactions in the blocks `from_block..=to_block`. Blocks
+    /// that predate the fork are fetched from the remote endpoint.
+    ///
+    /// Handler for RPC call: `anvil_txCountInRange`
+    pub async fn anvil_tx_count_in_range(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<U256> {
+        node_info!("anvil_txCountInRange");
+        let from = self.backend.ensure_block_number(Some(BlockId::Number(from_block))).await?;
+        let to = self.backend.ensure_block_number(Some(BlockId::Number(to_block))).await?;
+        if from > to {
+            return Err(RpcError::invalid_params("from block is greater than to block").into());
+        }
+        Ok(U256::from(self.backend.transaction_count_in_range(from, to).await?))
+    }
+
+    /// Executes a new message call like `eth_call`, with `coinbase` as the `block.coinbase` seen
+    /// by the call. This is shorthand for a `coinbase` block override, mined blocks keep using the
+    /// node's coinbase.
+    ///
+    /// Handler for RPC call: `anvil_callWithCoinbase`
+    pub async fn anvil_call_with_coinbase(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        coinbase: Address,
+        block_number: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> Result<Bytes> {
+        node_info!("anvil_callWithCoinbase");
+        let block_overrides = BlockOverrides { coinbase: Some(coinbase), ..Default::default() };
+        self.call(
+            request,
+            block_number,
+            EvmOverrides::new(state_override, Some(Box::new(block_overrides))),
+        )
+        .await
+    }
+
+    /// Returns the locally mined transactions in the blocks `from_block..=to_block` whose calldata
+    /// starts with the given 4-byte function selector. Blocks that predate the fork are skipped.
+    ///
+    /// Handler for RPC call: `anvil_txsBySelector`
+    pub async fn anvil_txs_by_selector(
+        &self,
+        selector: Selector,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<AnyRpcTransaction>> {
+        node_info!("anvil_txsBySelector");
+        let from = self.backend.ensure_block_number(Some(BlockId::Number(from_block))).await?;
+        let to = self.backend.ensure_block_number(Some(BlockId::Number(to_block))).await?;
+        if from > to {
+            return Err(RpcError::invalid_params("from block is greater than to block").into());
+        }
+        Ok(self.backend.mined_transactions_by_selector(selector, from, to))
+    }
+
+    /// Returns the storage slots of `address` whose value changed since `cursor`, together with
+    /// a new cursor to pass to the next call. Without a cursor all non-empty slots are returned.
+    /// Each cursor can only be used once, and only the most recent cursors are kept.
+    ///
+    /// Handler for RPC call: `anvil_storageChangesSince`
+    pub async fn anvil_storage_changes_since(
+        &self,
+        address: Address,
+        cursor: Option<B256>,
+    ) -> Result<StorageChanges> {
+        node_info!("anvil_storageChangesSince");
+        self.backend.storage_changes_since(address, cursor).await
+    }
+
+    /// Returns whether the logs bloom of the block may contain logs emitted by `address` with all
+    /// of the given `topics`. A `false` result is definite, a `true` result may be a false
+    /// positive and requires fetching the logs.
+    ///
+    /// Handler for RPC call: `anvil_bloomMatches`
+    pub async fn anvil_bloom_matches(
+        &self,
+        number: BlockNumber,
+        address: Option<Address>,
+        topics: Vec<B256>,
+    ) -> Result<bool> {
+        node_info!("anvil_bloomMatches");
+        let block =
+            self.backend.block_by_number(number).await?.ok_or(BlockchainError::BlockNotFound)?;
+        let bloom = block.header.logs_bloom;
+        Ok(address.is_none_or(|address| bloom.contains_input(BloomInput::Raw(address.as_slice())))
+            && topics.iter().all(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice()))))
+    }
+
+    /// Sets the nonce of `deployer`, so that the next contract it deploys with `CREATE` gets a
+    /// predictable address, and returns that address. Transactions of `deployer` still in the pool
+    /// are dropped, since they were signed for the previous nonces.
+    ///
+    /// Handler for RPC call: `anvil_resetNonceForDeployment`
+    pub async fn anvil_reset_nonce_for_deployment(
+        &self,
+        deployer: Address,
+        nonce: U256,
+    ) -> Result<Address> {
+        node_info!("anvil_resetNonceForDeployment");
+        let nonce: u64 =
+            nonce.try_into().map_err(|_| RpcError::invalid_params("nonce exceeds u64"))?;
+        self.pool.remove_transactions_by_address(deployer);
+        self.backend.set_nonce(deployer, U256::from(nonce)).await?;
+        Ok(deployer.create(nonce))
+    }
+
+    /// Returns the Merkle proof of `address` against the state root of the given block, without
+    /// any storage proofs. Blocks that predate the fork are proven by the remote endpoint.
+    ///
+    /// Handler for RPC call: `anvil_accountProof`
+    pub async fn anvil_account_proof(
+        &self,
+        address: Address,
+        block: BlockId,
+    ) -> Result<EIP1186AccountProofResponse> {
+        node_info!("anvil_accountProof");
+        self.get_proof(address, Vec::new(), Some(block)).await
+    }
+
+    /// Returns the min, average and max time between consecutive blocks over the last `count`
+    /// intervals of locally mined blocks, e.g. to verify the configured block time.
+    ///
+    /// Handler for RPC call: `anvil_blockTimeStats`
+    pub fn anvil_block_time_stats(&self, count: u64) -> Result<BlockTimeStats> {
+        node_info!("anvil_blockTimeStats");
+        if count == 0 {
+            return Err(RpcError::invalid_params("count must be greater than 0").into());
+        }
+        self.backend
+            .block_time_stats(count)
+            .ok_or_else(|| RpcError::invalid_params("not enough locally mined blocks").into())
+    }
+
+    /// Registers a view function on `address` that is called with `selector` after every mined
+    /// block. Once it reverts or returns `false` mining halts, requests to mine fail, and the
+    /// violation is reported by `anvil_invariantViolation` and sent to
+    /// [Self::invariant_violation_notifications], until a new check is registered or the check is
+    /// removed.
+    ///
+    /// Handler for RPC call: `anvil_setInvariantCheck`
+    pub fn anvil_set_invariant_check(&self, address: Address, selector: Selector) -> Result<()> {
+        node_info!("anvil_setInvariantCheck");
+        self.backend.set_invariant_check(address, selector);
+        // resume mining in case a previous check halted it
+        self.miner.wake();
+        Ok(())
+    }
+
+    /// Removes the invariant check registered via `anvil_setInvariantCheck`, together with any
+    /// violation it recorded, and resumes mining.
+    ///
+    /// Handler for RPC call: `anvil_clearInvariantCheck`
+    pub fn anvil_clear_invariant_check(&self) -> Result<()> {
+        node_info!("anvil_clearInvariantCheck");
+        self.backend.clear_invariant_check();
+        self.miner.wake();
+        Ok(())
+    }
+
+    /// Returns the failed invariant check that halted mining, if any.
+    ///
+    /// Handler for RPC call: `anvil_invariantViolation`
+    pub fn anvil_invariant_violation(&self) -> Result<Option<InvariantViolation>> {
+        node_info!("anvil_invariantViolation");
+        Ok(self.backend.invariant_violation())
+    }
+
+    /// Returns the receipt of a mined transaction together with its trace, saving a separate
+    /// `debug_traceTransaction` round-trip.
+    ///
+    /// Returns `None` if the transaction is unknown or not mined yet.
+    ///
+    /// Handler for RPC call: `anvil_receiptWithTrace`
+    pub async fn anvil_receipt_with_trace(
+        &self,
+        hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Option<ReceiptWithTrace>> {
+        node_info!("anvil_receiptWithTrace");
+        let Some(receipt) = self.backend.transaction_receipt(hash).await? else {
+            return Ok(None);
+        };
+        let trace = self.backend.debug_trace_transaction(hash, opts).await?;
+        Ok(Some(ReceiptWithTrace { receipt, trace }))
+    }
+
+    /// Reads the given storage slots of `address` at the latest block and decodes each as the
+    /// paired Solidity type.
+    ///
+    /// Handler for RPC call: `anvil_decodeStorage`
+    pub async fn anvil_decode_storage(
+        &self,
+        address: Address,
+        slots: Vec<(B256, StorageType)>,
+    ) -> Result<Vec<StorageValue>> {
+        node_info!("anvil_decodeStorage");
+        let mut values = Vec::with_capacity(slots.len());
+        for (slot, ty) in slots {
+            let value = self.backend.storage_at(address, slot.into(), None).await?;
+            values.push(StorageValue::decode(value, ty));
+        }
+        Ok(values)
+    }
+
+    /// Returns the genesis hash of the forked chain together with its config derived from the
+    /// chain id of the remote, even if the local chain id is overridden, or `None` if not in fork
+    /// mode.
+    ///
+    /// Handler for RPC call: `anvil_forkedChainGenesis`
+    pub async fn anvil_forked_chain_genesis(&self) -> Result<Option<ForkedChainGenesis>> {
+        node_info!("anvil_forkedChainGenesis");
+        let Some(fork) = self.get_fork() else { return Ok(None) };
+        let genesis = fork.block_by_number(0).await?.ok_or(BlockchainError::BlockNotFound)?;
+        let chain_id = fork.rpc_chain_id();
+        let named = NamedChain::try_from(chain_id).ok();
+        Ok(Some(ForkedChainGenesis {
+            chain_id,
+            chain: named.map(|chain| chain.to_string()),
+            is_testnet: named.is_some_and(|chain| chain.is_testnet()),
+            genesis_hash: genesis.header.hash,
+        }))
+    }
+
+    /// Returns the value of storage `slot` of `address` at the end of each block in the range.
+    /// Blocks before the fork are read from the remote endpoint, later blocks from local state.
+    /// The range is capped like the range of `eth_getLogs` queries.
+    ///
+    /// Handler for RPC call: `anvil_slotHistory`
+    pub async fn anvil_slot_history(
+        &self,
+        address: Address,
+        slot: U256,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<SlotHistoryEntry>> {
+        node_info!("anvil_slotHistory");
+        let from = self.backend.ensure_block_number(Some(BlockId::Number(from_block))).await?;
+        let to = self.backend.ensure_block_number(Some(BlockId::Number(to_block))).await?;
+        if from > to {
+            return Err(RpcError::invalid_params("from block is greater than to block").into());
+        }
+        self.backend.ensure_block_range(from, to)?;
+        let mut history = Vec::new();
+        for block_number in from..=to {
+            let value = self.storage_at(address, slot, Some(BlockId::number(block_number))).await?;
+            history.push(SlotHistoryEntry { block_number, value });
+        }
+        Ok(history)
+    }
+
+    /// Returns the gas limit that applied to the given block, which may differ from the current
+    /// one if it was changed since, e.g. via `evm_setBlockGasLimit`.
+    ///
+    /// Handler for RPC call: `anvil_blockGasLimit`
+    pub async fn anvil_block_gas_limit(&self, number: BlockNumber) -> Result<u64> {
+        node_info!("anvil_blockGasLimit");
+        let block = self.block_by_number(number).await?.ok_or(BlockchainError::BlockNotFound)?;
+        Ok(block.header.gas_limit)
+    }
+
+    /// Returns the block and the index within it at which a transaction was mined, or `None` if
+    /// the transaction is unknown or still pending.
+    ///
+    /// Handler for RPC call: `anvil_txIndex`
+    pub async fn anvil_tx_index(&self, hash: B256) -> Result<Option<TransactionIndex>> {
+        node_info!("anvil_txIndex");
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let (Some(block_number), Some(block_hash), Some(index)) =
+            (tx.block_number, tx.block_hash, tx.transaction_index)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(TransactionIndex { block_number, block_hash, index }))
+    }
+
+    /// Returns the address and derivation path of every dev wallet. Private keys are only
+    /// included if the node was configured to expose them.
+    ///
+    /// Handler for RPC call: `anvil_walletInfo`
+    pub async fn anvil_wallet_info(&self) -> Result<Vec<WalletInfo>> {
+        node_info!("anvil_walletInfo");
+        Ok(self.backend.wallet_info().await)
+    }
+
+    /// Mines a single block with the ready transactions, in pool order, whose gas limits fit
+    /// within `target_gas`, so the block's gas used never exceeds it. Transactions that don't fit,
+    /// later transactions of the same sender, and those beyond the `max_txs_per_block` limit stay
+    /// in the pool.
+    ///
+    /// Returns the number of the mined block.
+    ///
+    /// Handler for RPC call: `anvil_mineToTargetGas`
+    pub async fn anvil_mine_to_target_gas(&self, target_gas: u64) -> Result<u64> {
+        node_info!("anvil_mineToTargetGas");
+        let mut gas = 0u64;
+        let mut skipped_senders = HashSet::<Address>::default();
+        let max_transactions = self.backend.max_txs_per_block().unwrap_or(usize::MAX);
+        let transactions = self
+            .pool
+            .ready_transactions()
+            .filter(|tx| {
+                let sender = *tx.pending_transaction.sender();
+                let gas_limit = tx.pending_transaction.transaction.gas_limit();
+                let total = gas.checked_add(gas_limit).filter(|total| *total <= target_gas);
+                match total {
+                    Some(total) if !skipped_senders.contains(&sender) => {
+                        gas = total;
+                        true
+                    }
+                    _ => {
+                        skipped_senders.insert(sender);
+                        false
+                    }
+                }
+            })
+            .take(max_transactions)
+            .collect::<Vec<_>>();
+        let outcome = self.backend.mine_block(transactions).await?;
+        let block_number = outcome.block_number;
+        trace!(target: "node", blocknumber = ?block_number, gas, "mined block to target gas");
+        self.pool.on_mined_block(outcome);
+        Ok(block_number)
+    }
+
+    /// Returns the dependency graph of the given transactions. Each transaction is executed on
+    /// top of the latest state, and depends on every earlier transaction in the list that
+    /// writes an account or storage slot it accesses, or accesses one it writes.
+    ///
+    /// Transactions that don't share an edge can be executed in parallel or in any order.
+    ///
+    /// Handler for RPC call: `anvil_txDependencies`
+    pub async fn anvil_tx_dependencies(
+        &self,
+        transactions: Vec<TransactionHashOrRequest>,
+    ) -> Result<Vec<TransactionDependency>> {
+        node_info!("anvil_txDependencies");
+        let mut requests = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            let request = match tx {
+                TransactionHashOrRequest::Hash(hash) => {
+                    let tx = self.transaction_by_hash(hash).await?.ok_or_else(|| {
+                        RpcError::invalid_params(format!("transaction {hash} not found"))
+                    })?;
+                    TransactionRequest::default()
+                        .with_from(tx.from())
+                        .with_kind(tx.kind())
+                        .with_value(tx.value())
+                        .with_input(tx.input().clone())
+                        .with_gas_limit(tx.gas_limit())
+                }
+                TransactionHashOrRequest::Request(request) => request,
+            };
+            requests.push(WithOtherFields::new(request));
+        }
+
+        let access_sets = self
+            .backend
+            .with_database_at(None, |state, block_env| {
+                requests
+                    .into_iter()
+                    .map(|request| {
+                        self.backend.build_access_sets_with_state(
+                            &state,
+                            request,
+                            block_env.clone(),
+                        )
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .await??;
+
+        let mut dependencies = Vec::new();
+        for (index, sets) in access_sets.iter().enumerate() {
+            for (depends_on, earlier) in access_sets[..index].iter().enumerate() {
+                if sets.conflicts_with(earlier) {
+                    dependencies.push(TransactionDependency { index, depends_on });
+                }
+            }
+        }
+        Ok(dependencies)
+    }
+
+    /// Sets how long state overrides made via `anvil_set*` calls last. With the `block` scope
+    /// overrides only apply to the execution of the next mined block and are reverted right after
+    /// it, with the `permanent` scope overrides stay until they are changed. Resets and reverts
+    /// to a state snapshot discard pending block scoped overrides.
+    ///
+    /// Handler for RPC call: `anvil_setOverrideScope`
+    pub async fn anvil_set_override_scope(&self, scope: OverrideScope) -> Result<()> {
+        node_info!("anvil_setOverrideScope");
+        self.backend.set_override_scope(scope);
+        Ok(())
+    }
+
+    /// Returns the latest block number of the forked remote endpoint, fetched directly from it.
+    /// Unlike `eth_blockNumber` this is not the local head, so the two can be compared to see how
+    /// far the fork lags behind the remote chain.
+    ///
+    /// Returns `None` if the node is not forked.
+    ///
+    /// Handler for RPC call: `anvil_remoteBlockNumber`
+    pub async fn anvil_remote_block_number(&self) -> Result<Option<u64>> {
+        node_info!("anvil_remoteBlockNumber");
+        let Some(fork) = self.get_fork() else { return Ok(None) };
+        Ok(Some(fork.remote_block_number().await?))
+    }
+
+    /// Returns the init code the contract at `address` was deployed with, which unlike
+    /// `eth_getCode` includes the constructor. Contracts deployed locally are looked up in the
+    /// traces of the mined blocks, contracts deployed before the fork are looked up on the remote
+    /// endpoint if it supports `ots_getContractCreator`.
+    ///
+    /// Handler for RPC call: `anvil_creationCode`
+    pub async fn anvil_creation_code(&self, address: Address) -> Result<Option<Bytes>> {
+        node_info!("anvil_creationCode");
+        let from = self.get_fork().map(|fork| fork.block_number()).unwrap_or_default();
+        // loop in reverse, since we want the latest deploy to the address
+        for number in (from..=self.backend.best_number()).rev() {
+            if let Some(traces) = self.backend.mined_parity_trace_block(number)
+                && let Some(code) = find_creation_code(traces, address)
+            {
+                return Ok(Some(code));
+            }
+        }
+
+        if let Some(fork) = self.get_fork() {
+            let traces = fork.contract_creation_traces(address).await?;
+            return Ok(find_creation_code(traces, address));
+        }
+        Ok(None)
+    }
+
+    /// Caps the gas limit any single transaction may request below the block gas limit, like
+    /// some clients do on mainnet. Transactions above the cap are rejected, a cap of zero removes
+    /// it.
+    ///
+    /// Handler for RPC call: `anvil_setTransactionGasCap`
+    pub async fn anvil_set_transaction_gas_cap(&self, cap: U256) -> Result<()> {
+        node_info!("anvil_setTransactionGasCap");
+        let cap = (!cap.is_zero()).then(|| cap.saturating_to());
+        self.backend.set_transaction_gas_cap(cap);
+        Ok(())
+    }
+
+    /// Returns the balance of `address` in the latest block mined at or before `timestamp`, so
+    /// balances can be asserted at a point in time rather than at a block number.
+    ///
+    /// Handler for RPC call: `anvil_balanceAtTimestamp`
+    pub async fn anvil_balance_at_timestamp(
+        &self,
+        address: Address,
+        timestamp: u64,
+    ) -> Result<U256> {
+        node_info!("anvil_balanceAtTimestamp");
+        let number = self.backend.block_number_at_timestamp(timestamp).ok_or_else(|| {
+            RpcError::invalid_params(format!("no block mined at or before timestamp {timestamp}"))
+        })?;
+        self.balance(address, Some(BlockId::number(number))).await
+    }
+
+    /// Returns the deterministic address a CREATE2 deployment by `deployer` with `salt` and init
+    /// code hashing to `init_code_hash` lands at. Deploying to an address that already has code
+    /// or a nonce fails with a create collision.
+    ///
+    /// Handler for RPC call: `anvil_predictCreate2Address`
+    pub fn anvil_predict_create2_address(
+        &self,
+        deployer: Address,
+        salt: B256,
+        init_code_hash: B256,
+    ) -> Result<Address> {
+        node_info!("anvil_predictCreate2Address");
+        Ok(deployer.create2(salt, init_code_hash))
+    }
+
+    /// Returns how many accounts, storage slots, state snapshots and historic states the node
+    /// keeps in memory, with a rough estimate of their size, so long running nodes can detect
+    /// bloat before running out of memory.
+    ///
+    /// Handler for RPC call: `anvil_memoryUsage`
+    pub async fn anvil_memory_usage(&self) -> Result<MemoryUsage> {
+        node_info!("anvil_memoryUsage");
+        Ok(self.backend.memory_usage().await)
+    }
+
+    /// Returns the receipt of the transaction at `index` of the given block, without having to
+    /// look up the transaction's hash first.
+    ///
+    /// Handler for RPC call: `anvil_receiptByBlockAndIndex`
+    pub async fn anvil_receipt_by_block_and_index(
+        &self,
+        block: BlockId,
+        index: Index,
+    ) -> Result<Option<ReceiptResponse>> {
+        node_info!("anvil_receiptByBlockAndIndex");
+        let tx = match block {
+            BlockId::Hash(hash) => {
+                self.backend.transaction_by_block_hash_and_index(hash.block_hash, index).await?
+            }
+            BlockId::Number(number) => {
+                self.backend.transaction_by_block_number_and_index(number, index).await?
+            }
+        };
+        let Some(tx) = tx else { return Ok(None) };
+        self.backend.transaction_receipt(tx.tx_hash()).await
+    }
+
+    /// Returns the EVM spec calls against the given block are executed with. On a forked mainnet
+    /// this is the hardfork that was active at the block, so e.g. calls against pre-London blocks
+    /// don't have the `BASEFEE` opcode even if the node runs a later hardfork. The spec is named
+    /// like the `hardFork` reported by `anvil_nodeInfo`.
+    ///
+    /// Handler for RPC call: `anvil_specAtBlock`
+    pub fn anvil_spec_at_block(&self, number: BlockNumber) -> Result<String> {
+        node_info!("anvil_specAtBlock");
+        let number = self.backend.convert_block_number(Some(number));
+        let spec: &str = self.backend.spec_at_block(number).into();
+        Ok(spec.to_string())
+    }
+
+    /// Returns the storage trie root of the account at the given block.
+    ///
+    /// The root is computed from the local state for blocks mined by this node and requested from
+    /// the remote for blocks that predate the fork. Accounts without a local entry are requested
+    /// from the remote at the requested block, or at the fork block for later blocks.
+    ///
+    /// Handler for RPC call: `anvil_storageRoot`
+    pub async fn anvil_storage_root(
+        &self,
+        address: Address,
+        block_number: Option<BlockId>,
+    ) -> Result<B256> {
+        node_info!("anvil_storageRoot");
+        let block_request = self.block_request(block_number).await?;
+
+        if let BlockRequest::Number(number) = block_request
+            && let Some(fork) = self.get_fork()
+            && fork.predates_fork(number)
+        {
+            return Ok(fork.get_account(address, number).await?.storage_root);
+        }
+
+        let requested = match &block_request {
+            BlockRequest::Number(number) => Some(*number),
+            BlockRequest::Pending(_) => None,
+        };
+        if let Some(storage_root) =
+            self.backend.local_storage_root(address, Some(block_request)).await?
+        {
+            return Ok(storage_root);
+        }
+
+        // storage of accounts that weren't touched locally lives on the remote
+        match self.get_fork() {
+            Some(fork) => {
+                let number =
+                    requested.map_or(fork.block_number(), |number| number.min(fork.block_number()));
+                Ok(fork.get_account(address, number).await?.storage_root)
+            }
+            None => Ok(EMPTY_ROOT_HASH),
+        }
+    }
+
+    /// Re-executes the transactions of a block of the forked chain on top of the state of its
+    /// parent, with the given state overrides applied first, and returns the outcome of every
+    /// transaction.
+    ///
+    /// Handler for RPC call: `anvil_replayBlock`
+    pub async fn anvil_replay_block(
+        &self,
+        number: u64,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<ReplayedTransaction>> {
+        node_info!("anvil_replayBlock");
+        let Some(fork) = self.get_fork() else {
+            return Err(RpcError::invalid_params("only available in fork mode").into());
+        };
+        if number == 0 || !fork.predates_fork_inclusive(number) {
+            return Err(RpcError::invalid_params(
+                "block is not a replayable block of the forked chain",
+            )
+            .into());
+        }
+        self.backend.replay_remote_block(number, overrides).await
+    }
+
+    /// Writes `values[i]` to slot `start_slot + i` of the account's storage and returns the number
+    /// of slots written.
+    ///
+    /// Like `anvil_setStorageAt` the values are only written to the local state.
+    ///
+    /// Handler for RPC call: `anvil_setStorageAtRange`
+    pub async fn anvil_set_storage_at_range(
+        &self,
+        address: Address,
+        start_slot: U256,
+        values: Vec<B256>,
+    ) -> Result<usize> {
+        node_info!("anvil_setStorageAtRange");
+        if start_slot.checked_add(U256::from(values.len().saturating_sub(1))).is_none() {
+            return Err(RpcError::invalid_params("slot range exceeds the storage").into());
+        }
+        self.backend.ensure_state_account_capacity(address).await?;
+        for (i, value) in values.iter().enumerate() {
+            self.backend.set_storage_at(address, start_slot + U256::from(i), *value).await?;
+        }
+        Ok(values.len())
+    }
+
+    /// Returns the fee the transaction paid, split into the fee for its execution and, for
+    /// transactions of forked OP stack and Arbitrum chains, the L1 data fee.
+    ///
+    /// The L1 data fee of transactions mined locally is the one the node charged, see
+    /// [NodeConfig::with_l2_gas_accounting](crate::NodeConfig::with_l2_gas_accounting), the one of
+    /// transactions of the forked chain is read from the remote receipt.
+    ///
+    /// Handler for RPC call: `anvil_effectiveFee`
+    pub async fn anvil_effective_fee(&self, hash: B256) -> Result<Option<EffectiveFee>> {
+        node_info!("anvil_effectiveFee");
+        let fork = self.get_fork();
+        if let Some(receipt) = self.backend.mined_transaction_receipt(hash) {
+            let receipt = receipt.inner;
+            let fee = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+            // transactions replayed from the forked chain without charging the L1 data fee locally
+            // paid the same one as on the remote, which doesn't know any other local transaction
+            let l1_fee = match fork {
+                _ if self.backend.is_optimism() => self.backend.l1_data_fee(hash).await?,
+                Some(fork) if fork.is_replayed_transaction(hash) => {
+                    fork.effective_fee(hash).await?.and_then(|fee| fee.l1_fee)
+                }
+                _ => None,
+            };
+            return Ok(Some(EffectiveFee::new(fee, l1_fee)));
+        }
+
+        if let Some(fork) = fork {
+            return Ok(fork.effective_fee(hash).await?);
+        }
+
+        Ok(None)
+    }
+
+    /// Resets the fork like `anvil_reset` and re-applies the local overrides selected by
+    /// `options` on top of the new remote state.
+    ///
+    /// Balances dealt via `anvil_dealERC20` are written back to the slot they were found at. If
+    /// the token no longer resolves the holder's balance to that slot at the new block, a warning
+    /// is logged and the raw slot is written regardless.
+    ///
+    /// Handler for RPC call: `anvil_resetWithOptions`
+    pub async fn anvil_reset_with_options(
+        &self,
+        forking: Forking,
+        options: ResetOptions,
+    ) -> Result<()> {
+        self.reset_instance_id();
+        node_info!("anvil_resetWithOptions");
+        let impersonated = self.backend.cheats().impersonated_accounts();
+
+        self.backend.reset_fork(forking).await?;
+        // only taken once the reset succeeded, so they're kept if it fails
+        let overrides = std::mem::take(&mut *self.local_overrides.lock());
+
+        if options.preserve_impersonations {
+            for address in impersonated {
+                self.backend.impersonate(address);
+            }
+        }
+
+        if options.preserve_code {
+            for (address, code) in overrides.code {
+                self.backend.set_code(address, code.clone()).await?;
+                self.local_overrides.lock().code.insert(address, code);
+            }
+        }
+
+        if options.preserve_balances {
+            for (address, balance) in overrides.balances {
+                self.backend.set_balance(address, balance).await?;
+                self.local_overrides.lock().balances.insert(address, balance);
+            }
+
+            sol! {
+                #[sol(rpc)]
+                contract IERC20 {
+                    function balanceOf(address target) external view returns (uint256);
+                }
+            }
+
+            for ((token, holder), (slot, balance)) in overrides.erc20_balances {
+                let calldata = IERC20::balanceOfCall { target: holder }.abi_encode().into();
+                match self.find_erc20_storage_slot(token, calldata, balance).await {
+                    Ok(found) if found == slot => {}
+                    _ => warn!(
+                        target: "node",
+                        %token,
+                        %holder,
+                        %slot,
+                        "ERC20 balance slot differs at the new fork block, writing it anyway"
+                    ),
+                }
+                self.backend
+                    .set_storage_at(
+                        token,
+                        U256::from_be_bytes(slot.0),
+                        B256::from(balance.to_be_bytes()),
+                    )
+                    .await?;
+                self.local_overrides.lock().erc20_balances.insert((token, holder), (slot, balance));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the difficulty and total difficulty of each block in the range. Blocks up to the
+    /// fork are read from the remote endpoint, the total difficulty of later blocks is accumulated
+    /// on top of the fork's. The range is capped like the range of `eth_getLogs` queries.
+    ///
+    /// Handler for RPC call: `anvil_difficultyHistory`
+    pub async fn anvil_difficulty_history(
+        &self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<DifficultyHistoryEntry>> {
+        node_info!("anvil_difficultyHistory");
+        let from = self.backend.ensure_block_number(Some(BlockId::Number(from_block))).await?;
+        let to = self.backend.ensure_block_number(Some(BlockId::Number(to_block))).await?;
+        if from > to {
+            return Err(RpcError::invalid_params("from block is greater than to block").into());
+        }
+        self.backend.ensure_block_range(from, to)?;
+        let mut history = Vec::new();
+
+        let (first_local, mut total_difficulty) = match self.get_fork() {
+            Some(fork) => {
+                for number in from..=to.min(fork.block_number()) {
+                    let block = fork
+                        .block_by_number(number)
+                        .await?
+                        .ok_or(BlockchainError::BlockNotFound)?;
+                    history.push(DifficultyHistoryEntry {
+                        number,
+                        difficulty: block.header.difficulty,
+                        total_difficulty: block.header.total_difficulty,
+                    });
+                }
+                (fork.block_number() + 1, fork.total_difficulty())
+            }
+            None => (0, U256::ZERO),
+        };
+
+        // the local total difficulty only accumulates mined blocks, not the genesis block
+        for number in first_local..=to {
+            let block = self.backend.get_block(number).ok_or(BlockchainError::BlockNotFound)?;
+            if number != 0 {
+                total_difficulty = total_difficulty.saturating_add(block.header.difficulty);
+            }
+            if number >= from {
+                history.push(DifficultyHistoryEntry {
+                    number,
+                    difficulty: block.header.difficulty,
+                    total_difficulty: Some(total_difficulty),
+                });
+            }
+        }
+        Ok(history)
+    }
+
+    /// Transfers an ERC721 token to `owner` by rewriting the slot `ownerOf(token_id)` reads, and
+    /// moves one unit of the `balanceOf` counter from the previous owner to `owner`.
+    ///
+    /// Enumeration indices, e.g. those of `ERC721Enumerable`, are not updated.
+    ///
+    /// Handler for RPC call: `anvil_dealERC721`
+    pub async fn anvil_deal_erc721(
+        &self,
+        owner: Address,
+        token_address: Address,
+        token_id: U256,
+    ) -> Result<()> {
+        node_info!("anvil_dealERC721");
+
+        sol! {
+            #[sol(rpc)]
+            contract IERC721 {
+                function ownerOf(uint256 tokenId) external view returns (address);
+                function balanceOf(address owner) external view returns (uint256);
+            }
+        }
+
+        let owner_of: Bytes = IERC721::ownerOfCall { tokenId: token_id }.abi_encode().into();
+        let tx = TransactionRequest::default().with_to(token_address).with_input(owner_of.clone());
+        let previous_owner = self
+            .call(WithOtherFields::new(tx), None, EvmOverrides::default())
+            .await
+            .ok()
+            .and_then(|res| Address::abi_decode(&res).ok())
+            .unwrap_or_default();
+        if previous_owner == owner {
+            return Ok(());
+        }
+
+        // Find the storage slots before any of them is written
+        let owner_slot = self
+            .find_erc20_storage_slot(
+                token_address,
+                owner_of,
+                U256::from_be_bytes(owner.into_word().0),
+            )
+            .await
+            .map_err(|_| {
+                BlockchainError::Message(format!(
+                    "Unable to set ERC721 owner, no slot found for `ownerOf({token_id})`"
+                ))
+            })?;
+        let mut balance_updates = Vec::with_capacity(2);
+        for (account, increase) in [(owner, true), (previous_owner, false)] {
+            if account.is_zero() {
+                continue;
+            }
+            let calldata: Bytes = IERC721::balanceOfCall { owner: account }.abi_encode().into();
+            let tx =
+                TransactionRequest::default().with_to(token_address).with_input(calldata.clone());
+            let balance =
+                self.call(WithOtherFields::new(tx), None, EvmOverrides::default()).await?;
+            let balance = U256::abi_decode(&balance).map_err(|_| {
+                BlockchainError::Message("Unable to read ERC721 balance".to_string())
+            })?;
+            let balance = if increase {
+                balance.saturating_add(U256::ONE)
+            } else {
+                balance.saturating_sub(U256::ONE)
+            };
+            let slot = self
+                .find_erc20_storage_slot(token_address, calldata, balance)
+                .await
+                .map_err(|_| {
+                    BlockchainError::Message(format!(
+                        "Unable to set ERC721 balance, no slot found for `balanceOf({account})`"
+                    ))
+                })?;
+            balance_updates.push((slot, balance));
+        }
+
+        self.anvil_set_storage_at(
+            token_address,
+            U256::from_be_bytes(owner_slot.0),
+            owner.into_word(),
+        )
+        .await?;
+        for (slot, balance) in balance_updates {
+            self.anvil_set_storage_at(
+                token_address,
+                U256::from_be_bytes(slot.0),
+                B256::from(balance.to_be_bytes()),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Classifies the account at `address` as an EOA, a contract or an empty account, and for
+    /// proxies returns the address calls are forwarded to.
+    ///
+    /// EOAs that delegated their code via EIP-7702 are reported as EOAs, with the delegate as
+    /// implementation.
+    ///
+    /// Handler for RPC call: `anvil_accountType`
+    pub async fn anvil_account_type(&self, address: Address) -> Result<AccountType> {
+        node_info!("anvil_accountType");
+        let account = self.get_account(address, None);
+        let code = self.get_code(address, None);
+        let (account, code) = try_join!(account, code)?;
+
+        if code.is_empty() {
+            let kind = if account.balance.is_zero() && account.nonce == 0 {
+                AccountKind::Empty
+            } else {
+                AccountKind::Eoa
+            };
+            return Ok(AccountType { kind, implementation: None });
+        }
+
+        if let Some(delegate) = code.strip_prefix(&EIP7702_DELEGATION_DESIGNATOR)
+            && delegate.len() == 20
+        {
+            return Ok(AccountType {
+                kind: AccountKind::Eoa,
+                implementation: Some(Address::from_slice(delegate)),
+            });
+        }
+
+        let implementation = if let Some(implementation) = code
+            .strip_prefix(&EIP1167_PREFIX)
+            .and_then(|rest| rest.strip_suffix(&EIP1167_SUFFIX))
+            .filter(|implementation| implementation.len() == 20)
+        {
+            Some(Address::from_slice(implementation))
+        } else {
+            let slot = self.storage_at(address, EIP1967_IMPLEMENTATION_SLOT.into(), None).await?;
+            (!slot.is_zero()).then(|| Address::from_word(slot))
+        };
+
+        Ok(AccountType { kind: AccountKind::Contract, implementation })
+    }
+
+    /// Makes the `NUMBER` opcode report the block number plus `offset` in calls and mined
+    /// transactions, without changing the height of the chain. An offset of `0` disables it.
+    ///
+    /// Handler for RPC call: `anvil_setBlockNumberOffset`
+    pub fn anvil_set_block_number_offset(&self, offset: i64) -> Result<()> {
+        node_info!("anvil_setBlockNumberOffset");
+        self.backend.cheats().set_block_number_offset(offset);
+        Ok(())
+    }
+
+    /// Returns all logs whose first topic is `topic0`, usually an event signature hash, in the
+    /// given range. Blocks up to the fork are queried on the remote endpoint.
+    ///
+    /// Handler for RPC call: `anvil_logsByTopic0`
+    pub async fn anvil_logs_by_topic0(
+        &self,
+        topic0: B256,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<Log>> {
+        node_info!("anvil_logsByTopic0");
+        let filter =
+            Filter::new().event_signature(topic0).from_block(from_block).to_block(to_block);
+        self.backend.logs(filter).await
+    }
+
+    /// Deploys `init_code` with `value` from the first account. If the constructor reverts nothing
+    /// is sent and the (decoded) revert reason is returned instead of an error.
+    ///
+    /// Handler for RPC call: `anvil_deployAllowRevert`
+    pub async fn anvil_deploy_allow_revert(
+        &self,
+        init_code: Bytes,
+        value: U256,
+    ) -> Result<DeployResult> {
+        node_info!("anvil_deployAllowRevert");
+        let from = self.accounts()?.first().copied().ok_or(BlockchainError::NoSignerAvailable)?;
+        let mut request = WithOtherFields::new(
+            TransactionRequest::default()
+                .with_from(from)
+                .with_deploy_code(init_code)
+                .with_value(value),
+        );
+
+        match self.call_uncached(request.clone(), None, EvmOverrides::default()).await {
+            Ok(_) => {}
+            Err(BlockchainError::InvalidTransaction(InvalidTransactionError::Revert(data))) => {
+                let revert_reason =
+                    data.as_ref().and_then(|data| RevertDecoder::new().maybe_decode(data, None));
+                return Ok(DeployResult {
+                    address: None,
+                    transaction_hash: None,
+                    revert_reason,
+                    revert_data: data,
+                });
+            }
+            Err(err) => return Err(err),
+        }
+
+        let (nonce, _) = self.request_nonce(&request, from).await?;
+        request.nonce = Some(nonce);
+        let hash = self.send_transaction(request).await?;
+        Ok(DeployResult {
+            address: Some(from.create(nonce)),
+            transaction_hash: Some(hash),
+            revert_reason: None,
+            revert_data: None,
+        })
+    }
+
+    /// Returns the inputs of the base fee calculation of the block following `block` and the
+    /// resulting base fee.
+    ///
+    /// Handler for RPC call: `anvil_baseFeeExplain`
+    pub async fn anvil_base_fee_explain(&self, block: BlockNumber) -> Result<BaseFeeExplanation> {
+        node_info!("anvil_baseFeeExplain");
+        let block =
+            self.backend.block_by_number(block).await?.ok_or(BlockchainError::BlockNotFound)?;
+        let header = &block.header;
+        let parent_base_fee = header.base_fee_per_gas.unwrap_or_default();
+        let next_base_fee = self.backend.fees().get_next_block_base_fee_per_gas(
+            header.gas_used,
+            header.gas_limit,
+            parent_base_fee,
+        );
+
+        Ok(BaseFeeExplanation {
+            parent_number: header.number,
+            parent_gas_used: header.gas_used,
+            parent_gas_limit: header.gas_limit,
+            gas_target: calculate_gas_target(header.gas_limit),
+            parent_base_fee,
+            next_base_fee,
+        })
+    }
+
+    /// Snapshot the state of the blockchain at the current block.
+    ///
+    /// Handler for RPC call: `evm_snapshot`
+    pub async fn evm_snapshot(&self) -> Result<U256> {
+        node_info!("evm_snapshot");
+        Ok(self.backend.create_state_snapshot().await)
+    }
+
+    /// Revert the state of the blockchain to a previous snapshot.
+    /// Takes a single parameter, which is the snapshot id to revert to.
+    ///
+    /// Handler for RPC call: `evm_revert`
+    pub async fn evm_revert(&self, id: U256) -> Result<bool> {
+        node_info!("evm_revert");
+        self.backend.revert_state_snapshot(id).await
+    }
+
+    /// Returns a guard that reverts the node to the state snapshot `id` once it's dropped, so
+    /// the state changes made while it's alive are undone at the end of the scope.
+    pub fn with_auto_revert_guard(&self, id: U256) -> AutoRevertGuard {
+        AutoRevertGuard { backend: Arc::clone(&self.backend), id, reverted: false }
+    }
+
+    /// Jump forward in time by the given amount of time, in seconds.
+    ///
+    /// Handler for RPC call: `evm_increaseTime`
+    pub async fn evm_increase_time(&self, seconds: U256) -> Result<i64> {
+        node_info!("evm_increaseTime");
+        Ok(self.backend.time().increase_time(seconds.try_into().unwrap_or(u64::MAX)) as i64)
+    }
+
+    /// Similar to `evm_increaseTime` but takes the exact timestamp that you want in the next block
+    ///
+    /// Handler for RPC call: `evm_setNextBlockTimestamp`
+    pub fn evm_set_next_block_timestamp(&self, seconds: u64) -> Result<()> {
+        node_info!("evm_setNextBlockTimestamp");
+        self.backend.time().set_next_block_timestamp(seconds)
+    }
+
+    /// Sets the specific timestamp and returns the number of seconds between the given timestamp
+    /// and the current time.
+    ///
+    /// Handler for RPC call: `evm_setTime`
+    pub fn evm_set_time(&self, timestamp: u64) -> Result<u64> {
+        node_info!("evm_setTime");
+        let now = self.backend.time().current_call_timestamp();
+        self.backend.time().reset(timestamp);
+
+        // number of seconds between the given timestamp and the current time.
+        let offset = timestamp.saturating_sub(now);
+        Ok(Duration::from_millis(offset).as_secs())
+    }
+
+    /// Set the next block gas limit
+    ///
+    /// Handler for RPC call: `evm_setBlockGasLimit`
+    pub fn evm_set_block_gas_limit(&self, gas_limit: U256) -> Result<bool> {
+        node_info!("evm_setBlockGasLimit");
+        self.backend.set_gas_limit(gas_limit.to());
+        Ok(true)
+    }
+
+    /// Sets an interval for the block timestamp
+    ///
+    /// Handler for RPC call: `anvil_setBlockTimestampInterval`
+    pub fn evm_set_block_timestamp_interval(&self, seconds: u64) -> Result<()> {
+        node_info!("anvil_setBlockTimestampInterval");
+        self.backend.time().set_block_timestamp_interval(seconds);
+        Ok(())
+    }
+
+    /// Sets an interval for the block timestamp
+    ///
+    /// Handler for RPC call: `anvil_removeBlockTimestampInterval`
+    pub fn evm_remove_block_timestamp_interval(&self) -> Result<bool> {
+        node_info!("anvil_removeBlockTimestampInterval");
+        Ok(self.backend.time().remove_block_timestamp_interval())
+    }
+
+    /// Mine blocks, instantly.
+    ///
+    /// Handler for RPC call: `evm_mine`
+    ///
+    /// This will mine the blocks regardless of the configured mining mode.
+    /// **Note**: ganache returns `0x0` here as placeholder for additional meta-data in the future.
+    pub async fn evm_mine(&self, opts: Option<MineOptions>) -> Result<String> {
+        node_info!("evm_mine");
+
+        self.do_evm_mine(opts).await?;
+
+        Ok("0x0".to_string())
+    }
+
+    /// Mine blocks, instantly and return the mined blocks.
+    ///
+    /// Handler for RPC call: `evm_mine_detailed`
+    ///
+    /// This will mine the blocks regardless of the configured mining mode.
+    ///
+    /// **Note**: This behaves exactly as [Self::evm_mine] but returns different output, for
+    /// compatibility reasons, this is a separate call since `evm_mine` is not an anvil original.
+    /// and `ganache` may change the `0x0` placeholder.
+    pub async fn evm_mine_detailed(&self, opts: Option<MineOptions>) -> Result<Vec<AnyRpcBlock>> {
+        node_info!("evm_mine_detailed");
+
+        let mined_blocks = self.do_evm_mine(opts).await?;
+
+        let mut blocks = Vec::with_capacity(mined_blocks as usize);
+
+        let latest = self.backend.best_number();
+        for offset in (0..mined_blocks).rev() {
+            let block_num = latest - offset;
             if let Some(mut block) =
                 self.backend.block_by_number_full(BlockNumber::Number(block_num)).await?
             {
@@ -2651,7 +4489,9 @@ pub async fn eth_send_unsigned_transaction(
         let requires = required_marker(nonce, on_chain_nonce, from);
         let provides = vec![to_marker(nonce, from)];
 
-        self.add_pending_transaction(pending_transaction, requires, provides)
+        let hash = self.add_pending_transaction(pending_transaction, requires, provides)?;
+        self.mine_sent_transaction(hash).await;
+        Ok(hash)
     }
 
     /// Returns the number of transactions currently pending for inclusion in the next block(s), as
@@ -2924,7 +4764,7 @@ async fn do_evm_mine(&self, opts: Option<MineOptions>) -> Result<u64> {
         self.on_blocking_task(|this| async move {
             // mine all the blocks
             for _ in 0..blocks_to_mine {
-                this.mine_one().await;
+                this.mine_one().await?;
             }
             Ok(())
         })
@@ -2938,7 +4778,7 @@ async fn do_estimate_gas(
         request: WithOtherFields<TransactionRequest>,
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
-    ) -> Result<u128> {
+    ) -> Result<GasEstimateRange> {
         let block_request = self.block_request(block_number).await?;
         // check if the number predates the fork, if in fork mode
         if let BlockRequest::Number(number) = block_request
@@ -2950,7 +4790,8 @@ async fn do_estimate_gas(
                     "not available on past forked blocks".to_string(),
                 ));
             }
-            return Ok(fork.estimate_gas(&request, Some(number.into())).await?);
+            let estimate = fork.estimate_gas(&request, Some(number.into())).await?;
+            return Ok(GasEstimateRange::exact(estimate as u64));
         }
 
         // this can be blocking for a bit, especially in forking mode
@@ -2982,8 +4823,12 @@ fn do_estimate_gas_with_state(
         &self,
         mut request: WithOtherFields<TransactionRequest>,
         state: &dyn DatabaseRef,
-        block_env: BlockEnv,
-    ) -> Result<u128> {
+        mut block_env: BlockEnv,
+    ) -> Result<GasEstimateRange> {
+        if self.estimate_ignore_block_limit {
+            block_env.gas_limit = u64::MAX;
+        }
+
         // If the request is a simple native token transfer we can optimize
         // We assume it's a transfer if we have no input data.
         let to = request.to.as_ref().and_then(TxKind::to);
@@ -3000,7 +4845,7 @@ fn do_estimate_gas_with_state(
             && let Ok(target_code) = self.backend.get_code_with_state(&state, *to)
             && target_code.as_ref().is_empty()
         {
-            return Ok(MIN_TRANSACTION_GAS);
+            return Ok(GasEstimateRange::exact(MIN_TRANSACTION_GAS as u64));
         }
 
         let fees = FeeDetails::new(
@@ -3014,6 +4859,9 @@ fn do_estimate_gas_with_state(
         // get the highest possible gas limit, either the request's set value or the currently
         // configured gas limit
         let mut highest_gas_limit = request.gas.map_or(block_env.gas_limit.into(), |g| g as u128);
+        // whether the estimation is bounded by the block gas limit, which isn't the case if it's
+        // ignored
+        let mut capped_by_block_limit = request.gas.is_none() && !self.estimate_ignore_block_limit;
 
         let gas_price = fees.gas_price.unwrap_or_default();
         // If we have non-zero gas price, cap gas limit by sender balance
@@ -3030,9 +4878,15 @@ fn do_estimate_gas_with_state(
             }
             // amount of gas the sender can afford with the `gas_price`
             let allowance = available_funds.checked_div(U256::from(gas_price)).unwrap_or_default();
-            highest_gas_limit = std::cmp::min(highest_gas_limit, allowance.saturating_to());
+            if allowance < U256::from(highest_gas_limit) {
+                highest_gas_limit = allowance.saturating_to();
+                capped_by_block_limit = false;
+            }
         }
 
+        // the highest gas limit the estimation starts from
+        let upper_gas_limit = highest_gas_limit;
+
         let mut call_to_estimate = request.clone();
         call_to_estimate.gas = Some(highest_gas_limit as u64);
 
@@ -3042,6 +4896,10 @@ fn do_estimate_gas_with_state(
 
         let gas_used = match ethres.try_into()? {
             GasEstimationCallResult::Success(gas) => Ok(gas),
+            GasEstimationCallResult::OutOfGas if capped_by_block_limit => {
+                Err(InvalidTransactionError::GasRequiredExceedsBlockGasLimit(block_env.gas_limit)
+                    .into())
+            }
             GasEstimationCallResult::OutOfGas => {
                 Err(InvalidTransactionError::BasicOutOfGas(highest_gas_limit).into())
             }
@@ -3101,7 +4959,11 @@ fn do_estimate_gas_with_state(
 
         trace!(target : "node", "Estimated Gas for call {:?}", highest_gas_limit);
 
-        Ok(highest_gas_limit)
+        Ok(GasEstimateRange {
+            lower: gas_used as u64,
+            estimate: highest_gas_limit as u64,
+            upper: upper_gas_limit as u64,
+        })
     }
 
     /// Updates the `TransactionOrder`
@@ -3180,13 +5042,38 @@ pub fn is_fork(&self) -> bool {
         self.backend.is_fork()
     }
 
-    /// Mines exactly one block
-    pub async fn mine_one(&self) {
+    /// Mines exactly one block, fails while mining is halted by a failed invariant check
+    pub async fn mine_one(&self) -> Result<()> {
         let transactions = self.pool.ready_transactions().collect::<Vec<_>>();
-        let outcome = self.backend.mine_block(transactions).await;
+        let outcome = self.backend.mine_block(transactions).await?;
 
         trace!(target: "node", blocknumber = ?outcome.block_number, "mined block");
         self.pool.on_mined_block(outcome);
+        Ok(())
+    }
+
+    /// Returns a channel that receives every failed invariant check, see
+    /// [Self::anvil_set_invariant_check]
+    pub fn invariant_violation_notifications(&self) -> InvariantViolationNotifications {
+        self.backend.invariant_violation_notifications()
+    }
+
+    /// Mines a block containing just the sent transaction if mine on send is enabled and the
+    /// transaction is ready.
+    async fn mine_sent_transaction(&self, hash: TxHash) {
+        if !self.mine_on_send {
+            return;
+        }
+        if let Some(tx) = self.pool.ready_transactions().find(|tx| tx.hash() == hash) {
+            match self.backend.mine_block(vec![tx]).await {
+                Ok(outcome) => {
+                    trace!(target: "node", blocknumber = ?outcome.block_number, "mined sent transaction");
+                    self.pool.on_mined_block(outcome);
+                }
+                // the transaction stays pending until mining resumes
+                Err(err) => trace!(target: "node", %err, "didn't mine sent transaction"),
+            }
+        }
     }
 
     /// Returns the pending block with tx hashes
@@ -3400,6 +5287,23 @@ fn add_pending_transaction(
         let priority = self.transaction_priority(&pending_transaction.transaction);
         let pool_transaction =
             PoolTransaction { requires, provides, pending_transaction, priority };
+
+        if let Some(delay) = self.inclusion_delay {
+            // simulate propagation latency by only adding the transaction once the delay elapsed,
+            // transactions the pool rejects right away are still rejected right away
+            self.pool.validate_transaction(&pool_transaction)?;
+            let hash = pool_transaction.hash();
+            let pool = Arc::clone(&self.pool);
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                if let Err(err) = pool.add_transaction(pool_transaction) {
+                    warn!(target: "node", ?hash, %err, "failed to add delayed transaction");
+                }
+            });
+            trace!(target: "node", "Delayed transaction: [{:?}] sender={:?}", hash, from);
+            return Ok(hash);
+        }
+
         let tx = self.pool.add_transaction(pool_transaction)?;
         trace!(target: "node", "Added transaction: [{:?}] sender={:?}", tx.hash(), from);
         Ok(*tx.hash())
@@ -3412,6 +5316,13 @@ pub async fn state_root(&self) -> Option<B256> {
 
     /// additional validation against hardfork
     fn ensure_typed_transaction_supported(&self, tx: &TypedTransaction) -> Result<()> {
+        let tx_type = tx.r#type().unwrap_or_default();
+        if let Some(allowed_tx_types) = &self.allowed_tx_types
+            && !allowed_tx_types.contains(&tx_type)
+        {
+            return Err(BlockchainError::TransactionTypeNotAllowed(tx_type));
+        }
+
         match &tx {
             TypedTransaction::EIP2930(_) => self.backend.ensure_eip2930_active(),
             TypedTransaction::EIP1559(_) => self.backend.ensure_eip1559_active(),
@@ -3423,6 +5334,66 @@ fn ensure_typed_transaction_supported(&self, tx: &TypedTransaction) -> Result<()
     }
 }
 
+/// Local overrides recorded so `anvil_resetWithOptions` can re-apply them after a reset
+#[derive(Debug, Default)]
+struct LocalOverrides {
+    /// Balances set via `anvil_setBalance` and `anvil_addBalance`
+    balances: HashMap<Address, U256>,
+    /// Code set via `anvil_setCode`
+    code: HashMap<Address, Bytes>,
+    /// Slot and balance dealt via `anvil_dealERC20`, keyed by token and holder
+    erc20_balances: HashMap<(Address, Address), (B256, U256)>,
+}
+
+/// Reverts the node to a state snapshot when dropped, see [`EthApi::with_auto_revert_guard`].
+///
+/// Dropping the guard awaits the revert in place on a multi-threaded tokio runtime. On a
+/// current-thread runtime the revert is spawned instead and only applied once the runtime gets to
+/// it, use [`AutoRevertGuard::revert`] to revert right away. Outside a tokio runtime nothing is
+/// reverted.
+#[must_use = "the state snapshot is reverted as soon as the guard is dropped"]
+pub struct AutoRevertGuard {
+    backend: Arc<backend::mem::Backend>,
+    id: U256,
+    /// Whether the state snapshot was already reverted via [`AutoRevertGuard::revert`]
+    reverted: bool,
+}
+
+impl AutoRevertGuard {
+    /// Reverts the node to the state snapshot now instead of when the guard is dropped.
+    pub async fn revert(mut self) -> Result<bool> {
+        self.reverted = true;
+        self.backend.revert_state_snapshot(self.id).await
+    }
+}
+
+impl Drop for AutoRevertGuard {
+    fn drop(&mut self) {
+        if self.reverted {
+            return;
+        }
+        let (backend, id) = (Arc::clone(&self.backend), self.id);
+        let revert = async move {
+            let reverted = backend.revert_state_snapshot(id).await;
+            if !matches!(reverted, Ok(true)) {
+                warn!(target: "node", %id, ?reverted, "failed to revert state snapshot on drop");
+            }
+        };
+        // never panic here, the guard is most useful while a failing test is unwinding
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(move || handle.block_on(revert))
+            }
+            Ok(handle) => {
+                handle.spawn(revert);
+            }
+            Err(_) => {
+                warn!(target: "node", %id, "not reverting state snapshot on drop outside a tokio runtime");
+            }
+        }
+    }
+}
+
 fn required_marker(provided_nonce: u64, on_chain_nonce: u64, from: Address) -> Vec<TxMarker> {
     if provided_nonce == on_chain_nonce {
         return Vec::new();
@@ -3431,6 +5402,18 @@ fn required_marker(provided_nonce: u64, on_chain_nonce: u64, from: Address) -> V
     if on_chain_nonce <= prev_nonce { vec![to_marker(prev_nonce, from)] } else { Vec::new() }
 }
 
+/// Returns the init code of the latest deployment to `address` in the given traces
+fn find_creation_code(traces: Vec<LocalizedTransactionTrace>, address: Address) -> Option<Bytes> {
+    traces.into_iter().rev().find_map(|trace| match (trace.trace.action, trace.trace.result) {
+        (Action::Create(CreateAction { init, .. }), Some(TraceOutput::Create(output)))
+            if output.address == address =>
+        {
+            Some(init)
+        }
+        _ => None,
+    })
+}
+
 fn convert_transact_out(out: &Option<Output>) -> Bytes {
     match out {
         None => Default::default(),
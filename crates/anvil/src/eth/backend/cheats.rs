@@ -36,6 +36,12 @@ pub fn stop_impersonating(&self, addr: &Address) {
         self.state.write().impersonated_accounts.remove(addr);
     }
 
+    /// Stops impersonating all accounts
+    pub fn clear_impersonated_accounts(&self) {
+        trace!(target: "cheats", "Stop impersonating all accounts");
+        self.state.write().impersonated_accounts.clear();
+    }
+
     /// Returns true if the `addr` is currently impersonated
     pub fn is_impersonated(&self, addr: Address) -> bool {
         if self.auto_impersonate_accounts() {
@@ -61,6 +67,29 @@ pub fn set_auto_impersonate_account(&self, enabled: bool) {
     pub fn impersonated_accounts(&self) -> AddressHashSet {
         self.state.read().impersonated_accounts.clone()
     }
+
+    /// Replaces the set of addresses that can't be called
+    pub fn set_call_denylist(&self, addresses: impl IntoIterator<Item = Address>) {
+        let call_denylist = addresses.into_iter().collect::<AddressHashSet>();
+        trace!(target: "cheats", "Call denylist set to {:?}", call_denylist);
+        self.state.write().call_denylist = call_denylist;
+    }
+
+    /// Returns all addresses that can't be called
+    pub fn call_denylist(&self) -> AddressHashSet {
+        self.state.read().call_denylist.clone()
+    }
+
+    /// Sets the offset added to the block number the `NUMBER` opcode reports
+    pub fn set_block_number_offset(&self, offset: i64) {
+        trace!(target: "cheats", "Block number offset set to {offset}");
+        self.state.write().block_number_offset = offset;
+    }
+
+    /// Returns the offset added to the block number the `NUMBER` opcode reports
+    pub fn block_number_offset(&self) -> i64 {
+        self.state.read().block_number_offset
+    }
 }
 
 /// Container type for all the state variables
@@ -70,4 +99,8 @@ pub struct CheatsState {
     pub impersonated_accounts: AddressHashSet,
     /// If set to true will make the `is_impersonated` function always return true
     pub auto_impersonate_accounts: bool,
+    /// Calls to these addresses revert
+    pub call_denylist: AddressHashSet,
+    /// Offset added to the block number the `NUMBER` opcode reports
+    pub block_number_offset: i64,
 }
@@ -140,6 +140,9 @@ fn set_code(&mut self, address: Address, code: Bytes) -> DatabaseResult<()> {
     /// Sets the storage value at the given slot for the address
     fn set_storage_at(&mut self, address: Address, slot: B256, val: B256) -> DatabaseResult<()>;
 
+    /// Replaces the account with an empty one without any code or storage
+    fn clear_account(&mut self, address: Address) -> DatabaseResult<()>;
+
     /// inserts a blockhash for the given number
     fn insert_block_hash(&mut self, number: U256, hash: B256);
 
@@ -222,6 +225,11 @@ fn set_storage_at(&mut self, address: Address, slot: B256, val: B256) -> Databas
         self.insert_account_storage(address, slot.into(), val.into())
     }
 
+    fn clear_account(&mut self, address: Address) -> DatabaseResult<()> {
+        self.insert_account_info(address, AccountInfo::default());
+        self.replace_account_storage(address, Default::default())
+    }
+
     fn insert_block_hash(&mut self, number: U256, hash: B256) {
         self.cache.block_hashes.insert(number, hash);
     }
@@ -524,6 +532,11 @@ pub struct SerializableState {
     /// Note: This is an Option for backwards compatibility.
     #[serde(default)]
     pub historical_states: Option<SerializableHistoricalStates>,
+    /// The chain id of the state.
+    ///
+    /// Note: This is an Option for backwards compatibility.
+    #[serde(default)]
+    pub chain_id: Option<u64>,
 }
 
 impl SerializableState {
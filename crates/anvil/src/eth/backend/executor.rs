@@ -8,16 +8,19 @@
         error::InvalidTransactionError,
         pool::transactions::PoolTransaction,
     },
+    hardfork::{miner_reward, uncle_reward},
     inject_precompiles,
     mem::inspector::AnvilInspector,
 };
 use alloy_consensus::{
-    Receipt, ReceiptWithBloom, constants::EMPTY_WITHDRAWALS, proofs::calculate_receipt_root,
+    Header, Receipt, ReceiptWithBloom,
+    constants::EMPTY_WITHDRAWALS,
+    proofs::{calculate_ommers_root, calculate_receipt_root},
 };
 use alloy_eips::{eip7685::EMPTY_REQUESTS_HASH, eip7840::BlobParams};
 use alloy_evm::{EthEvm, Evm, eth::EthEvmContext, precompiles::PrecompilesMap};
 use alloy_op_evm::OpEvm;
-use alloy_primitives::{B256, Bloom, BloomInput, Log};
+use alloy_primitives::{Address, B256, Bloom, BloomInput, Log, U256, map::AddressHashSet};
 use anvil_core::eth::{
     block::{Block, BlockInfo, PartialHeader},
     transaction::{
@@ -33,7 +36,7 @@
 use revm::{
     Database, DatabaseRef, Inspector, Journal,
     context::{Block as RevmBlock, BlockEnv, CfgEnv, Evm as RevmEvm, JournalTr, LocalContext},
-    context_interface::result::{EVMError, ExecutionResult, Output},
+    context_interface::result::{EVMError, ExecutionResult, Output, ResultAndState},
     database::WrapDatabaseRef,
     handler::{EthPrecompiles, instructions::EthInstructions},
     interpreter::InstructionResult,
@@ -127,6 +130,21 @@ pub struct TransactionExecutor<'a, Db: ?Sized, V: TransactionValidator> {
     /// Precompiles to inject to the EVM.
     pub precompile_factory: Option<Arc<dyn PrecompileFactory>>,
     pub blob_params: BlobParams,
+    /// Addresses that can't be called
+    pub call_denylist: AddressHashSet,
+    /// Maximum size of the data a call frame may return
+    pub max_returndata_size: Option<usize>,
+    /// Offset added to the block number the `NUMBER` opcode reports
+    pub block_number_offset: i64,
+    /// Whether access lists are applied, if not they're ignored during execution
+    pub access_list_gas: bool,
+    /// Uncles to include in the block
+    pub ommers: Vec<Header>,
+    /// Whether the pre-merge block and uncle rewards are paid
+    pub block_rewards: bool,
+    /// Highest number of accounts the state may hold, transactions that create accounts beyond
+    /// it are invalid
+    pub max_state_accounts: Option<usize>,
 }
 
 impl<DB: Db + ?Sized, V: TransactionValidator> TransactionExecutor<'_, DB, V> {
@@ -228,6 +246,20 @@ pub fn execute(mut self) -> ExecutedTransactions {
             transactions.push(transaction.pending_transaction.transaction.clone());
         }
 
+        let ommers = std::mem::take(&mut self.ommers);
+        if self.block_rewards {
+            let spec = self.cfg_env.spec;
+            if let Some(reward) = miner_reward(spec, ommers.len()) {
+                self.credit_reward(beneficiary, reward);
+            }
+            for ommer in &ommers {
+                if let Some(reward) = uncle_reward(spec, block_number.saturating_to(), ommer.number)
+                {
+                    self.credit_reward(ommer.beneficiary, reward);
+                }
+            }
+        }
+
         let receipts_root = calculate_receipt_root(&receipts);
 
         let partial_header = PartialHeader {
@@ -252,14 +284,36 @@ pub fn execute(mut self) -> ExecutedTransactions {
             requests_hash: is_prague.then_some(EMPTY_REQUESTS_HASH),
         };
 
-        let block = Block::new(partial_header, transactions.clone());
+        let mut block = Block::new(partial_header, transactions.clone());
+        if !ommers.is_empty() {
+            block.header.ommers_hash = calculate_ommers_root(&ommers);
+            block.ommers = ommers;
+        }
         let block = BlockInfo { block, transactions: transaction_infos, receipts };
         ExecutedTransactions { block, included, invalid }
     }
 
+    /// Adds the reward to the balance of the given account
+    fn credit_reward(&mut self, address: Address, reward: u128) {
+        match self.db.basic(address) {
+            Ok(account) => {
+                let mut info = account.unwrap_or_default();
+                info.balance = info.balance.saturating_add(U256::from(reward));
+                self.db.insert_account(address, info);
+            }
+            Err(err) => {
+                trace!(target: "backend", ?err, ?address, "Failed to credit block reward");
+            }
+        }
+    }
+
     fn env_for(&self, tx: &PendingTransaction) -> Env {
         let mut tx_env = tx.to_revm_tx_env();
 
+        if !self.access_list_gas {
+            tx_env.base.access_list = Default::default();
+        }
+
         if self.optimism {
             tx_env.enveloped_tx = Some(alloy_rlp::encode(&tx.transaction.transaction).into());
         }
@@ -332,8 +386,17 @@ fn next(&mut self) -> Option<Self::Item> {
         if self.print_traces {
             inspector = inspector.with_trace_printer();
         }
+        if !self.call_denylist.is_empty() {
+            inspector = inspector.with_call_denylist(self.call_denylist.clone());
+        }
+        if let Some(max_returndata_size) = self.max_returndata_size {
+            inspector = inspector.with_max_returndata_size(max_returndata_size);
+        }
+        if self.block_number_offset != 0 {
+            inspector = inspector.with_block_number_offset(self.block_number_offset);
+        }
 
-        let exec_result = {
+        let ResultAndState { result: exec_result, state } = {
             let mut evm = new_evm_with_inspector(&mut *self.db, &env, &mut inspector);
 
             if self.odyssey {
@@ -345,9 +408,8 @@ fn next(&mut self) -> Option<Self::Item> {
             }
 
             trace!(target: "backend", "[{:?}] executing", transaction.hash());
-            // transact and commit the transaction
-            match evm.transact_commit(env.tx) {
-                Ok(exec_result) => exec_result,
+            match evm.transact(env.tx) {
+                Ok(result_and_state) => result_and_state,
                 Err(err) => {
                     warn!(target: "backend", "[{:?}] failed to execute: {:?}", transaction.hash(), err);
                     match err {
@@ -371,6 +433,22 @@ fn next(&mut self) -> Option<Self::Item> {
             }
         };
 
+        // the accounts loaded during execution are already part of the state, so it only exceeds
+        // the limit if the transaction created an account
+        if let Some(limit) = self.max_state_accounts
+            && state
+                .values()
+                .any(|account| account.is_touched() && account.is_loaded_as_not_existing())
+            && self.db.maybe_as_full_db().is_some_and(|accounts| accounts.len() > limit)
+        {
+            warn!(target: "backend", "[{:?}] exceeds the state account limit", transaction.hash());
+            return Some(TransactionExecutionOutcome::Invalid(
+                transaction,
+                InvalidTransactionError::StateAccountLimitExceeded(limit),
+            ));
+        }
+        self.db.commit(state);
+
         if self.print_traces {
             inspector.print_traces(self.call_trace_decoder.clone());
         }
@@ -1,11 +1,11 @@
 //! Support for forking off another client
 
 use crate::eth::{backend::db::Db, error::BlockchainError, pool::transactions::PoolTransaction};
-use alloy_consensus::Account;
+use alloy_consensus::{Account, Transaction as _};
 use alloy_eips::eip2930::AccessListResult;
 use alloy_network::{AnyRpcBlock, AnyRpcTransaction, BlockResponse, TransactionResponse};
 use alloy_primitives::{
-    Address, B256, Bytes, StorageValue, U256,
+    Address, B256, Bytes, StorageValue, U64, U256, keccak256,
     map::{FbHashMap, HashMap},
 };
 use alloy_provider::{
@@ -19,18 +19,24 @@
     simulate::{SimulatePayload, SimulatedBlock},
     trace::{
         geth::{GethDebugTracingOptions, GethTrace},
+        otterscan::ContractCreator,
         parity::LocalizedTransactionTrace as Trace,
     },
 };
 use alloy_serde::WithOtherFields;
 use alloy_transport::TransportError;
-use anvil_core::eth::transaction::{ReceiptResponse, convert_to_anvil_receipt};
+use alloy_trie::{EMPTY_ROOT_HASH, KECCAK_EMPTY, Nibbles, TrieAccount, proof::verify_proof};
+use anvil_core::{
+    eth::transaction::{ReceiptResponse, convert_to_anvil_receipt},
+    types::EffectiveFee,
+};
 use foundry_common::provider::{ProviderBuilder, RetryProvider};
+use foundry_evm::backend::{BlockchainDb, BlockchainDbMeta, SharedBackend};
 use parking_lot::{
     RawRwLock, RwLock,
     lock_api::{RwLockReadGuard, RwLockWriteGuard},
 };
-use revm::context_interface::block::BlobExcessGasAndPrice;
+use revm::{context::BlockEnv, context_interface::block::BlobExcessGasAndPrice};
 use std::{sync::Arc, time::Duration};
 use tokio::sync::RwLock as AsyncRwLock;
 
@@ -89,6 +95,7 @@ pub async fn reset(
         let timestamp = block.header.timestamp;
         let base_fee = block.header.base_fee_per_gas;
         let total_difficulty = block.header.total_difficulty.unwrap_or_default();
+        let state_root = block.header.state_root;
 
         let number = block.header.number;
         self.config.write().update_block(
@@ -97,6 +104,7 @@ pub async fn reset(
             timestamp,
             base_fee.map(|g| g as u128),
             total_difficulty,
+            state_root,
         );
 
         self.clear_cached_storage();
@@ -121,6 +129,16 @@ pub fn predates_fork_inclusive(&self, block: u64) -> bool {
         block <= self.block_number()
     }
 
+    /// Returns true whether the transaction was replayed from the forked block because the fork
+    /// was created at a transaction hash
+    pub fn is_replayed_transaction(&self, hash: B256) -> bool {
+        self.config
+            .read()
+            .force_transactions
+            .as_ref()
+            .is_some_and(|txs| txs.iter().any(|tx| tx.hash() == hash))
+    }
+
     pub fn timestamp(&self) -> u64 {
         self.config.read().timestamp
     }
@@ -146,6 +164,10 @@ pub fn block_hash(&self) -> B256 {
         self.config.read().block_hash
     }
 
+    pub fn state_root(&self) -> B256 {
+        self.config.read().state_root
+    }
+
     pub fn eth_rpc_url(&self) -> String {
         self.config.read().eth_rpc_url.clone()
     }
@@ -154,7 +176,67 @@ pub fn chain_id(&self) -> u64 {
         self.config.read().chain_id
     }
 
-    fn provider(&self) -> Arc<RetryProvider> {
+    /// Returns the chain id of the remote, which may differ from [`Self::chain_id`] if it is
+    /// overridden
+    pub fn rpc_chain_id(&self) -> u64 {
+        self.config.read().rpc_chain_id
+    }
+
+    /// Fetches the latest block number of the remote endpoint, which keeps advancing while the
+    /// fork stays pinned
+    pub async fn remote_block_number(&self) -> Result<u64, TransportError> {
+        self.provider().get_block_number().await
+    }
+
+    /// Returns the traces of the transaction that deployed the contract at the given address, if
+    /// the remote endpoint supports `ots_getContractCreator`
+    pub async fn contract_creation_traces(
+        &self,
+        address: Address,
+    ) -> Result<Vec<Trace>, TransportError> {
+        let Ok(Some(creator)) = self
+            .provider()
+            .raw_request::<_, Option<ContractCreator>>("ots_getContractCreator".into(), (address,))
+            .await
+        else {
+            return Ok(Vec::new());
+        };
+        self.trace_transaction(creator.hash).await
+    }
+
+    /// Returns a database that lazily fetches the state of the remote endpoint as of the given
+    /// block
+    pub async fn database_at(&self, number: u64, block_env: BlockEnv) -> SharedBackend {
+        let meta = BlockchainDbMeta::new(block_env, self.eth_rpc_url());
+        let db = BlockchainDb::new_skip_check(meta, None);
+        SharedBackend::spawn_backend(self.provider(), db, Some(number.into())).await
+    }
+
+    /// Returns the fee the transaction paid on the remote chain, including the L1 data fee that OP
+    /// stack (`l1Fee`) and Arbitrum (`gasUsedForL1`) chains report in their receipts
+    pub async fn effective_fee(&self, hash: B256) -> Result<Option<EffectiveFee>, TransportError> {
+        let Some(receipt) = self.provider().get_transaction_receipt(hash).await? else {
+            return Ok(None);
+        };
+        let gas_price = U256::from(receipt.effective_gas_price);
+        let gas_used = receipt.gas_used;
+
+        let fee = if let Some(Ok(l1_fee)) = receipt.other.get_deserialized::<U256>("l1Fee") {
+            EffectiveFee::new(U256::from(gas_used) * gas_price, Some(l1_fee))
+        } else if let Some(Ok(l1_gas)) = receipt.other.get_deserialized::<U64>("gasUsedForL1") {
+            // the L1 gas is part of the gas used on Arbitrum
+            let l1_gas = l1_gas.to::<u64>();
+            EffectiveFee::new(
+                U256::from(gas_used.saturating_sub(l1_gas)) * gas_price,
+                Some(U256::from(l1_gas) * gas_price),
+            )
+        } else {
+            EffectiveFee::new(U256::from(gas_used) * gas_price, None)
+        };
+        Ok(Some(fee))
+    }
+
+    pub fn provider(&self) -> Arc<RetryProvider> {
         self.config.read().provider.clone()
     }
 
@@ -279,6 +361,20 @@ pub async fn get_code(
         Ok(code)
     }
 
+    /// Returns the priority fee suggested by the remote endpoint, which is fetched once per fork
+    /// block
+    pub async fn max_priority_fee_per_gas(&self) -> Result<u128, TransportError> {
+        trace!(target: "backend::fork", "max_priority_fee_per_gas");
+        let block_number = self.block_number();
+        if let Some(fee) = self.storage_read().max_priority_fees.get(&block_number).copied() {
+            return Ok(fee);
+        }
+
+        let fee = self.provider().get_max_priority_fee_per_gas().await?;
+        self.storage_write().max_priority_fees.insert(block_number, fee);
+        Ok(fee)
+    }
+
     pub async fn get_balance(
         &self,
         address: Address,
@@ -623,6 +719,65 @@ fn convert_to_full_block(&self, mut block: AnyRpcBlock) -> AnyRpcBlock {
     }
 }
 
+/// Maximum number of accounts [`prove_state_root_sample`] proves
+const STATE_ROOT_SAMPLE_SIZE: usize = 16;
+
+/// Proves a sample of the accounts of block `number` against the state root of the block and
+/// returns the proven accounts.
+///
+/// The beneficiary of the block and the senders and recipients of its transactions are proven
+/// with `eth_getProof`, every proof must verify against the state root. If the endpoint doesn't
+/// return the block, nothing is proven.
+pub async fn prove_state_root_sample(
+    provider: &RetryProvider,
+    number: u64,
+) -> Result<Vec<(Address, TrieAccount)>, BlockchainError> {
+    let Some(block) = provider.get_block_by_number(number.into()).full().await? else {
+        return Ok(Vec::new());
+    };
+    let state_root = block.header.state_root;
+
+    let mut accounts = vec![block.header.beneficiary];
+    for tx in block.transactions.txns() {
+        accounts.push(tx.from());
+        accounts.extend(tx.to());
+    }
+    accounts.sort_unstable();
+    accounts.dedup();
+    accounts.truncate(STATE_ROOT_SAMPLE_SIZE);
+
+    let block_id = BlockId::number(number);
+    let mut proven = Vec::with_capacity(accounts.len());
+    for address in accounts {
+        let proof = provider.get_proof(address, vec![]).block_id(block_id).await?;
+        // some providers report zero hashes for nonexistent accounts
+        let code_hash = if proof.code_hash.is_zero() { KECCAK_EMPTY } else { proof.code_hash };
+        let storage_root =
+            if proof.storage_hash.is_zero() { EMPTY_ROOT_HASH } else { proof.storage_hash };
+        let exists = proof.nonce != 0
+            || !proof.balance.is_zero()
+            || code_hash != KECCAK_EMPTY
+            || storage_root != EMPTY_ROOT_HASH;
+        let account =
+            TrieAccount { nonce: proof.nonce, balance: proof.balance, storage_root, code_hash };
+        let expected = exists.then(|| alloy_rlp::encode(account));
+        verify_proof(
+            state_root,
+            Nibbles::unpack(keccak256(address)),
+            expected,
+            &proof.account_proof,
+        )
+        .map_err(|_| {
+            BlockchainError::Message(format!(
+                "state root mismatch at forked block {number}: account {address} is inconsistent \
+                 with state root {state_root}"
+            ))
+        })?;
+        proven.push((address, account));
+    }
+    Ok(proven)
+}
+
 /// Contains all fork metadata
 #[derive(Clone, Debug)]
 pub struct ClientForkConfig {
@@ -637,10 +792,15 @@ pub struct ClientForkConfig {
     pub provider: Arc<RetryProvider>,
     pub chain_id: u64,
     pub override_chain_id: Option<u64>,
+    /// The chain id of the remote, chain specifics are detected from it even if the chain id is
+    /// overridden
+    pub rpc_chain_id: u64,
     /// The timestamp for the forked block
     pub timestamp: u64,
     /// The basefee of the forked block
     pub base_fee: Option<u128>,
+    /// The state root of the forked block
+    pub state_root: B256,
     /// Blob gas used of the forked block
     pub blob_gas_used: Option<u128>,
     /// Blob excess gas and price of the forked block
@@ -653,10 +813,18 @@ pub struct ClientForkConfig {
     pub backoff: Duration,
     /// available CUPS
     pub compute_units_per_second: u64,
+    /// maximum number of concurrent requests
+    pub max_concurrent_requests: Option<usize>,
+    /// whether to use HTTP/2 with prior knowledge for `http://` endpoints
+    pub http2: bool,
+    /// whether requests fail right away once the remote couldn't be reached
+    pub fail_fast_when_offline: bool,
     /// total difficulty of the chain until this block
     pub total_difficulty: U256,
     /// Transactions to force include in the forked chain
     pub force_transactions: Option<Vec<PoolTransaction>>,
+    /// Block environment fields adjusted for the forked chain
+    pub chain_specific_env_changes: Vec<String>,
 }
 
 impl ClientForkConfig {
@@ -674,6 +842,9 @@ fn update_url(&mut self, url: String) -> Result<(), BlockchainError> {
                 .max_retry(self.retries)
                 .initial_backoff(self.backoff.as_millis() as u64)
                 .compute_units_per_second(self.compute_units_per_second)
+                .maybe_max_concurrent_requests(self.max_concurrent_requests)
+                .http2(self.http2)
+                .fail_fast_when_offline(self.fail_fast_when_offline)
                 .build()
                 .map_err(|_| BlockchainError::InvalidUrl(url.clone()))?, // .interval(interval),
         );
@@ -689,12 +860,14 @@ pub fn update_block(
         timestamp: u64,
         base_fee: Option<u128>,
         total_difficulty: U256,
+        state_root: B256,
     ) {
         self.block_number = block_number;
         self.block_hash = block_hash;
         self.timestamp = timestamp;
         self.base_fee = base_fee;
         self.total_difficulty = total_difficulty;
+        self.state_root = state_root;
         trace!(target: "fork", "Updated block number={} hash={:?}", block_number, block_hash);
     }
 }
@@ -715,6 +888,7 @@ pub struct ForkedStorage {
     pub block_traces: HashMap<u64, Vec<Trace>>,
     pub block_receipts: HashMap<u64, Vec<ReceiptResponse>>,
     pub code_at: HashMap<(Address, u64), Bytes>,
+    pub max_priority_fees: HashMap<u64, u128>,
 }
 
 impl ForkedStorage {
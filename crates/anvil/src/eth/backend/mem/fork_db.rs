@@ -29,6 +29,10 @@ fn set_storage_at(&mut self, address: Address, slot: B256, val: B256) -> Databas
         self.database_mut().set_storage_at(address, slot, val)
     }
 
+    fn clear_account(&mut self, address: Address) -> DatabaseResult<()> {
+        self.database_mut().clear_account(address)
+    }
+
     fn insert_block_hash(&mut self, number: U256, hash: B256) {
         self.inner().block_hashes().write().insert(number, hash);
     }
@@ -72,6 +76,7 @@ fn dump_state(
             blocks,
             transactions,
             historical_states,
+            chain_id: None,
         }))
     }
 
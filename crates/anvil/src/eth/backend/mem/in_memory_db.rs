@@ -29,6 +29,11 @@ fn set_storage_at(&mut self, address: Address, slot: B256, val: B256) -> Databas
         self.inner.insert_account_storage(address, slot.into(), val.into())
     }
 
+    fn clear_account(&mut self, address: Address) -> DatabaseResult<()> {
+        self.inner.insert_account_info(address, AccountInfo::default());
+        self.inner.replace_account_storage(address, Default::default())
+    }
+
     fn insert_block_hash(&mut self, number: U256, hash: B256) {
         self.inner.cache.block_hashes.insert(number, hash);
     }
@@ -72,6 +77,7 @@ fn dump_state(
             blocks,
             transactions,
             historical_states,
+            chain_id: None,
         }))
     }
 
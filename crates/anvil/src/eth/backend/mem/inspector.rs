@@ -1,7 +1,9 @@
 //! Anvil specific [`revm::Inspector`] implementation
 
 use crate::eth::macros::node_info;
-use alloy_primitives::{Address, Log, U256};
+use alloy_primitives::{Address, Log, U256, map::AddressHashSet};
+use alloy_sol_types::{Revert, SolError};
+use anvil_core::types::GasByOpcode;
 use foundry_evm::{
     call_inspectors,
     decode::decode_console_logs,
@@ -12,11 +14,15 @@
 };
 use revm::{
     Inspector,
+    bytecode::opcode,
     context::ContextTr,
     inspector::JournalExt,
     interpreter::{
-        CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, FrameInput, Gas, InstructionResult,
+        Interpreter, InterpreterAction, InterpreterResult,
+        gas::CALL_STIPEND,
         interpreter::EthInterpreter,
+        interpreter_types::{Jumps, LoopControl},
     },
 };
 use std::sync::Arc;
@@ -28,6 +34,16 @@ pub struct AnvilInspector {
     pub tracer: Option<TracingInspector>,
     /// Collects all `console.sol` logs
     pub log_collector: Option<LogCollector>,
+    /// Whether gas metering is disabled, see [`AnvilInspector::with_gas_metering_disabled`]
+    pub gas_metering_disabled: bool,
+    /// Addresses that can't be called, calls to them revert
+    pub call_denylist: AddressHashSet,
+    /// Maximum size of the data a call frame may return or revert with, create frames are exempt
+    pub max_returndata_size: Option<usize>,
+    /// Offset added to the block number the `NUMBER` opcode reports
+    pub block_number_offset: i64,
+    /// Whether the opcode currently executed is `NUMBER`
+    executing_number: bool,
 }
 
 impl AnvilInspector {
@@ -79,6 +95,32 @@ pub fn with_log_collector(mut self) -> Self {
         self
     }
 
+    /// Disables gas metering: the gas of every frame is restored after each step so execution
+    /// never runs out of gas and no refunds are accumulated.
+    pub fn with_gas_metering_disabled(mut self) -> Self {
+        self.gas_metering_disabled = true;
+        self
+    }
+
+    /// Reverts all calls to the given addresses
+    pub fn with_call_denylist(mut self, call_denylist: AddressHashSet) -> Self {
+        self.call_denylist = call_denylist;
+        self
+    }
+
+    /// Reverts every call frame that returns or reverts with more than `max_returndata_size` bytes
+    pub fn with_max_returndata_size(mut self, max_returndata_size: usize) -> Self {
+        self.max_returndata_size = Some(max_returndata_size);
+        self
+    }
+
+    /// Adds `block_number_offset` to the block number the `NUMBER` opcode reports, without
+    /// changing the block the transaction executes in
+    pub fn with_block_number_offset(mut self, block_number_offset: i64) -> Self {
+        self.block_number_offset = block_number_offset;
+        self
+    }
+
     /// Configures the `Tracer` [`revm::Inspector`] with a trace printer
     pub fn with_trace_printer(mut self) -> Self {
         self.tracer = Some(TracingInspector::new(TracingInspectorConfig::all().with_state_diffs()));
@@ -121,12 +163,27 @@ fn step(&mut self, interp: &mut Interpreter, ecx: &mut CTX) {
         call_inspectors!([&mut self.tracer], |inspector| {
             inspector.step(interp, ecx);
         });
+        if self.gas_metering_disabled {
+            restore_gas(interp);
+        }
+        if let Some(max_returndata_size) = self.max_returndata_size {
+            check_returndata_size(interp, max_returndata_size);
+        }
+        if self.block_number_offset != 0 {
+            self.executing_number = interp.bytecode.opcode() == opcode::NUMBER;
+        }
     }
 
     fn step_end(&mut self, interp: &mut Interpreter, ecx: &mut CTX) {
         call_inspectors!([&mut self.tracer], |inspector| {
             inspector.step_end(interp, ecx);
         });
+        if self.gas_metering_disabled {
+            restore_gas(interp);
+        }
+        if std::mem::take(&mut self.executing_number) {
+            offset_block_number(interp, self.block_number_offset);
+        }
     }
 
     #[allow(clippy::redundant_clone)]
@@ -142,6 +199,18 @@ fn call(&mut self, ecx: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome
             [&mut self.tracer, &mut self.log_collector],
             |inspector| inspector.call(ecx, inputs).map(Some),
         );
+        if self.call_denylist.contains(&inputs.bytecode_address) {
+            let output =
+                Revert::from(format!("call to denylisted address {}", inputs.bytecode_address));
+            return Some(CallOutcome::new(
+                InterpreterResult {
+                    result: InstructionResult::Revert,
+                    output: output.abi_encode().into(),
+                    gas: Gas::new(inputs.gas_limit),
+                },
+                inputs.return_memory_offset.clone(),
+            ));
+        }
         None
     }
 
@@ -174,6 +243,142 @@ fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
     }
 }
 
+/// Reverts the current call frame if it is about to return or revert with more than
+/// `max_returndata_size` bytes.
+///
+/// Create frames are skipped, the data they return is the deployed code and not returndata.
+fn check_returndata_size(interp: &mut Interpreter, max_returndata_size: usize) {
+    if !matches!(interp.bytecode.opcode(), opcode::RETURN | opcode::REVERT) {
+        return;
+    }
+    // only call frames execute code at a bytecode address
+    if interp.input.bytecode_address.is_none() {
+        return;
+    }
+    // the size is the second stack item, after the memory offset
+    let stack = interp.stack.data();
+    let Some(size) = stack.len().checked_sub(2).map(|i| stack[i]) else { return };
+    if size <= U256::from(max_returndata_size) {
+        return;
+    }
+    let output = Revert::from(format!(
+        "returndata size {size} exceeds limit of {max_returndata_size} bytes"
+    ));
+    interp.bytecode.set_action(InterpreterAction::new_return(
+        InstructionResult::Revert,
+        output.abi_encode().into(),
+        interp.gas,
+    ));
+}
+
+/// Adds `offset` to the block number `NUMBER` just pushed onto the stack.
+fn offset_block_number(interp: &mut Interpreter, offset: i64) {
+    let Some(number) = interp.stack.data_mut().last_mut() else { return };
+    let delta = U256::from(offset.unsigned_abs());
+    *number = if offset.is_negative() {
+        number.saturating_sub(delta)
+    } else {
+        number.saturating_add(delta)
+    };
+}
+
+/// [`revm::Inspector`] that accumulates the gas spent per opcode category
+#[derive(Clone, Debug, Default)]
+pub struct OpcodeGasInspector {
+    /// The opcode currently executed, the gas remaining and the gas refunded before it
+    current: Option<(u8, u64, i64)>,
+    /// The gas refunds earned per opcode category, in the order of [`GasByOpcode`]
+    refunds: [i64; 4],
+    /// The accumulated gas per opcode category
+    pub gas: GasByOpcode,
+}
+
+impl OpcodeGasInspector {
+    /// Deducts the gas refunded at the end of the transaction from the categories that earned
+    /// it, proportionally to the refunds they earned.
+    pub fn apply_refund(&mut self, refunded: u64) {
+        self.gas.refunded = refunded;
+        let earned = self.refunds.map(|refund| refund.max(0) as u128);
+        let total = earned.iter().sum::<u128>();
+        if total == 0 {
+            return;
+        }
+        let categories = [
+            &mut self.gas.storage,
+            &mut self.gas.memory,
+            &mut self.gas.calls,
+            &mut self.gas.compute,
+        ];
+        for (gas, earned) in categories.into_iter().zip(earned) {
+            *gas = gas.saturating_sub((refunded as u128 * earned / total) as u64);
+        }
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for OpcodeGasInspector
+where
+    CTX: ContextTr<Journal: JournalExt>,
+{
+    fn step(&mut self, interp: &mut Interpreter, _ecx: &mut CTX) {
+        self.current =
+            Some((interp.bytecode.opcode(), interp.gas.remaining(), interp.gas.refunded()));
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _ecx: &mut CTX) {
+        let Some((op, gas_remaining, gas_refunded)) = self.current.take() else { return };
+        // the gas forwarded to a new frame is spent by the opcodes of that frame, the stipend of
+        // value transfers is added on top of the forwarded gas and not paid by the caller
+        let forwarded = match &interp.bytecode.action {
+            Some(InterpreterAction::NewFrame(FrameInput::Call(inputs))) => {
+                if inputs.transfers_value() {
+                    inputs.gas_limit.saturating_sub(CALL_STIPEND)
+                } else {
+                    inputs.gas_limit
+                }
+            }
+            Some(InterpreterAction::NewFrame(FrameInput::Create(inputs))) => inputs.gas_limit,
+            _ => 0,
+        };
+        let cost = gas_remaining.saturating_sub(interp.gas.remaining()).saturating_sub(forwarded);
+        let category = match op {
+            opcode::SLOAD | opcode::SSTORE | opcode::TLOAD | opcode::TSTORE => 0,
+            opcode::MLOAD
+            | opcode::MSTORE
+            | opcode::MSTORE8
+            | opcode::MCOPY
+            | opcode::MSIZE
+            | opcode::CALLDATACOPY
+            | opcode::CODECOPY
+            | opcode::EXTCODECOPY
+            | opcode::RETURNDATACOPY => 1,
+            opcode::CALL
+            | opcode::CALLCODE
+            | opcode::DELEGATECALL
+            | opcode::STATICCALL
+            | opcode::CREATE
+            | opcode::CREATE2
+            | opcode::SELFDESTRUCT => 2,
+            _ => 3,
+        };
+        self.refunds[category] += interp.gas.refunded() - gas_refunded;
+        let gas = match category {
+            0 => &mut self.gas.storage,
+            1 => &mut self.gas.memory,
+            2 => &mut self.gas.calls,
+            _ => &mut self.gas.compute,
+        };
+        *gas += cost;
+    }
+}
+
+/// Resets the gas of the frame to its limit, keeping track of the memory expansion so it is only
+/// charged for new memory.
+fn restore_gas(interp: &mut Interpreter) {
+    let memory = *interp.gas.memory();
+    interp.gas = Gas::new(interp.gas.limit());
+    *interp.gas.memory_mut() = memory;
+}
+
 /// Prints all the logs
 pub fn print_logs(logs: &[Log]) {
     for log in decode_console_logs(logs) {
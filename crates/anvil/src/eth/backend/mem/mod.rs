@@ -8,17 +8,19 @@
     eth::{
         backend::{
             cheats::CheatsManager,
-            db::{Db, MaybeFullDatabase, SerializableState},
+            db::{Db, MaybeFullDatabase, SerializableState, StateDb},
             env::Env,
             executor::{ExecutedTransactions, TransactionExecutor},
-            fork::ClientFork,
+            fork::{self, ClientFork},
             genesis::GenesisConfig,
             mem::{
                 state::{storage_root, trie_accounts},
                 storage::MinedTransactionReceipt,
             },
-            notifications::{NewBlockNotification, NewBlockNotifications},
-            time::{TimeManager, utc_from_secs},
+            notifications::{
+                InvariantViolationNotifications, NewBlockNotification, NewBlockNotifications,
+            },
+            time::{TimeManager, duration_since_unix_epoch, utc_from_secs},
             validate::TransactionValidator,
         },
         error::{BlockchainError, ErrDetail, InvalidTransactionError},
@@ -27,9 +29,10 @@
         pool::transactions::PoolTransaction,
         sign::build_typed_transaction,
     },
+    hardfork::{ethereum_hardfork_from_block_tag, spec_id_from_ethereum_hardfork},
     inject_precompiles,
     mem::{
-        inspector::AnvilInspector,
+        inspector::{AnvilInspector, OpcodeGasInspector},
         storage::{BlockchainStorage, InMemoryBlockStates, MinedBlockOutcome},
     },
 };
@@ -52,8 +55,10 @@
     EthereumWallet, UnknownTxEnvelope, UnknownTypedTransaction,
 };
 use alloy_primitives::{
-    Address, B256, Bytes, TxHash, TxKind, U64, U256, address, hex, keccak256, logs_bloom,
-    map::HashMap, utils::Unit,
+    Address, B64, B256, Bytes, I256, Selector, TxHash, TxKind, U64, U256, address, hex, keccak256,
+    logs_bloom,
+    map::{HashMap, HashSet},
+    utils::Unit,
 };
 use alloy_rpc_types::{
     AccessList, Block as AlloyBlock, BlockId, BlockNumberOrTag as BlockNumber, BlockTransactions,
@@ -63,7 +68,7 @@
     request::TransactionRequest,
     serde_helpers::JsonStorageKey,
     simulate::{SimBlock, SimCallResult, SimulatePayload, SimulatedBlock},
-    state::EvmOverrides,
+    state::{EvmOverrides, StateOverride},
     trace::{
         filter::TraceFilter,
         geth::{
@@ -74,22 +79,29 @@
     },
 };
 use alloy_serde::{OtherFields, WithOtherFields};
-use alloy_signer::Signature;
+use alloy_signer::{Signature, SignerSync};
 use alloy_signer_local::PrivateKeySigner;
-use alloy_trie::{HashBuilder, Nibbles, proof::ProofRetainer};
-use anvil_core::eth::{
-    block::{Block, BlockInfo},
-    transaction::{
-        DepositReceipt, MaybeImpersonatedTransaction, PendingTransaction, ReceiptResponse,
-        TransactionInfo, TypedReceipt, TypedTransaction, has_optimism_fields,
-        transaction_request_to_typed,
+use alloy_trie::{HashBuilder, Nibbles, TrieAccount, proof::ProofRetainer};
+use anvil_core::{
+    eth::{
+        block::{Block, BlockInfo, clique_seal},
+        transaction::{
+            DepositReceipt, MaybeImpersonatedTransaction, PendingTransaction, ReceiptResponse,
+            TransactionInfo, TypedReceipt, TypedTransaction, has_optimism_fields,
+            transaction_request_to_typed,
+        },
+        wallet::{Capabilities, DelegationCapability, WalletCapabilities},
+    },
+    types::{
+        BlockTimeStats, GasByOpcode, InvariantViolation, MemoryUsage, OverrideScope,
+        ReorgHistoryEntry, ReorgKind, ReplayedTransaction, StorageChanges, WalletInfo,
     },
-    wallet::{Capabilities, DelegationCapability, WalletCapabilities},
 };
 use anvil_rpc::error::RpcError;
 use chrono::Datelike;
 use eyre::{Context, Result};
 use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use foundry_common::provider::ProviderBuilder;
 use foundry_evm::{
     backend::{DatabaseError, DatabaseResult, RevertStateSnapshotAction},
     constants::DEFAULT_CREATE2_DEPLOYER_RUNTIME_CODE,
@@ -99,10 +111,14 @@
     utils::{get_blob_base_fee_update_fraction, get_blob_base_fee_update_fraction_by_spec_id},
 };
 use foundry_evm_core::either_evm::EitherEvm;
-use futures::channel::mpsc::{UnboundedSender, unbounded};
+use futures::{
+    StreamExt, TryStreamExt,
+    channel::mpsc::{UnboundedSender, unbounded},
+};
 use op_alloy_consensus::DEPOSIT_TX_TYPE_ID;
 use op_revm::{
-    OpContext, OpHaltReason, OpTransaction, transaction::deposit::DepositTransactionParts,
+    L1BlockInfo, OpContext, OpHaltReason, OpSpecId, OpTransaction,
+    transaction::deposit::DepositTransactionParts,
 };
 use parking_lot::{Mutex, RwLock};
 use revm::{
@@ -112,20 +128,23 @@
         block::BlobExcessGasAndPrice,
         result::{ExecutionResult, Output, ResultAndState},
     },
-    database::{CacheDB, WrapDatabaseRef},
-    interpreter::InstructionResult,
+    database::{CacheDB, DbAccount, WrapDatabaseRef},
+    interpreter::{InstructionResult, return_ok},
     precompile::secp256r1::{P256VERIFY, P256VERIFY_BASE_GAS_FEE},
-    primitives::{KECCAK_EMPTY, hardfork::SpecId},
+    primitives::{KECCAK_EMPTY, eip3860::MAX_INITCODE_SIZE, hardfork::SpecId},
     state::AccountInfo,
 };
 use revm_inspectors::transfer::TransferInspector;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, VecDeque},
     fmt::Debug,
     io::{Read, Write},
     ops::Not,
     path::PathBuf,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
     time::Duration,
 };
 use storage::{Blockchain, DEFAULT_HISTORY_LIMIT, MinedTransaction};
@@ -149,6 +168,12 @@ impl DatabaseRef for dyn crate::eth::backend::db::Db {}
 pub const MIN_TRANSACTION_GAS: u128 = 21000;
 // Gas per transaction creating a contract.
 pub const MIN_CREATE_GAS: u128 = 53000;
+// Maximum number of unconsumed storage cursors, the oldest ones are evicted first
+pub const MAX_STORAGE_CURSORS: usize = 64;
+// Maximum number of remote blocks fetched when counting transactions in a range
+pub const MAX_REMOTE_TX_COUNT_BLOCKS: u64 = 10_000;
+// Maximum number of remote blocks fetched concurrently when counting transactions in a range
+pub const REMOTE_TX_COUNT_CONCURRENCY: usize = 16;
 // Executor
 pub const EXECUTOR: Address = address!("0x6634F723546eCc92277e8a2F93d4f248bf1189ea");
 pub const EXECUTOR_PK: &str = "0x502d47e1421cb9abef497096728e69f07543232b93ef24de4998e18b5fd9ba0f";
@@ -168,8 +193,34 @@ impl DatabaseRef for dyn crate::eth::backend::db::Db {}
 
 pub type State = foundry_evm::utils::StateChangeset;
 
+/// The accounts and storage slots a transaction reads and writes, as returned by
+/// [`Backend::build_access_sets_with_state`].
+///
+/// A location without a slot stands for the account's balance, nonce and code.
+#[derive(Clone, Debug, Default)]
+pub struct AccessSets {
+    pub reads: HashSet<(Address, Option<B256>)>,
+    pub writes: HashSet<(Address, Option<B256>)>,
+}
+
+/// A value overwritten by a state override while overrides are scoped to the next block.
+#[derive(Clone, Debug)]
+enum OverriddenValue {
+    Balance(Address, U256),
+    Nonce(Address, u64),
+    Code(Address, Bytes),
+    Storage(Address, U256, B256),
+}
+
+impl AccessSets {
+    /// Returns true if either of the transactions writes a location the other one accesses.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        !self.writes.is_disjoint(&other.reads) || !other.writes.is_disjoint(&self.reads)
+    }
+}
+
 /// A block request, which includes the Pool Transactions if it's Pending
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum BlockRequest {
     Pending(Vec<Arc<PoolTransaction>>),
     Number(u64),
@@ -207,6 +258,9 @@ pub struct Backend {
     /// a new block is currently mined and a new [`Self::set_storage_at()`] request is being
     /// executed.
     db: Arc<AsyncRwLock<Box<dyn Db>>>,
+    /// Incremented on every write to the `db` and every change of the environment calls are
+    /// executed in, see [`Self::state_version`]
+    state_version: Arc<AtomicU64>,
     /// stores all block related data in memory.
     blockchain: Blockchain,
     /// Historic states of previous blocks.
@@ -227,6 +281,25 @@ pub struct Backend {
     new_block_listeners: Arc<Mutex<Vec<UnboundedSender<NewBlockNotification>>>>,
     /// Keeps track of active state snapshots at a specific block.
     active_state_snapshots: Arc<Mutex<HashMap<U256, (u64, B256)>>>,
+    /// Log of all resets and reorgs, oldest first.
+    reorg_history: Arc<RwLock<Vec<ReorgHistoryEntry>>>,
+    /// Locally mined transactions discarded by the most recent reset or reorg.
+    orphaned_transactions: Arc<RwLock<Vec<TxHash>>>,
+    /// Storage of a contract as of a cursor handed out by `anvil_storageChangesSince`, oldest
+    /// first and bounded by [`MAX_STORAGE_CURSORS`].
+    storage_cursors: Arc<Mutex<VecDeque<(B256, Address, HashMap<U256, U256>)>>>,
+    /// View function called after every mined block, registered via `anvil_setInvariantCheck`.
+    invariant_check: Arc<RwLock<Option<(Address, Selector)>>>,
+    /// The failed invariant check that halted mining, if any.
+    invariant_violation: Arc<RwLock<Option<InvariantViolation>>>,
+    /// Listeners notified when an invariant check fails.
+    invariant_violation_listeners: Arc<Mutex<Vec<UnboundedSender<InvariantViolation>>>>,
+    /// How long state overrides last, set via `anvil_setOverrideScope`.
+    override_scope: Arc<RwLock<OverrideScope>>,
+    /// Values overwritten by block scoped overrides, in the order they were overwritten.
+    overridden_values: Arc<Mutex<Vec<OverriddenValue>>>,
+    /// Highest gas limit a single transaction may request, set via `anvil_setTransactionGasCap`.
+    transaction_gas_cap: Arc<RwLock<Option<u64>>>,
     enable_steps_tracing: bool,
     print_logs: bool,
     print_traces: bool,
@@ -242,6 +315,30 @@ pub struct Backend {
     slots_in_an_epoch: u64,
     /// Precompiles to inject to the EVM.
     precompile_factory: Option<Arc<dyn PrecompileFactory>>,
+    /// Seed used to derive a deterministic prevrandao for mined blocks
+    prevrandao_seed: Option<u64>,
+    /// Maximum number of transactions included in a mined block
+    max_txs_per_block: Option<usize>,
+    /// Maximum number of blocks an `eth_getLogs` query may span
+    max_log_block_range: Option<u64>,
+    /// Maximum size of the data a call frame may return
+    max_returndata_size: Option<usize>,
+    /// Whether access lists are applied when executing transactions and calls
+    access_list_gas: bool,
+    /// Whether impersonated accounts stay impersonated after a reset
+    persist_impersonation: bool,
+    /// Whether legacy transactions without EIP-155 replay protection are rejected
+    require_eip155: bool,
+    /// Highest total fee a single transaction may pay
+    max_fee_per_tx: Option<U256>,
+    /// Highest number of accounts the state may hold
+    max_state_accounts: Option<usize>,
+    /// Whether the pre-merge block and uncle rewards are paid when mining
+    block_rewards: bool,
+    /// Uncles included in the next mined block, injected via `anvil_injectUncle`
+    pending_ommers: Arc<RwLock<Vec<Header>>>,
+    /// Signer sealing mined blocks the way clique does
+    block_signer: Option<PrivateKeySigner>,
     /// Prevent race conditions during mining
     mining: Arc<tokio::sync::Mutex<()>>,
     // === wallet === //
@@ -310,9 +407,37 @@ pub async fn with_genesis(
             states = states.disk_path(cache_path);
         }
 
-        let (slots_in_an_epoch, precompile_factory) = {
+        let (
+            slots_in_an_epoch,
+            precompile_factory,
+            prevrandao_seed,
+            max_txs_per_block,
+            max_log_block_range,
+            max_returndata_size,
+            access_list_gas,
+            persist_impersonation,
+            require_eip155,
+            max_fee_per_tx,
+            max_state_accounts,
+            block_rewards,
+            block_signer,
+        ) = {
             let cfg = node_config.read().await;
-            (cfg.slots_in_an_epoch, cfg.precompile_factory.clone())
+            (
+                cfg.slots_in_an_epoch,
+                cfg.precompile_factory.clone(),
+                cfg.prevrandao_seed,
+                cfg.max_txs_per_block,
+                cfg.max_log_block_range,
+                cfg.max_returndata_size,
+                cfg.access_list_gas,
+                cfg.persist_impersonation,
+                cfg.require_eip155,
+                cfg.max_fee_per_tx,
+                cfg.max_state_accounts,
+                cfg.block_rewards,
+                cfg.block_signer.clone(),
+            )
         };
 
         let (capabilities, executor_wallet) = if odyssey {
@@ -354,6 +479,7 @@ pub async fn with_genesis(
 
         let backend = Self {
             db,
+            state_version: Default::default(),
             blockchain,
             states: Arc::new(RwLock::new(states)),
             env,
@@ -364,6 +490,15 @@ pub async fn with_genesis(
             fees,
             genesis,
             active_state_snapshots: Arc::new(Mutex::new(Default::default())),
+            reorg_history: Default::default(),
+            orphaned_transactions: Default::default(),
+            storage_cursors: Default::default(),
+            invariant_check: Default::default(),
+            invariant_violation: Default::default(),
+            invariant_violation_listeners: Default::default(),
+            override_scope: Default::default(),
+            overridden_values: Default::default(),
+            transaction_gas_cap: Default::default(),
             enable_steps_tracing,
             print_logs,
             print_traces,
@@ -374,6 +509,18 @@ pub async fn with_genesis(
             node_config,
             slots_in_an_epoch,
             precompile_factory,
+            prevrandao_seed,
+            max_txs_per_block,
+            max_log_block_range,
+            max_returndata_size,
+            access_list_gas,
+            persist_impersonation,
+            require_eip155,
+            max_fee_per_tx,
+            max_state_accounts,
+            block_rewards,
+            pending_ommers: Default::default(),
+            block_signer,
             mining: Arc::new(tokio::sync::Mutex::new(())),
             capabilities: Arc::new(RwLock::new(capabilities)),
             executor_wallet: Arc::new(RwLock::new(executor_wallet)),
@@ -457,15 +604,20 @@ async fn apply_genesis(&self) -> Result<(), DatabaseError> {
 
             let genesis_accounts = futures::future::join_all(genesis_accounts_futures).await;
 
-            let mut db = self.db.write().await;
+            let mut db = self.write_db().await;
 
             for res in genesis_accounts {
                 let (address, mut info) = res.unwrap()?;
                 info.balance = self.genesis.balance;
                 db.insert_account(address, info.clone());
             }
+
+            // hide the remote state of all masked accounts
+            for address in self.node_config.read().await.masked_accounts.iter().copied() {
+                db.clear_account(address)?;
+            }
         } else {
-            let mut db = self.db.write().await;
+            let mut db = self.write_db().await;
             for (account, info) in self.genesis.account_infos() {
                 db.insert_account(account, info);
             }
@@ -475,7 +627,7 @@ async fn apply_genesis(&self) -> Result<(), DatabaseError> {
             db.insert_block_hash(U256::from(self.best_number()), self.best_hash());
         }
 
-        let db = self.db.write().await;
+        let db = self.write_db().await;
         // apply the genesis.json alloc
         self.genesis.apply_genesis_json_alloc(db)?;
 
@@ -531,6 +683,16 @@ pub fn is_fork(&self) -> bool {
 
     /// Resets the fork to a fresh state
     pub async fn reset_fork(&self, forking: Forking) -> Result<(), BlockchainError> {
+        // prove before anything is reset, so an inconsistent remote leaves the node untouched
+        let proven = if self.node_config.read().await.verify_state_root_on_reset {
+            self.prove_fork_state_root(&forking).await?
+        } else {
+            Vec::new()
+        };
+
+        let (old_number, old_hash) = (self.best_number(), self.best_hash());
+        let orphaned = self.local_transaction_hashes();
+
         if !self.is_fork() {
             if let Some(eth_rpc_url) = forking.clone().json_rpc_url {
                 let mut env = self.env.read().clone();
@@ -545,7 +707,7 @@ pub async fn reset_fork(&self, forking: Forking) -> Result<(), BlockchainError>
                     node_config.setup_fork_db_config(eth_rpc_url, &mut env, &self.fees).await?
                 };
 
-                *self.db.write().await = Box::new(db);
+                *self.write_db().await = Box::new(db);
 
                 let fork = ClientFork::new(config, Arc::clone(&self.db));
 
@@ -623,13 +785,26 @@ pub async fn reset_fork(&self, forking: Forking) -> Result<(), BlockchainError>
                 fork.total_difficulty(),
             );
             self.states.write().clear();
-            self.db.write().await.clear();
+            self.write_db().await.clear();
+
+            // the fork database must serve the proven state before the genesis accounts are
+            // applied on top of it, otherwise its cache is corrupted. The reset is completed
+            // regardless so the node stays usable, but reports the mismatch
+            let mismatch = self.check_fork_db_state(fork.block_number(), &proven).await.err();
 
             self.apply_genesis().await?;
 
+            self.record_reorg(ReorgKind::Reset, old_number, old_hash);
+            *self.orphaned_transactions.write() = orphaned;
+            self.pending_ommers.write().clear();
+            self.overridden_values.lock().clear();
+            if !self.persist_impersonation {
+                self.cheats.clear_impersonated_accounts();
+            }
+
             trace!(target: "backend", "reset fork");
 
-            Ok(())
+            mismatch.map_or(Ok(()), Err)
         } else {
             Err(RpcError::invalid_params("Forking not enabled").into())
         }
@@ -637,6 +812,9 @@ pub async fn reset_fork(&self, forking: Forking) -> Result<(), BlockchainError>
 
     /// Resets the backend to a fresh in-memory state, clearing all existing data
     pub async fn reset_to_in_mem(&self) -> Result<(), BlockchainError> {
+        let (old_number, old_hash) = (self.best_number(), self.best_hash());
+        let orphaned = self.local_transaction_hashes();
+
         // Clear the fork if any exists
         *self.fork.write() = None;
 
@@ -663,7 +841,7 @@ pub async fn reset_to_in_mem(&self) -> Result<(), BlockchainError> {
         self.states.write().clear();
 
         // Clear the database
-        self.db.write().await.clear();
+        self.write_db().await.clear();
 
         // Reset time manager
         self.time.reset(genesis_timestamp);
@@ -677,12 +855,113 @@ pub async fn reset_to_in_mem(&self) -> Result<(), BlockchainError> {
 
         // Reapply genesis configuration
         self.apply_genesis().await?;
+        self.record_reorg(ReorgKind::Reset, old_number, old_hash);
+        *self.orphaned_transactions.write() = orphaned;
+        self.pending_ommers.write().clear();
+        self.overridden_values.lock().clear();
+        if !self.persist_impersonation {
+            self.cheats.clear_impersonated_accounts();
+        }
 
         trace!(target: "backend", "reset to fresh in-memory state");
 
         Ok(())
     }
 
+    /// Appends a head change from `(old_number, old_hash)` to the current head to the reorg
+    /// history.
+    fn record_reorg(&self, kind: ReorgKind, old_number: u64, old_hash: B256) {
+        let entry = ReorgHistoryEntry {
+            kind,
+            old_block_number: old_number,
+            old_block_hash: old_hash,
+            new_block_number: self.best_number(),
+            new_block_hash: self.best_hash(),
+            timestamp: duration_since_unix_epoch().as_secs(),
+        };
+        self.reorg_history.write().push(entry);
+    }
+
+    /// Returns all resets and reorgs recorded so far, oldest first.
+    pub fn reorg_history(&self) -> Vec<ReorgHistoryEntry> {
+        self.reorg_history.read().clone()
+    }
+
+    /// Returns the hashes of all transactions mined locally, i.e. on top of the forked block or
+    /// genesis, in the order they were mined.
+    fn local_transaction_hashes(&self) -> Vec<TxHash> {
+        let first = self.get_fork().map(|fork| fork.block_number()).unwrap_or(self.genesis.number);
+        let storage = self.blockchain.storage.read();
+        (first..=storage.best_number)
+            .filter_map(|number| storage.hashes.get(&number))
+            .filter_map(|hash| storage.blocks.get(hash))
+            .flat_map(|block| block.transactions.iter().map(|tx| tx.hash()))
+            .collect()
+    }
+
+    /// Returns the hashes of the locally mined transactions discarded by the most recent reset or
+    /// reorg that haven't been mined again since.
+    pub fn orphaned_transactions(&self) -> Vec<TxHash> {
+        let storage = self.blockchain.storage.read();
+        self.orphaned_transactions
+            .read()
+            .iter()
+            .filter(|hash| !storage.transactions.contains_key(*hash))
+            .copied()
+            .collect()
+    }
+
+    /// Proves a sample of the accounts of the pinned block `anvil_reset` forks from against the
+    /// block's state root, see [`fork::prove_state_root_sample`].
+    ///
+    /// The accounts are proven on the endpoint the reset forks from. Resets to the latest block
+    /// are not checked.
+    async fn prove_fork_state_root(
+        &self,
+        forking: &Forking,
+    ) -> Result<Vec<(Address, TrieAccount)>, BlockchainError> {
+        let Some(number) = forking.block_number else { return Ok(Vec::new()) };
+        let provider = match (&forking.json_rpc_url, self.get_fork()) {
+            (Some(url), _) => Arc::new(
+                ProviderBuilder::new(url)
+                    .build()
+                    .map_err(|_| BlockchainError::InvalidUrl(url.clone()))?,
+            ),
+            (None, Some(fork)) => fork.provider(),
+            (None, None) => return Ok(Vec::new()),
+        };
+        fork::prove_state_root_sample(&provider, number).await
+    }
+
+    /// Checks that the fork database serves the `proven` accounts of the forked block, which
+    /// catches a corrupted fork cache.
+    async fn check_fork_db_state(
+        &self,
+        number: u64,
+        proven: &[(Address, TrieAccount)],
+    ) -> Result<(), BlockchainError> {
+        let db = self.db.read().await;
+        for (address, account) in proven {
+            let info = db.basic_ref(*address)?.unwrap_or_default();
+            if info.balance != account.balance
+                || info.nonce != account.nonce
+                || info.code_hash != account.code_hash
+            {
+                return Err(BlockchainError::Message(format!(
+                    "state root mismatch at forked block {number}: the fork database serves \
+                     account {address} inconsistently with the state root, the fork cache may be \
+                     corrupted"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the dev wallets of the node, see [NodeConfig::wallet_info]
+    pub async fn wallet_info(&self) -> Vec<WalletInfo> {
+        self.node_config.read().await.wallet_info()
+    }
+
     async fn reset_block_number(
         &self,
         fork_url: String,
@@ -695,7 +974,7 @@ async fn reset_block_number(
         let (forked_db, client_fork_config) =
             node_config.setup_fork_db_config(fork_url, &mut env, &self.fees).await?;
 
-        *self.db.write().await = Box::new(forked_db);
+        *self.write_db().await = Box::new(forked_db);
         let fork = ClientFork::new(client_fork_config, Arc::clone(&self.db));
         *self.fork.write() = Some(fork);
         *self.env.write() = env;
@@ -703,6 +982,16 @@ async fn reset_block_number(
         Ok(())
     }
 
+    /// Returns the address of the signer sealing mined blocks, if configured
+    pub fn block_signer(&self) -> Option<Address> {
+        self.block_signer.as_ref().map(|signer| signer.address())
+    }
+
+    /// Returns the maximum number of transactions included in a mined block, if limited
+    pub fn max_txs_per_block(&self) -> Option<usize> {
+        self.max_txs_per_block
+    }
+
     /// Returns the `TimeManager` responsible for timestamps
     pub fn time(&self) -> &TimeManager {
         &self.time
@@ -730,6 +1019,23 @@ pub fn env(&self) -> &Arc<RwLock<Env>> {
         &self.env
     }
 
+    /// Returns a counter that changes whenever the state or environment calls are executed in
+    /// changes, e.g. when a block is mined or an account is modified.
+    pub fn state_version(&self) -> u64 {
+        self.state_version.load(Ordering::SeqCst)
+    }
+
+    fn bump_state_version(&self) {
+        self.state_version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Acquires the `db` for writing, bumping the [`Self::state_version`]
+    async fn write_db(&self) -> tokio::sync::RwLockWriteGuard<'_, Box<dyn Db>> {
+        let db = self.db.write().await;
+        self.bump_state_version();
+        db
+    }
+
     /// Returns the current best hash of the chain
     pub fn best_hash(&self) -> B256 {
         self.blockchain.storage.read().best_hash
@@ -758,6 +1064,14 @@ pub fn chain_id(&self) -> U256 {
 
     pub fn set_chain_id(&self, chain_id: u64) {
         self.env.write().evm_env.cfg_env.chain_id = chain_id;
+        self.bump_state_version();
+    }
+
+    /// Sets the init code size limit (EIP-3860), `None` restores the default of twice the code
+    /// size limit
+    pub fn set_initcode_size_limit(&self, initcode_size_limit: Option<usize>) {
+        self.env.write().evm_env.cfg_env.limit_contract_initcode_size = initcode_size_limit;
+        self.bump_state_version();
     }
 
     /// Returns balance of the given account.
@@ -773,21 +1087,42 @@ pub async fn current_nonce(&self, address: Address) -> DatabaseResult<u64> {
     /// Sets the coinbase address
     pub fn set_coinbase(&self, address: Address) {
         self.env.write().evm_env.block_env.beneficiary = address;
+        self.bump_state_version();
     }
 
     /// Sets the nonce of the given address
     pub async fn set_nonce(&self, address: Address, nonce: U256) -> DatabaseResult<()> {
-        self.db.write().await.set_nonce(address, nonce.try_into().unwrap_or(u64::MAX))
+        let mut db = self.write_db().await;
+        if self.is_block_scoped_override() {
+            let previous = db.basic_ref(address)?.unwrap_or_default().nonce;
+            self.overridden_values.lock().push(OverriddenValue::Nonce(address, previous));
+        }
+        db.set_nonce(address, nonce.try_into().unwrap_or(u64::MAX))
     }
 
     /// Sets the balance of the given address
     pub async fn set_balance(&self, address: Address, balance: U256) -> DatabaseResult<()> {
-        self.db.write().await.set_balance(address, balance)
+        let mut db = self.write_db().await;
+        if self.is_block_scoped_override() {
+            let previous = db.basic_ref(address)?.unwrap_or_default().balance;
+            self.overridden_values.lock().push(OverriddenValue::Balance(address, previous));
+        }
+        db.set_balance(address, balance)
     }
 
     /// Sets the code of the given address
     pub async fn set_code(&self, address: Address, code: Bytes) -> DatabaseResult<()> {
-        self.db.write().await.set_code(address, code.0.into())
+        let mut db = self.write_db().await;
+        if self.is_block_scoped_override() {
+            let info = db.basic_ref(address)?.unwrap_or_default();
+            let previous = match info.code {
+                Some(code) => code.original_bytes(),
+                None if info.code_hash == KECCAK_EMPTY => Bytes::new(),
+                None => db.code_by_hash_ref(info.code_hash)?.original_bytes(),
+            };
+            self.overridden_values.lock().push(OverriddenValue::Code(address, previous));
+        }
+        db.set_code(address, code.0.into())
     }
 
     /// Sets the value for the given slot of the given address
@@ -797,7 +1132,104 @@ pub async fn set_storage_at(
         slot: U256,
         val: B256,
     ) -> DatabaseResult<()> {
-        self.db.write().await.set_storage_at(address, slot.into(), val)
+        let mut db = self.write_db().await;
+        if self.is_block_scoped_override() {
+            let previous = db.storage_ref(address, slot)?;
+            self.overridden_values.lock().push(OverriddenValue::Storage(
+                address,
+                slot,
+                previous.into(),
+            ));
+        }
+        db.set_storage_at(address, slot.into(), val)
+    }
+
+    /// Sets how long state overrides last.
+    ///
+    /// Switching to [`OverrideScope::Permanent`] keeps the overrides made since the last block.
+    pub fn set_override_scope(&self, scope: OverrideScope) {
+        *self.override_scope.write() = scope;
+        if scope == OverrideScope::Permanent {
+            self.overridden_values.lock().clear();
+        }
+    }
+
+    /// Returns the number of entries in the state, state snapshots and historic states, and a
+    /// rough estimate of the memory they take.
+    ///
+    /// Every snapshot and historic state is counted as a full copy of the current state.
+    pub async fn memory_usage(&self) -> MemoryUsage {
+        let (accounts, storage_slots) = self
+            .db
+            .read()
+            .await
+            .maybe_as_full_db()
+            .map(|accounts| {
+                (accounts.len(), accounts.values().map(|account| account.storage.len()).sum())
+            })
+            .unwrap_or_default();
+        let state_snapshots = self.active_state_snapshots.lock().len();
+        let historic_states = self.states.read().in_memory_len();
+
+        let state_bytes = accounts * std::mem::size_of::<DbAccount>()
+            + storage_slots * 2 * std::mem::size_of::<U256>();
+        let approximate_bytes = state_bytes * (1 + state_snapshots + historic_states);
+        MemoryUsage {
+            accounts,
+            storage_slots,
+            state_snapshots,
+            historic_states,
+            approximate_bytes: approximate_bytes as u64,
+        }
+    }
+
+    /// Returns an error if the account isn't part of the state yet and the state already holds the
+    /// configured maximum number of accounts.
+    pub async fn ensure_state_account_capacity(
+        &self,
+        address: Address,
+    ) -> Result<(), BlockchainError> {
+        let Some(limit) = self.max_state_accounts else { return Ok(()) };
+        let db = self.db.read().await;
+        if let Some(accounts) = db.maybe_as_full_db()
+            && accounts.len() >= limit
+            && !accounts.contains_key(&address)
+        {
+            return Err(BlockchainError::StateAccountLimitExceeded(limit));
+        }
+        Ok(())
+    }
+
+    /// Sets the highest gas limit a single transaction may request, `None` removes the cap.
+    pub fn set_transaction_gas_cap(&self, cap: Option<u64>) {
+        *self.transaction_gas_cap.write() = cap;
+    }
+
+    fn is_block_scoped_override(&self) -> bool {
+        *self.override_scope.read() == OverrideScope::Block
+    }
+
+    /// Reverts the values overwritten by block scoped overrides, latest first so every value ends
+    /// up as it was before the first override.
+    ///
+    /// Called with the state of a block right after its transactions were executed, so the
+    /// overrides only apply to the execution of that block and neither the latest state nor the
+    /// state stored for the block include them.
+    fn revert_block_scoped_overrides(&self, db: &mut dyn Db) {
+        let overridden = std::mem::take(&mut *self.overridden_values.lock());
+        for value in overridden.into_iter().rev() {
+            let res = match value {
+                OverriddenValue::Balance(address, balance) => db.set_balance(address, balance),
+                OverriddenValue::Nonce(address, nonce) => db.set_nonce(address, nonce),
+                OverriddenValue::Code(address, code) => db.set_code(address, code.0.into()),
+                OverriddenValue::Storage(address, slot, val) => {
+                    db.set_storage_at(address, slot.into(), val)
+                }
+            };
+            if let Err(err) = res {
+                warn!(target: "backend", ?err, "failed to revert block scoped override");
+            }
+        }
     }
 
     /// Returns the configured specid
@@ -805,6 +1237,25 @@ pub fn spec_id(&self) -> SpecId {
         self.env.read().evm_env.cfg_env.spec
     }
 
+    /// Returns the spec calls against the given block are executed with.
+    ///
+    /// Blocks up to the fork block of a forked mainnet use the hardfork that was active on mainnet
+    /// at that height if it predates the configured spec, all other blocks use the configured
+    /// spec.
+    pub fn spec_at_block(&self, number: u64) -> SpecId {
+        let spec = self.spec_id();
+        if let Some(fork) = self.get_fork()
+            && number <= fork.block_number()
+            && fork.chain_id() == NamedChain::Mainnet as u64
+        {
+            let historic = spec_id_from_ethereum_hardfork(ethereum_hardfork_from_block_tag(number));
+            if historic < spec {
+                return historic;
+            }
+        }
+        spec
+    }
+
     /// Returns true for post London
     pub fn is_eip1559(&self) -> bool {
         (self.spec_id() as u8) >= (SpecId::LONDON as u8)
@@ -825,6 +1276,18 @@ pub fn is_eip4844(&self) -> bool {
         (self.spec_id() as u8) >= (SpecId::CANCUN as u8)
     }
 
+    /// Returns true if the chain has Arbitrum specifics, e.g. `block.number` being the L1 block.
+    ///
+    /// When forking these are detected from the remote chain id, since the configured chain id
+    /// may be overridden.
+    pub fn has_arbitrum_specifics(&self) -> bool {
+        let chain_id = match self.get_fork() {
+            Some(fork) => fork.rpc_chain_id(),
+            None => self.env.read().evm_env.cfg_env.chain_id,
+        };
+        is_arbitrum(chain_id)
+    }
+
     /// Returns true for post Prague
     pub fn is_eip7702(&self) -> bool {
         (self.spec_id() as u8) >= (SpecId::PRAGUE as u8)
@@ -835,6 +1298,43 @@ pub fn is_optimism(&self) -> bool {
         self.env.read().is_optimism
     }
 
+    /// Returns the L1 data fee the locally mined transaction paid on an OP stack chain, computed
+    /// like during execution from the L1 fee parameters of the `L1Block` predeploy at its block.
+    ///
+    /// Returns `None` if the node doesn't charge the L1 data fee or the transaction isn't mined
+    /// locally.
+    pub async fn l1_data_fee(&self, hash: B256) -> Result<Option<U256>, BlockchainError> {
+        if !self.is_optimism() {
+            return Ok(None);
+        }
+        let Some((number, index)) = self
+            .blockchain
+            .storage
+            .read()
+            .transactions
+            .get(&hash)
+            .map(|tx| (tx.block_number, tx.info.transaction_index))
+        else {
+            return Ok(None);
+        };
+        let Some(tx) = self
+            .get_block(number)
+            .and_then(|block| block.transactions.get(index as usize).cloned())
+        else {
+            return Ok(None);
+        };
+        let enveloped_tx = alloy_rlp::encode(&tx.transaction);
+        self.with_database_at(Some(BlockRequest::Number(number)), |db, _| {
+            let mut l1_block_info = L1BlockInfo::try_fetch(
+                &mut WrapDatabaseRef(&db),
+                U256::from(number),
+                OpSpecId::ISTHMUS,
+            )?;
+            Ok(Some(l1_block_info.calculate_tx_l1_cost(&enveloped_tx, OpSpecId::ISTHMUS)))
+        })
+        .await?
+    }
+
     /// Returns [`BlobParams`] corresponding to the current spec.
     pub fn blob_params(&self) -> BlobParams {
         let spec_id = self.env.read().evm_env.cfg_env.spec;
@@ -896,6 +1396,7 @@ pub fn gas_limit(&self) -> u64 {
     /// Sets the block gas limit
     pub fn set_gas_limit(&self, gas_limit: u64) {
         self.env.write().evm_env.block_env.gas_limit = gas_limit;
+        self.bump_state_version();
     }
 
     /// Returns the current base fee
@@ -940,7 +1441,7 @@ pub fn total_difficulty(&self) -> U256 {
     pub async fn create_state_snapshot(&self) -> U256 {
         let num = self.best_number();
         let hash = self.best_hash();
-        let id = self.db.write().await.snapshot_state();
+        let id = self.write_db().await.snapshot_state();
         trace!(target: "backend", "creating snapshot {} at {}", id, num);
         self.active_state_snapshots.lock().insert(id, (num, hash));
         id
@@ -950,6 +1451,9 @@ pub async fn create_state_snapshot(&self) -> U256 {
     pub async fn revert_state_snapshot(&self, id: U256) -> Result<bool, BlockchainError> {
         let block = { self.active_state_snapshots.lock().remove(&id) };
         if let Some((num, hash)) = block {
+            // the overwritten values and the queued uncles belong to the state that's reverted
+            self.overridden_values.lock().clear();
+            self.pending_ommers.write().clear();
             let best_block_hash = {
                 // revert the storage that's newer than the snapshot
                 let current_height = self.best_number();
@@ -990,7 +1494,7 @@ pub async fn revert_state_snapshot(&self, id: U256) -> Result<bool, BlockchainEr
                 ..Default::default()
             }
         }
-        Ok(self.db.write().await.revert_state(id, RevertStateSnapshotAction::RevertRemove))
+        Ok(self.write_db().await.revert_state(id, RevertStateSnapshotAction::RevertRemove))
     }
 
     pub fn list_state_snapshots(&self) -> BTreeMap<U256, (u64, B256)> {
@@ -1019,10 +1523,11 @@ pub async fn serialized_state(
             transactions,
             historical_states,
         )?;
-        state.ok_or_else(|| {
+        let mut state = state.ok_or_else(|| {
             RpcError::invalid_params("Dumping state not supported with the current configuration")
-                .into()
-        })
+        })?;
+        state.chain_id = Some(self.chain_id().to());
+        Ok(state)
     }
 
     /// Write all chain data to serialized bytes buffer
@@ -1113,7 +1618,7 @@ pub async fn load_state(&self, state: SerializableState) -> Result<bool, Blockch
             ));
         }
 
-        if !self.db.write().await.load_state(state.clone())? {
+        if !self.write_db().await.load_state(state.clone())? {
             return Err(RpcError::invalid_params(
                 "Loading state not supported with the current configuration",
             )
@@ -1225,6 +1730,35 @@ pub async fn inspect_tx(
         Ok((exit_reason, out, gas_used, state, logs.unwrap_or_default()))
     }
 
+    /// Queues an uncle mined by `beneficiary` at height `number` for inclusion in the next block.
+    ///
+    /// The uncle must be one of the six ancestors of the next block.
+    pub fn inject_uncle(&self, beneficiary: Address, number: u64) -> Result<B256, BlockchainError> {
+        let next_number = self.best_number() + 1;
+        if number >= next_number || number + 6 < next_number {
+            return Err(BlockchainError::Message(format!(
+                "uncle {number} is not within the 6 ancestors of the next block {next_number}"
+            )));
+        }
+        let parent = number.checked_sub(1).and_then(|parent| self.get_block(parent));
+        let env = self.env.read();
+        let mut ommers = self.pending_ommers.write();
+        let uncle = Header {
+            parent_hash: parent.map(|parent| parent.header.hash_slow()).unwrap_or_default(),
+            beneficiary,
+            number,
+            difficulty: env.evm_env.block_env.difficulty,
+            gas_limit: env.evm_env.block_env.gas_limit,
+            timestamp: env.evm_env.block_env.timestamp.saturating_to(),
+            // distinguishes uncles injected at the same height
+            nonce: B64::from(ommers.len() as u64),
+            ..Default::default()
+        };
+        let hash = uncle.hash_slow();
+        ommers.push(uncle);
+        Ok(hash)
+    }
+
     /// Creates the pending block
     ///
     /// This will execute all transaction in the order they come but will not mine the block
@@ -1267,6 +1801,13 @@ pub async fn with_pending_block<F, T>(
             odyssey: self.odyssey,
             optimism: self.is_optimism(),
             blob_params: self.blob_params(),
+            call_denylist: self.cheats.call_denylist(),
+            block_number_offset: self.cheats.block_number_offset(),
+            max_returndata_size: self.max_returndata_size,
+            access_list_gas: self.access_list_gas,
+            ommers: self.pending_ommers.read().clone(),
+            block_rewards: self.block_rewards,
+            max_state_accounts: self.max_state_accounts,
         };
 
         // create a new pending block
@@ -1278,11 +1819,88 @@ pub async fn with_pending_block<F, T>(
     ///
     /// this will execute all transaction in the order they come in and return all the markers they
     /// provide.
+    ///
+    /// At most `max_txs_per_block` of the transactions are mined, the rest stay in the pool for
+    /// the following blocks.
+    ///
+    /// Fails without mining while a failed invariant check is recorded, see
+    /// [Self::set_invariant_check].
     pub async fn mine_block(
         &self,
-        pool_transactions: Vec<Arc<PoolTransaction>>,
-    ) -> MinedBlockOutcome {
-        self.do_mine_block(pool_transactions).await
+        mut pool_transactions: Vec<Arc<PoolTransaction>>,
+    ) -> Result<MinedBlockOutcome, BlockchainError> {
+        if let Some(violation) = self.invariant_violation() {
+            return Err(BlockchainError::MiningHalted(violation));
+        }
+        if let Some(max_txs_per_block) = self.max_txs_per_block {
+            pool_transactions.truncate(max_txs_per_block);
+        }
+        let outcome = self.do_mine_block(pool_transactions).await;
+        self.check_invariant(outcome.block_number).await;
+        Ok(outcome)
+    }
+
+    /// Registers a view function that is called after every mined block, mining halts once it
+    /// reverts or returns `false`.
+    ///
+    /// Clears any previously recorded violation.
+    pub fn set_invariant_check(&self, address: Address, selector: Selector) {
+        *self.invariant_check.write() = Some((address, selector));
+        *self.invariant_violation.write() = None;
+    }
+
+    /// Removes the registered invariant check and any violation it recorded.
+    pub fn clear_invariant_check(&self) {
+        *self.invariant_check.write() = None;
+        *self.invariant_violation.write() = None;
+    }
+
+    /// Returns the failed invariant check that halted mining, if any.
+    pub fn invariant_violation(&self) -> Option<InvariantViolation> {
+        self.invariant_violation.read().clone()
+    }
+
+    /// Returns a channel that receives every failed invariant check.
+    pub fn invariant_violation_notifications(&self) -> InvariantViolationNotifications {
+        let (tx, rx) = unbounded();
+        self.invariant_violation_listeners.lock().push(tx);
+        rx
+    }
+
+    /// Calls the registered invariant check against the latest state and records a violation if
+    /// it reverts or returns `false`.
+    async fn check_invariant(&self, block_number: u64) {
+        let Some((address, selector)) = *self.invariant_check.read() else { return };
+        let request = TransactionRequest::default().to(address).input(selector.to_vec().into());
+        let reason = match self
+            .call(WithOtherFields::new(request), FeeDetails::zero(), None, Default::default())
+            .await
+        {
+            Ok((return_ok!(), out, _, _)) => {
+                let out = out.map(|out| out.into_data()).unwrap_or_default();
+                if out.iter().any(|byte| *byte != 0) {
+                    return;
+                }
+                "returned false".to_string()
+            }
+            Ok((exit, _, _, _)) => format!("reverted: {exit:?}"),
+            Err(err) => format!("failed: {err}"),
+        };
+        let block_hash = self.best_hash();
+        warn!(
+            target: "node",
+            %address,
+            %selector,
+            block_number,
+            ?block_hash,
+            %reason,
+            "Invariant check failed, mining halted"
+        );
+        let violation = InvariantViolation { block_number, block_hash, reason };
+        *self.invariant_violation.write() = Some(violation.clone());
+        self.invariant_violation_listeners
+            .lock()
+            .retain(|tx| tx.unbounded_send(violation.clone()).is_ok());
     }
 
     async fn do_mine_block(
@@ -1307,7 +1925,7 @@ async fn do_mine_block(
             let block_number = self.blockchain.storage.read().best_number.saturating_add(1);
 
             // increase block number for this block
-            if is_arbitrum(env.evm_env.cfg_env.chain_id) {
+            if self.has_arbitrum_specifics() {
                 // Temporary set `env.block.number` to `block_number` for Arbitrum chains.
                 env.evm_env.block_env.number = U256::from(block_number);
             } else {
@@ -1318,8 +1936,11 @@ async fn do_mine_block(
             env.evm_env.block_env.basefee = current_base_fee;
             env.evm_env.block_env.blob_excess_gas_and_price = current_excess_blob_gas_and_price;
 
-            // pick a random value for prevrandao
-            env.evm_env.block_env.prevrandao = Some(B256::random());
+            // pick a random value for prevrandao, unless it should be derived from a seed
+            env.evm_env.block_env.prevrandao = Some(match self.prevrandao_seed {
+                Some(seed) => deterministic_prevrandao(seed, block_number),
+                None => B256::random(),
+            });
 
             let best_hash = self.blockchain.storage.read().best_hash;
 
@@ -1330,7 +1951,7 @@ async fn do_mine_block(
             }
 
             let (executed_tx, block_hash) = {
-                let mut db = self.db.write().await;
+                let mut db = self.write_db().await;
 
                 // finally set the next block timestamp, this is done just before execution, because
                 // there can be concurrent requests that can delay acquiring the db lock and we want
@@ -1354,8 +1975,22 @@ async fn do_mine_block(
                     precompile_factory: self.precompile_factory.clone(),
                     optimism: self.is_optimism(),
                     blob_params: self.blob_params(),
+                    call_denylist: self.cheats.call_denylist(),
+                    block_number_offset: self.cheats.block_number_offset(),
+                    max_returndata_size: self.max_returndata_size,
+                    access_list_gas: self.access_list_gas,
+                    ommers: std::mem::take(&mut *self.pending_ommers.write()),
+                    block_rewards: self.block_rewards,
+                    max_state_accounts: self.max_state_accounts,
                 };
-                let executed_tx = executor.execute();
+                let mut executed_tx = executor.execute();
+                self.revert_block_scoped_overrides(&mut **db);
+                if let Some(signer) = &self.block_signer {
+                    clique_seal(&mut executed_tx.block.block.header, |hash| {
+                        signer.sign_hash_sync(&hash)
+                    })
+                    .expect("signing with a local key is infallible");
+                }
 
                 // we also need to update the new blockhash in the db itself
                 let block_hash = executed_tx.block.block.header.hash_slow();
@@ -1478,6 +2113,37 @@ pub async fn call(
         fee_details: FeeDetails,
         block_request: Option<BlockRequest>,
         overrides: EvmOverrides,
+    ) -> Result<(InstructionResult, Option<Output>, u128, State), BlockchainError> {
+        self.call_with_inspector(
+            request,
+            fee_details,
+            block_request,
+            overrides,
+            self.build_inspector(),
+        )
+        .await
+    }
+
+    /// Executes the [TransactionRequest] without writing to the DB and with gas metering disabled,
+    /// so the call never runs out of gas.
+    pub async fn call_without_gas_metering(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        fee_details: FeeDetails,
+        block_request: Option<BlockRequest>,
+        overrides: EvmOverrides,
+    ) -> Result<(InstructionResult, Option<Output>, u128, State), BlockchainError> {
+        let inspector = self.build_inspector().with_gas_metering_disabled();
+        self.call_with_inspector(request, fee_details, block_request, overrides, inspector).await
+    }
+
+    async fn call_with_inspector(
+        &self,
+        request: WithOtherFields<TransactionRequest>,
+        fee_details: FeeDetails,
+        block_request: Option<BlockRequest>,
+        overrides: EvmOverrides,
+        inspector: AnvilInspector,
     ) -> Result<(InstructionResult, Option<Output>, u128, State), BlockchainError> {
         self.with_database_at(block_request, |state, mut block| {
             let block_number = block.number;
@@ -1489,7 +2155,13 @@ pub async fn call(
                 if let Some(block_overrides) = overrides.block {
                     cache_db.apply_block_overrides(*block_overrides, &mut block);
                 }
-                self.call_with_state(&cache_db as &dyn DatabaseRef, request, fee_details, block)
+                self.call_with_state_and_inspector(
+                    &cache_db as &dyn DatabaseRef,
+                    request,
+                    fee_details,
+                    block,
+                    inspector,
+                )
             }?;
             trace!(target: "backend", "call return {:?} out: {:?} gas {} on block {}", exit, out, gas, block_number);
             Ok((exit, out, gas, state))
@@ -1539,8 +2211,10 @@ fn build_call_env(
         } = fee_details;
 
         let gas_limit = gas.unwrap_or(block_env.gas_limit);
+        let spec = self.spec_at_block(block_env.number.saturating_to());
         let mut env = self.env.read().clone();
         env.evm_env.block_env = block_env;
+        env.evm_env.cfg_env.spec = spec;
         // we want to disable this in eth_call, since this is common practice used by other node
         // impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
         env.evm_env.cfg_env.disable_block_gas_limit = true;
@@ -1580,7 +2254,11 @@ fn build_call_env(
             value: value.unwrap_or_default(),
             data: input.into_input().unwrap_or_default(),
             chain_id: Some(chain_id.unwrap_or(self.env.read().evm_env.cfg_env.chain_id)),
-            access_list: access_list.unwrap_or_default(),
+            access_list: if self.access_list_gas {
+                access_list.unwrap_or_default()
+            } else {
+                Default::default()
+            },
             blob_hashes,
             ..Default::default()
         };
@@ -1632,6 +2310,17 @@ fn build_inspector(&self) -> AnvilInspector {
         if self.print_traces {
             inspector = inspector.with_trace_printer();
         }
+        let call_denylist = self.cheats.call_denylist();
+        if !call_denylist.is_empty() {
+            inspector = inspector.with_call_denylist(call_denylist);
+        }
+        if let Some(max_returndata_size) = self.max_returndata_size {
+            inspector = inspector.with_max_returndata_size(max_returndata_size);
+        }
+        let block_number_offset = self.cheats.block_number_offset();
+        if block_number_offset != 0 {
+            inspector = inspector.with_block_number_offset(block_number_offset);
+        }
 
         inspector
     }
@@ -1851,8 +2540,23 @@ pub fn call_with_state(
         fee_details: FeeDetails,
         block_env: BlockEnv,
     ) -> Result<(InstructionResult, Option<Output>, u128, State), BlockchainError> {
-        let mut inspector = self.build_inspector();
+        self.call_with_state_and_inspector(
+            state,
+            request,
+            fee_details,
+            block_env,
+            self.build_inspector(),
+        )
+    }
 
+    fn call_with_state_and_inspector(
+        &self,
+        state: &dyn DatabaseRef,
+        request: WithOtherFields<TransactionRequest>,
+        fee_details: FeeDetails,
+        block_env: BlockEnv,
+        mut inspector: AnvilInspector,
+    ) -> Result<(InstructionResult, Option<Output>, u128, State), BlockchainError> {
         let env = self.build_call_env(request, fee_details, block_env);
         let mut evm = self.new_evm_with_inspector_ref(state, &env, &mut inspector);
         let ResultAndState { result, state } = evm.transact(env.tx)?;
@@ -1882,6 +2586,7 @@ pub async fn call_with_tracing(
         request: WithOtherFields<TransactionRequest>,
         fee_details: FeeDetails,
         block_request: Option<BlockRequest>,
+        overrides: EvmOverrides,
         opts: GethDebugTracingCallOptions,
     ) -> Result<GethTrace, BlockchainError> {
         let GethDebugTracingCallOptions { tracing_options, block_overrides, state_overrides } =
@@ -1891,7 +2596,15 @@ pub async fn call_with_tracing(
         self.with_database_at(block_request, |state, mut block| {
             let block_number = block.number;
 
+            // the call overrides are layered like in `call`, the overrides of the tracing options
+            // are applied on top of them
             let mut cache_db = CacheDB::new(state);
+            if let Some(state_overrides) = overrides.state {
+                apply_state_overrides(state_overrides.into_iter().collect(), &mut cache_db)?;
+            }
+            if let Some(block_overrides) = overrides.block {
+                cache_db.apply_block_overrides(*block_overrides, &mut block);
+            }
             if let Some(state_overrides) = state_overrides {
                 apply_state_overrides(state_overrides, &mut cache_db)?;
             }
@@ -2034,6 +2747,54 @@ pub fn build_access_list_with_state(
         Ok((exit_reason, out, gas_used, access_list))
     }
 
+    /// Executes the request on top of the given state and returns the locations it reads,
+    /// combining its access list with the touched state, and the locations it changes.
+    ///
+    /// The beneficiary of the block is ignored, since every transaction pays it.
+    pub fn build_access_sets_with_state(
+        &self,
+        state: &dyn DatabaseRef,
+        request: WithOtherFields<TransactionRequest>,
+        block_env: BlockEnv,
+    ) -> Result<AccessSets, BlockchainError> {
+        let beneficiary = block_env.beneficiary;
+        let (_, _, _, access_list) = self.build_access_list_with_state(
+            state,
+            request.clone(),
+            FeeDetails::zero(),
+            block_env.clone(),
+        )?;
+        let (_, _, _, changes) =
+            self.call_with_state(state, request, FeeDetails::zero(), block_env)?;
+
+        let mut sets = AccessSets::default();
+        for item in access_list.0 {
+            sets.reads.insert((item.address, None));
+            sets.reads.extend(item.storage_keys.into_iter().map(|key| (item.address, Some(key))));
+        }
+        for (address, account) in changes {
+            if address == beneficiary {
+                continue;
+            }
+            sets.reads.insert((address, None));
+            let before = state.basic_ref(address)?.unwrap_or_default();
+            if before.balance != account.info.balance
+                || before.nonce != account.info.nonce
+                || before.code_hash != account.info.code_hash
+            {
+                sets.writes.insert((address, None));
+            }
+            for (key, slot) in account.storage {
+                let key = B256::from(key);
+                sets.reads.insert((address, Some(key)));
+                if slot.is_changed() {
+                    sets.writes.insert((address, Some(key)));
+                }
+            }
+        }
+        Ok(sets)
+    }
+
     /// returns all receipts for the given transactions
     fn get_receipts(&self, tx_hashes: impl IntoIterator<Item = TxHash>) -> Vec<TypedReceipt> {
         let storage = self.blockchain.storage.read();
@@ -2152,6 +2913,7 @@ pub async fn logs(&self, filter: Filter) -> Result<Vec<Log>, BlockchainError> {
                 // requested log range does not exist yet
                 return Ok(vec![]);
             }
+            self.ensure_block_range(from_block, to_block)?;
 
             self.logs_for_range(&filter, from_block, to_block).await
         }
@@ -2201,6 +2963,35 @@ pub(crate) async fn mined_transactions_by_block_number(
         None
     }
 
+    /// Returns the mined transactions in the blocks `from..=to` whose calldata starts with the
+    /// given function selector. Blocks up to and including the forked block are skipped.
+    pub fn mined_transactions_by_selector(
+        &self,
+        selector: Selector,
+        from: u64,
+        to: u64,
+    ) -> Vec<AnyRpcTransaction> {
+        let from = self.get_fork().map_or(from, |fork| from.max(fork.block_number() + 1));
+        let mut transactions = Vec::new();
+        for number in from..=to {
+            let Some(block) = self.get_block(number) else { continue };
+            let base_fee = block.header.base_fee_per_gas;
+            let storage = self.blockchain.storage.read();
+            for tx in block.transactions.iter().filter(|tx| tx.data().starts_with(&selector[..])) {
+                let hash = tx.hash();
+                let Some(mined) = storage.transactions.get(&hash) else { continue };
+                transactions.push(transaction_build(
+                    Some(hash),
+                    tx.clone(),
+                    Some(&block),
+                    Some(mined.info.clone()),
+                    base_fee,
+                ));
+            }
+        }
+        transactions
+    }
+
     /// Returns all transactions given a block
     pub(crate) fn mined_transactions_in_block(
         &self,
@@ -2257,6 +3048,163 @@ pub async fn block_by_number_full(
         Ok(None)
     }
 
+    /// Returns the total number of transactions in the blocks `from..=to`, blocks that predate
+    /// the fork are fetched from the remote, at most [`MAX_REMOTE_TX_COUNT_BLOCKS`] of them.
+    pub async fn transaction_count_in_range(
+        &self,
+        from: u64,
+        to: u64,
+    ) -> Result<u64, BlockchainError> {
+        self.ensure_block_range(from, to)?;
+
+        let fork = self.get_fork();
+        let mut count = 0;
+        let mut remote_blocks = Vec::new();
+        for number in from..=to {
+            if let Some(block) = self.get_block(number) {
+                count += block.transactions.len() as u64;
+                continue;
+            }
+            if let Some(fork) = &fork
+                && fork.predates_fork_inclusive(number)
+            {
+                if remote_blocks.len() as u64 >= MAX_REMOTE_TX_COUNT_BLOCKS {
+                    return Err(BlockchainError::LogBlockRangeExceeded(MAX_REMOTE_TX_COUNT_BLOCKS));
+                }
+                remote_blocks.push(number);
+                continue;
+            }
+            return Err(BlockchainError::BlockNotFound);
+        }
+
+        let Some(fork) = fork else { return Ok(count) };
+        let remote_counts = futures::stream::iter(remote_blocks)
+            .map(|number| {
+                let fork = fork.clone();
+                async move {
+                    let block = fork
+                        .block_by_number(number)
+                        .await?
+                        .ok_or(BlockchainError::BlockNotFound)?;
+                    Ok::<_, BlockchainError>(block.transactions.len() as u64)
+                }
+            })
+            .buffer_unordered(REMOTE_TX_COUNT_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(count + remote_counts.into_iter().sum::<u64>())
+    }
+
+    /// Returns the time between consecutive blocks over the last `count` intervals of locally
+    /// mined blocks, or `None` if there are no two such blocks.
+    pub fn block_time_stats(&self, count: u64) -> Option<BlockTimeStats> {
+        let best_number = self.best_number();
+        let timestamps = (best_number.saturating_sub(count)..=best_number)
+            .filter_map(|number| self.get_block(number))
+            .map(|block| block.header.timestamp)
+            .collect::<Vec<_>>();
+        let intervals =
+            timestamps.windows(2).map(|pair| pair[1].saturating_sub(pair[0])).collect::<Vec<_>>();
+        if intervals.is_empty() {
+            return None;
+        }
+
+        Some(BlockTimeStats {
+            intervals: intervals.len() as u64,
+            min: intervals.iter().copied().min()?,
+            avg: intervals.iter().sum::<u64>() as f64 / intervals.len() as f64,
+            max: intervals.iter().copied().max()?,
+        })
+    }
+
+    /// Returns the RLP encoding of the block, i.e. its header, transactions, ommers and
+    /// withdrawals. Blocks that predate the fork are rebuilt from the remote block.
+    pub async fn block_rlp(&self, number: BlockNumber) -> Result<Option<Bytes>, BlockchainError> {
+        if let Some(fork) = self.get_fork() {
+            let number = self.convert_block_number(Some(number));
+            if fork.predates_fork_inclusive(number) {
+                return self.fork_block_rlp(&fork, number).await;
+            }
+        }
+
+        let Some(block) = self.get_block(number) else { return Ok(None) };
+        // blocks mined post Shanghai carry an (empty) list of withdrawals
+        let withdrawals = block.header.withdrawals_root.map(|_| Default::default());
+        let block = alloy_consensus::Block {
+            header: block.header,
+            body: alloy_consensus::BlockBody {
+                transactions: block
+                    .transactions
+                    .into_iter()
+                    .map(TypedTransaction::from)
+                    .collect::<Vec<_>>(),
+                ommers: block.ommers,
+                withdrawals,
+            },
+        };
+        Ok(Some(alloy_rlp::encode(&block).into()))
+    }
+
+    /// Rebuilds the consensus block from the forked client and returns its RLP encoding.
+    async fn fork_block_rlp(
+        &self,
+        fork: &ClientFork,
+        number: u64,
+    ) -> Result<Option<Bytes>, BlockchainError> {
+        let Some(block) = fork.block_by_number_full(number).await? else { return Ok(None) };
+
+        let into_header = |block: AnyRpcBlock| {
+            block.header.inner.clone().try_into_header().map_err(|_| {
+                BlockchainError::Message(format!("unsupported header of block {number}"))
+            })
+        };
+        let mut ommers = Vec::with_capacity(block.uncles.len());
+        for index in 0..block.uncles.len() {
+            let uncle = fork
+                .uncle_by_block_number_and_index(number, index)
+                .await?
+                .ok_or(BlockchainError::BlockNotFound)?;
+            ommers.push(into_header(uncle)?);
+        }
+        let transactions = block
+            .transactions
+            .clone()
+            .into_transactions()
+            .map(|tx| {
+                TypedTransaction::try_from(tx).map_err(|_| {
+                    BlockchainError::Message(format!("unsupported transaction in block {number}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let withdrawals = block.withdrawals.clone();
+        let block = alloy_consensus::Block {
+            header: into_header(block)?,
+            body: alloy_consensus::BlockBody { transactions, ommers, withdrawals },
+        };
+        Ok(Some(alloy_rlp::encode(&block).into()))
+    }
+
+    /// Returns the number of the latest local block mined at or before the given timestamp, or
+    /// `None` if the first local block is younger.
+    pub fn block_number_at_timestamp(&self, timestamp: u64) -> Option<u64> {
+        let timestamp_of = |number: u64| self.get_block(number).map(|block| block.header.timestamp);
+        let mut low = self.get_fork().map(|fork| fork.block_number()).unwrap_or_default();
+        let mut high = self.best_number();
+        if timestamp_of(low)? > timestamp {
+            return None;
+        }
+        // block timestamps never decrease, and block `low` is always at or before the timestamp
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if timestamp_of(mid)? <= timestamp {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Some(low)
+    }
+
     pub fn get_block(&self, id: impl Into<BlockId>) -> Option<Block> {
         let hash = match id.into() {
             BlockId::Hash(hash) => hash.block_hash,
@@ -2299,6 +3247,12 @@ pub fn mined_block_by_number(&self, number: BlockNumber) -> Option<AnyRpcBlock>
         Some(block)
     }
 
+    /// Returns the uncle at `index` of a locally mined block.
+    pub fn mined_uncle(&self, id: impl Into<BlockId>, index: usize) -> Option<AnyRpcBlock> {
+        let uncle = self.get_block(id)?.ommers.into_iter().nth(index)?;
+        Some(self.convert_block(Block { header: uncle, transactions: vec![], ommers: vec![] }))
+    }
+
     pub fn get_full_block(&self, id: impl Into<BlockId>) -> Option<AnyRpcBlock> {
         let block = self.get_block(id)?;
         let transactions = self.mined_transactions_in_block(&block)?;
@@ -2312,7 +3266,7 @@ pub fn get_full_block(&self, id: impl Into<BlockId>) -> Option<AnyRpcBlock> {
     pub fn convert_block(&self, block: Block) -> AnyRpcBlock {
         let size = U256::from(alloy_rlp::encode(&block).len() as u32);
 
-        let Block { header, transactions, .. } = block;
+        let Block { header, transactions, ommers } = block;
 
         let hash = header.hash_slow();
         let Header { number, withdrawals_root, .. } = header;
@@ -2327,14 +3281,14 @@ pub fn convert_block(&self, block: Block) -> AnyRpcBlock {
             transactions: alloy_rpc_types::BlockTransactions::Hashes(
                 transactions.into_iter().map(|tx| tx.hash()).collect(),
             ),
-            uncles: vec![],
+            uncles: ommers.iter().map(Header::hash_slow).collect(),
             withdrawals: withdrawals_root.map(|_| Default::default()),
         };
 
         let mut block = WithOtherFields::new(block);
 
         // If Arbitrum, apply chain specifics to converted block.
-        if is_arbitrum(self.env.read().evm_env.cfg_env.chain_id) {
+        if self.has_arbitrum_specifics() {
             // Set `l1BlockNumber` field.
             block.other.insert("l1BlockNumber".to_string(), number.into());
         }
@@ -2342,6 +3296,16 @@ pub fn convert_block(&self, block: Block) -> AnyRpcBlock {
         AnyRpcBlock::from(block)
     }
 
+    /// Ensures the range `from..=to` doesn't span more blocks than an `eth_getLogs` query may
+    pub fn ensure_block_range(&self, from: u64, to: u64) -> Result<(), BlockchainError> {
+        if let Some(max_range) = self.max_log_block_range
+            && to.saturating_sub(from) >= max_range
+        {
+            return Err(BlockchainError::LogBlockRangeExceeded(max_range));
+        }
+        Ok(())
+    }
+
     /// Converts the `BlockNumber` into a numeric value
     ///
     /// # Errors
@@ -2480,6 +3444,79 @@ pub async fn get_code(
         self.with_database_at(block_request, |db, _| self.get_code_with_state(&db, address)).await?
     }
 
+    /// Returns the code of `address` as of the end of the mined block `number`.
+    ///
+    /// Only the state stored for the block is used, unlike [`Self::get_code`] which returns the
+    /// current state, including changes that weren't mined yet, for the latest block. Fails if the
+    /// state of the block was pruned.
+    pub async fn code_at(&self, address: Address, number: u64) -> Result<Bytes, BlockchainError> {
+        let hash = self
+            .blockchain
+            .storage
+            .read()
+            .hashes
+            .get(&number)
+            .copied()
+            .ok_or(BlockchainError::BlockNotFound)?;
+        let mut states = self.states.write();
+        let state = states.get(&hash).ok_or(BlockchainError::DataUnavailable)?;
+        let state: Box<dyn MaybeFullDatabase + '_> = Box::new(state);
+        self.get_code_with_state(&state, address)
+    }
+
+    /// Returns the storage slots of `address` whose value differs from the storage captured for
+    /// `cursor`, and a new cursor capturing the current storage. The given cursor is consumed, and
+    /// the oldest cursor is evicted once more than [`MAX_STORAGE_CURSORS`] are outstanding.
+    pub async fn storage_changes_since(
+        &self,
+        address: Address,
+        cursor: Option<B256>,
+    ) -> Result<StorageChanges, BlockchainError> {
+        let storage = {
+            let db = self.db.read().await;
+            let accounts = db.maybe_as_full_db().ok_or(BlockchainError::DataUnavailable)?;
+            accounts.get(&address).map(|account| account.storage.clone()).unwrap_or_default()
+        };
+
+        let mut cursors = self.storage_cursors.lock();
+        let previous = match cursor {
+            Some(cursor) => match cursors
+                .iter()
+                .position(|(id, ..)| *id == cursor)
+                .and_then(|index| cursors.remove(index))
+            {
+                Some((_, cursor_address, previous)) if cursor_address == address => previous,
+                _ => {
+                    return Err(RpcError::invalid_params(format!(
+                        "unknown storage cursor {cursor} for {address}"
+                    ))
+                    .into());
+                }
+            },
+            None => Default::default(),
+        };
+
+        let mut changes = BTreeMap::new();
+        for (slot, value) in &storage {
+            if previous.get(slot).unwrap_or(&U256::ZERO) != value {
+                changes.insert(B256::from(*slot), B256::from(*value));
+            }
+        }
+        // slots that were cleared since
+        for (slot, value) in &previous {
+            if !value.is_zero() && !storage.contains_key(slot) {
+                changes.insert(B256::from(*slot), B256::ZERO);
+            }
+        }
+
+        let cursor = B256::random();
+        if cursors.len() == MAX_STORAGE_CURSORS {
+            cursors.pop_front();
+        }
+        cursors.push_back((cursor, address, storage));
+        Ok(StorageChanges { cursor, changes })
+    }
+
     pub fn get_code_with_state(
         &self,
         state: &dyn DatabaseRef<Error = DatabaseError>,
@@ -2511,6 +3548,18 @@ pub async fn get_balance(
             .await?
     }
 
+    /// Returns the balances of all given addresses, in the same order
+    pub async fn get_balances(
+        &self,
+        addresses: &[Address],
+        block_request: Option<BlockRequest>,
+    ) -> Result<Vec<U256>, BlockchainError> {
+        self.with_database_at(block_request, |db, _| {
+            addresses.iter().map(|address| self.get_balance_with_state(&db, *address)).collect()
+        })
+        .await?
+    }
+
     pub async fn get_account_at_block(
         &self,
         address: Address,
@@ -2528,6 +3577,20 @@ pub async fn get_account_at_block(
         .await?
     }
 
+    /// Returns the storage root of the account at the given block, computed from the local state,
+    /// or `None` if the state has no entry for the account.
+    pub async fn local_storage_root(
+        &self,
+        address: Address,
+        block_request: Option<BlockRequest>,
+    ) -> Result<Option<B256>, BlockchainError> {
+        self.with_database_at(block_request, |block_db, _| {
+            let db = block_db.maybe_as_full_db().ok_or(BlockchainError::DataUnavailable)?;
+            Ok(db.get(&address).map(|account| storage_root(&account.storage)))
+        })
+        .await?
+    }
+
     pub fn get_balance_with_state<D>(
         &self,
         state: D,
@@ -2644,6 +3707,77 @@ pub async fn trace_tx_with_js_tracer(
     ) -> Result<GethTrace, BlockchainError> {
         let GethDebugTracingOptions { tracer_config, .. } = opts;
 
+        self.replay_transaction(hash, |cache_db, env, tx_env| {
+            let config = tracer_config.into_json();
+            let mut inspector = revm_inspectors::tracing::js::JsInspector::new(code, config)
+                .map_err(|err| BlockchainError::Message(err.to_string()))?;
+            let mut evm = self.new_evm_with_inspector_ref(cache_db, env, &mut inspector);
+
+            let result = evm
+                .transact(tx_env.clone())
+                .map_err(|err| BlockchainError::Message(err.to_string()))?;
+
+            let trace = inspector
+                .json_result(
+                    result,
+                    &alloy_evm::IntoTxEnv::into_tx_env(tx_env),
+                    &env.evm_env.block_env,
+                    cache_db,
+                )
+                .map_err(|e| BlockchainError::Message(e.to_string()))?;
+            Ok(GethTrace::JS(trace))
+        })
+    }
+
+    /// Re-executes the mined transaction and returns the gas it spent per opcode category
+    pub fn gas_by_opcode(&self, hash: B256) -> Result<GasByOpcode, BlockchainError> {
+        self.replay_transaction(hash, |cache_db, env, tx_env| {
+            let mut inspector = OpcodeGasInspector::default();
+            let mut evm = self.new_evm_with_inspector_ref(cache_db, env, &mut inspector);
+            let ResultAndState { result, .. } =
+                evm.transact(tx_env).map_err(|err| BlockchainError::Message(err.to_string()))?;
+            drop(evm);
+            if let ExecutionResult::Success { gas_refunded, .. } = result {
+                inspector.apply_refund(gas_refunded);
+            }
+            Ok(inspector.gas)
+        })
+    }
+
+    /// Re-executes the mined transaction and returns the net balance change of every account whose
+    /// balance it changed, including the gas payment of the sender and the tip credited to the
+    /// coinbase.
+    pub fn balance_diff(&self, hash: B256) -> Result<BTreeMap<Address, I256>, BlockchainError> {
+        self.replay_transaction(hash, |cache_db, env, tx_env| {
+            let mut inspector = AnvilInspector::default();
+            let mut evm = self.new_evm_with_inspector_ref(cache_db, env, &mut inspector);
+            let ResultAndState { state, .. } =
+                evm.transact(tx_env).map_err(|err| BlockchainError::Message(err.to_string()))?;
+            drop(evm);
+
+            let mut diffs = BTreeMap::new();
+            for (address, account) in state {
+                let before = cache_db.basic_ref(address)?.unwrap_or_default().balance;
+                let after = account.info.balance;
+                if before != after {
+                    diffs.insert(address, I256::from_raw(after) - I256::from_raw(before));
+                }
+            }
+            Ok(diffs)
+        })
+    }
+
+    /// Restores the state the mined transaction was executed on, by executing the transactions
+    /// preceding it in its block on top of the parent state, and calls `f` with that state, the
+    /// env of the block and the env of the transaction.
+    fn replay_transaction<F, T>(&self, hash: B256, f: F) -> Result<T, BlockchainError>
+    where
+        F: FnOnce(
+            &CacheDB<Box<&StateDb>>,
+            &Env,
+            OpTransaction<TxEnv>,
+        ) -> Result<T, BlockchainError>,
+    {
         let block = {
             let storage = self.blockchain.storage.read();
             let MinedTransaction { block_hash, .. } = storage
@@ -2661,6 +3795,24 @@ pub async fn trace_tx_with_js_tracer(
             .position(|tx| tx.hash() == hash)
             .expect("transaction not found in block");
 
+        self.replay_block_until(&block, index, |cache_db, env| {
+            let target_tx = block.transactions[index].clone();
+            let target_tx = PendingTransaction::from_maybe_impersonated(target_tx)?;
+            f(cache_db, env, target_tx.to_revm_tx_env())
+        })
+    }
+
+    /// Executes the first `index` transactions of the block on top of its parent state and calls
+    /// `f` with the resulting state and the env of the block.
+    fn replay_block_until<F, T>(
+        &self,
+        block: &Block,
+        index: usize,
+        f: F,
+    ) -> Result<T, BlockchainError>
+    where
+        F: FnOnce(&CacheDB<Box<&StateDb>>, &Env) -> Result<T, BlockchainError>,
+    {
         let pool_txs: Vec<Arc<PoolTransaction>> = block.transactions[..index]
             .iter()
             .map(|tx| {
@@ -2711,32 +3863,135 @@ pub async fn trace_tx_with_js_tracer(
             odyssey: self.odyssey,
             optimism: self.is_optimism(),
             blob_params: self.blob_params(),
+            call_denylist: self.cheats.call_denylist(),
+            block_number_offset: self.cheats.block_number_offset(),
+            max_returndata_size: self.max_returndata_size,
+            access_list_gas: self.access_list_gas,
+            ommers: vec![],
+            block_rewards: false,
+            max_state_accounts: None,
         };
 
         let _ = executor.execute();
 
-        let target_tx = block.transactions[index].clone();
-        let target_tx = PendingTransaction::from_maybe_impersonated(target_tx)?;
-        let tx_env = target_tx.to_revm_tx_env();
+        f(&cache_db, &env)
+    }
 
-        let config = tracer_config.into_json();
-        let mut inspector = revm_inspectors::tracing::js::JsInspector::new(code, config)
+    /// Re-executes the transactions of the remote block on top of the remote state of its parent,
+    /// with the given state overrides applied first, and returns the outcome of every transaction
+    /// in block order.
+    pub async fn replay_remote_block(
+        &self,
+        number: u64,
+        overrides: Option<StateOverride>,
+    ) -> Result<Vec<ReplayedTransaction>, BlockchainError> {
+        let Some(fork) = self.get_fork() else { return Err(BlockchainError::BlockNotFound) };
+        let block =
+            fork.block_by_number_full(number).await?.ok_or(BlockchainError::BlockNotFound)?;
+        let transactions = block.transactions.as_transactions().unwrap_or_default();
+        let pool_txs = transactions
+            .iter()
+            .map(|tx| PoolTransaction::try_from(tx.clone()).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()
             .map_err(|err| BlockchainError::Message(err.to_string()))?;
-        let mut evm = self.new_evm_with_inspector_ref(&cache_db, &env, &mut inspector);
 
-        let result = evm
-            .transact(tx_env.clone())
-            .map_err(|err| BlockchainError::Message(err.to_string()))?;
+        // configure the env for the replayed block
+        let mut env = self.env.read().clone();
+        env.evm_env.block_env = BlockEnv {
+            number: U256::from(number),
+            beneficiary: block.header.beneficiary,
+            timestamp: U256::from(block.header.timestamp),
+            difficulty: block.header.difficulty,
+            prevrandao: block.header.mix_hash,
+            basefee: block.header.base_fee_per_gas.unwrap_or_default(),
+            gas_limit: block.header.gas_limit,
+            ..Default::default()
+        };
+        env.evm_env.cfg_env.spec = self.spec_at_block(number);
 
-        let trace = inspector
-            .json_result(
-                result,
-                &alloy_evm::IntoTxEnv::into_tx_env(tx_env),
-                &env.evm_env.block_env,
-                &cache_db,
-            )
-            .map_err(|e| BlockchainError::Message(e.to_string()))?;
-        Ok(GethTrace::JS(trace))
+        let parent =
+            fork.database_at(number.saturating_sub(1), env.evm_env.block_env.clone()).await;
+        let mut cache_db = CacheDB::new(parent);
+        if let Some(overrides) = overrides {
+            apply_state_overrides(overrides, &mut cache_db)?;
+        }
+
+        let executor = TransactionExecutor {
+            db: &mut cache_db,
+            validator: self,
+            pending: pool_txs.into_iter(),
+            block_env: env.evm_env.block_env.clone(),
+            cfg_env: env.evm_env.cfg_env.clone(),
+            parent_hash: block.header.parent_hash,
+            gas_used: 0,
+            blob_gas_used: 0,
+            enable_steps_tracing: self.enable_steps_tracing,
+            print_logs: self.print_logs,
+            print_traces: self.print_traces,
+            call_trace_decoder: self.call_trace_decoder.clone(),
+            precompile_factory: self.precompile_factory.clone(),
+            odyssey: self.odyssey,
+            optimism: self.is_optimism(),
+            blob_params: self.blob_params(),
+            call_denylist: self.cheats.call_denylist(),
+            block_number_offset: self.cheats.block_number_offset(),
+            max_returndata_size: self.max_returndata_size,
+            access_list_gas: self.access_list_gas,
+            ommers: vec![],
+            block_rewards: false,
+            max_state_accounts: None,
+        };
+        let executed = executor.execute();
+
+        Ok(transactions
+            .iter()
+            .map(|tx| {
+                let transaction_hash = *tx.inner.tx_hash();
+                match executed
+                    .block
+                    .transactions
+                    .iter()
+                    .find(|info| info.transaction_hash == transaction_hash)
+                {
+                    Some(info) => ReplayedTransaction {
+                        transaction_hash,
+                        success: info.exit.is_ok(),
+                        gas_used: info.gas_used,
+                        output: info.out.clone(),
+                    },
+                    None => ReplayedTransaction {
+                        transaction_hash,
+                        success: false,
+                        gas_used: 0,
+                        output: None,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the account as of right before the transaction at `index` of the block was
+    /// executed, by replaying the preceding transactions of the block.
+    pub fn account_at_transaction(
+        &self,
+        number: u64,
+        index: usize,
+        address: Address,
+    ) -> Result<alloy_rpc_types::eth::AccountInfo, BlockchainError> {
+        let block = self.get_block(number).ok_or(BlockchainError::BlockNotFound)?;
+        if index >= block.transactions.len() {
+            return Err(BlockchainError::TransactionNotFound);
+        }
+
+        self.replay_block_until(&block, index, |cache_db, _| {
+            let account = cache_db.basic_ref(address)?.unwrap_or_default();
+            let code = self.get_code_with_state(cache_db, address)?;
+            Ok(alloy_rpc_types::eth::AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce,
+                code,
+            })
+        })
     }
 
     /// Returns code by its hash
@@ -3218,6 +4473,7 @@ pub async fn reorg(
         tx_pairs: HashMap<u64, Vec<Arc<PoolTransaction>>>,
         common_block: Block,
     ) -> Result<(), BlockchainError> {
+        let (old_number, old_hash) = (self.best_number(), self.best_hash());
         self.rollback(common_block).await?;
         // Create the new reorged chain, filling the blocks with transactions if supplied
         for i in 0..depth {
@@ -3230,6 +4486,7 @@ pub async fn reorg(
                 outcome.invalid.len()
             );
         }
+        self.record_reorg(ReorgKind::Reorg, old_number, old_hash);
 
         Ok(())
     }
@@ -3251,21 +4508,28 @@ pub async fn rollback(&self, common_block: Block) -> Result<(), BlockchainError>
 
         {
             // Set state to common state
-            self.db.write().await.clear();
+            self.write_db().await.clear();
             for (address, acc) in common_state {
                 for (key, value) in acc.storage {
-                    self.db.write().await.set_storage_at(address, key.into(), value.into())?;
+                    self.write_db().await.set_storage_at(address, key.into(), value.into())?;
                 }
-                self.db.write().await.insert_account(address, acc.info);
+                self.write_db().await.insert_account(address, acc.info);
             }
         }
 
         {
             // Unwind the storage back to the common ancestor
-            self.blockchain
+            let removed = self
+                .blockchain
                 .storage
                 .write()
                 .unwind_to(common_block.header.number, common_block.header.hash_slow());
+            *self.orphaned_transactions.write() = removed
+                .iter()
+                .flat_map(|block| block.transactions.iter().map(|tx| tx.hash()))
+                .collect();
+            // uncles were queued relative to the unwound chain
+            self.pending_ommers.write().clear();
 
             // Set environment back to common block
             let mut env = self.env.write();
@@ -3306,6 +4570,11 @@ async fn validate_pool_transaction(
     ) -> Result<(), BlockchainError> {
         let address = *tx.sender();
         let account = self.get_account(address).await?;
+        let target = match tx.transaction.kind() {
+            TxKind::Call(to) => to,
+            TxKind::Create => address.create(tx.nonce()),
+        };
+        self.ensure_state_account_capacity(target).await?;
         let env = self.next_env();
         Ok(self.validate_pool_transaction_for(tx, &account, &env)?)
     }
@@ -3318,6 +4587,14 @@ fn validate_pool_transaction_for(
     ) -> Result<(), InvalidTransactionError> {
         let tx = &pending.transaction;
 
+        if self.require_eip155
+            && let Some(legacy) = tx.as_legacy()
+            && legacy.tx().chain_id.is_none()
+        {
+            warn!(target: "backend", "[{:?}] missing EIP-155 replay protection", tx.hash());
+            return Err(InvalidTransactionError::ReplayProtectionRequired);
+        }
+
         if let Some(tx_chain_id) = tx.chain_id() {
             let chain_id = self.chain_id();
             if chain_id.to::<u64>() != tx_chain_id {
@@ -3351,6 +4628,28 @@ fn validate_pool_transaction_for(
             }));
         }
 
+        if let Some(cap) = *self.transaction_gas_cap.read()
+            && tx.gas_limit() > cap
+        {
+            warn!(target: "backend", "[{:?}] gas above transaction gas cap", tx.hash());
+            return Err(InvalidTransactionError::GasTooHigh(ErrDetail {
+                detail: format!("tx.gas_limit > transaction gas cap of {cap}"),
+            }));
+        }
+
+        // EIP-3860: check the init code size of contract creations
+        if env.evm_env.cfg_env.spec >= SpecId::SHANGHAI && tx.kind().is_create() {
+            let cfg = &env.evm_env.cfg_env;
+            let max_initcode_size = cfg
+                .limit_contract_initcode_size
+                .or_else(|| cfg.limit_contract_code_size.map(|limit| limit.saturating_mul(2)))
+                .unwrap_or(MAX_INITCODE_SIZE);
+            if tx.essentials().input.len() > max_initcode_size {
+                warn!(target: "backend", "[{:?}] max initcode size exceeded", tx.hash());
+                return Err(InvalidTransactionError::MaxInitCodeSizeExceeded);
+            }
+        }
+
         // check nonce
         let is_deposit_tx =
             matches!(&pending.transaction.transaction, TypedTransaction::Deposit(_));
@@ -3429,6 +4728,16 @@ fn validate_pool_transaction_for(
                 }
             }
             _ => {
+                if let Some(cap) = self.max_fee_per_tx
+                    && U256::from(max_cost) > cap
+                {
+                    warn!(target: "backend", "[{:?}] fee={} above transaction fee cap={}", tx.hash(), max_cost, cap);
+                    return Err(InvalidTransactionError::TxFeeCapExceeded(
+                        U256::from(max_cost),
+                        cap,
+                    ));
+                }
+
                 // check sufficient funds: `gas * price + value`
                 let req_funds = max_cost.checked_add(value.saturating_to()).ok_or_else(|| {
                     warn!(target: "backend", "[{:?}] cost too high", tx.hash());
@@ -3618,6 +4927,15 @@ pub fn is_arbitrum(chain_id: u64) -> bool {
     false
 }
 
+/// Derives the prevrandao of the block with the given number from the seed as
+/// `keccak256(seed || number)`, both encoded as big-endian `u64`.
+pub fn deterministic_prevrandao(seed: u64, number: u64) -> B256 {
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&seed.to_be_bytes());
+    data[8..].copy_from_slice(&number.to_be_bytes());
+    keccak256(data)
+}
+
 pub fn op_haltreason_to_instruction_result(op_reason: OpHaltReason) -> InstructionResult {
     match op_reason {
         OpHaltReason::Base(eth_h) => eth_h.into(),
@@ -186,6 +186,11 @@ pub fn get(&mut self, hash: &B256) -> Option<&StateDb> {
         })
     }
 
+    /// Returns the number of states currently kept in memory
+    pub fn in_memory_len(&self) -> usize {
+        self.states.len()
+    }
+
     /// Sets the maximum number of stats we keep in memory
     pub fn set_cache_limit(&mut self, limit: usize) {
         self.in_memory_limit = limit;
@@ -2,6 +2,7 @@
 
 use alloy_consensus::Header;
 use alloy_primitives::B256;
+use anvil_core::types::InvariantViolation;
 use futures::channel::mpsc::UnboundedReceiver;
 use std::sync::Arc;
 
@@ -16,3 +17,6 @@ pub struct NewBlockNotification {
 
 /// Type alias for a receiver that receives [NewBlockNotification]
 pub type NewBlockNotifications = UnboundedReceiver<NewBlockNotification>;
+
+/// Type alias for a receiver that receives every failed invariant check
+pub type InvariantViolationNotifications = UnboundedReceiver<InvariantViolation>;
@@ -2,11 +2,11 @@
 
 use crate::eth::pool::transactions::PoolTransaction;
 use alloy_evm::overrides::StateOverrideError;
-use alloy_primitives::{B256, Bytes, SignatureError};
+use alloy_primitives::{B256, Bytes, SignatureError, TxHash, U256};
 use alloy_rpc_types::BlockNumberOrTag;
 use alloy_signer::Error as SignerError;
 use alloy_transport::TransportError;
-use anvil_core::eth::wallet::WalletError;
+use anvil_core::{eth::wallet::WalletError, types::InvariantViolation};
 use anvil_rpc::{
     error::{ErrorCode, RpcError},
     response::ResponseResult,
@@ -68,6 +68,8 @@ pub enum BlockchainError {
     Internal(String),
     #[error("BlockOutOfRangeError: block height is {0} but requested was {1}")]
     BlockOutOfRange(u64, u64),
+    #[error("query exceeds max block range {0}")]
+    LogBlockRangeExceeded(u64),
     #[error("Resource not found")]
     BlockNotFound,
     /// Thrown when a requested transaction is not found
@@ -107,6 +109,8 @@ pub enum BlockchainError {
     DepositTransactionUnsupported,
     #[error("UnknownTransactionType not supported ")]
     UnknownTransactionType,
+    #[error("transaction type {0} is not allowed by this node")]
+    TransactionTypeNotAllowed(u8),
     #[error("Excess blob gas not set.")]
     ExcessBlobGasNotSet,
     #[error("{0}")]
@@ -120,6 +124,16 @@ pub enum BlockchainError {
     },
     #[error("Failed to parse transaction request: missing required fields")]
     MissingRequiredFields,
+    /// Thrown when adding an account would grow the state beyond the configured account limit
+    #[error("state account limit of {0} reached")]
+    StateAccountLimitExceeded(usize),
+    /// Thrown when mining while a failed invariant check halts mining
+    #[error(
+        "mining is halted by the invariant check that failed after block {}: {}",
+        .0.block_number,
+        .0.reason
+    )]
+    MiningHalted(InvariantViolation),
 }
 
 impl From<eyre::Report> for BlockchainError {
@@ -221,6 +235,9 @@ pub enum PoolError {
     ReplacementUnderpriced(Box<PoolTransaction>),
     #[error("Tx: [{0:?}] already Imported")]
     AlreadyImported(Box<PoolTransaction>),
+    /// Thrown if a transaction with a future nonce is submitted while future nonces are rejected
+    #[error("Tx: [{0:?}] nonce too high")]
+    FutureNonce(TxHash),
 }
 
 /// Errors that can occur with `eth_feeHistory`
@@ -280,6 +297,9 @@ pub enum InvalidTransactionError {
     /// Thrown during estimate if caller has insufficient funds to cover the tx.
     #[error("Out of gas: gas required exceeds allowance: {0:?}")]
     BasicOutOfGas(u128),
+    /// Thrown during estimate if the call needs more gas than the block gas limit allows.
+    #[error("gas required exceeds block gas limit of {0}")]
+    GasRequiredExceedsBlockGasLimit(u64),
     /// Thrown if executing a transaction failed during estimate/call
     #[error("execution reverted: {0:?}")]
     Revert(Option<Bytes>),
@@ -292,6 +312,12 @@ pub enum InvalidTransactionError {
     /// Thrown when a legacy tx was signed for a different chain
     #[error("Incompatible EIP-155 transaction, signed for another chain")]
     IncompatibleEIP155,
+    /// Thrown when a legacy tx without EIP-155 replay protection is sent to a node requiring it
+    #[error("only replay-protected (EIP-155) transactions allowed")]
+    ReplayProtectionRequired,
+    /// Thrown when the worst case fee of a transaction exceeds the configured cap
+    #[error("tx fee ({0}) exceeds the configured cap ({1})")]
+    TxFeeCapExceeded(U256, U256),
     /// Thrown when an access list is used before the berlin hard fork.
     #[error("Access lists are not supported before the Berlin hardfork")]
     AccessListNotSupported,
@@ -334,6 +360,9 @@ pub enum InvalidTransactionError {
     /// Deposit transaction error post regolith
     #[error("op-deposit failure post regolith")]
     DepositTxErrorPostRegolith,
+    /// Thrown when executing the transaction would create accounts beyond the configured limit
+    #[error("state account limit of {0} reached")]
+    StateAccountLimitExceeded(usize),
 }
 
 impl From<InvalidTransaction> for InvalidTransactionError {
@@ -430,6 +459,9 @@ fn to_rpc_result(self) -> ResponseResult {
                         PoolError::AlreadyImported(_) => {
                             RpcError::transaction_rejected("transaction already imported")
                         }
+                        PoolError::FutureNonce(_) => {
+                            RpcError::transaction_rejected("nonce too high")
+                        }
                     }
                 }
                 BlockchainError::NoSignerAvailable => {
@@ -524,6 +556,12 @@ fn to_rpc_result(self) -> ResponseResult {
                 err @ BlockchainError::BlockOutOfRange(_, _) => {
                     RpcError::invalid_params(err.to_string())
                 }
+                err @ BlockchainError::LogBlockRangeExceeded(_) => RpcError {
+                    // <https://eips.ethereum.org/EIPS/eip-1474>
+                    code: ErrorCode::ServerError(-32005),
+                    message: err.to_string().into(),
+                    data: None,
+                },
                 err @ BlockchainError::BlockNotFound => RpcError {
                     // <https://eips.ethereum.org/EIPS/eip-1898>
                     code: ErrorCode::ServerError(-32001),
@@ -573,9 +611,21 @@ fn to_rpc_result(self) -> ResponseResult {
                 err @ BlockchainError::UnknownTransactionType => {
                     RpcError::invalid_params(err.to_string())
                 }
+                err @ BlockchainError::TransactionTypeNotAllowed(_) => {
+                    RpcError::invalid_params(err.to_string())
+                }
                 err @ BlockchainError::MissingRequiredFields => {
                     RpcError::invalid_params(err.to_string())
                 }
+                err @ BlockchainError::MiningHalted(_) => {
+                    RpcError::internal_error_with(err.to_string())
+                }
+                err @ BlockchainError::StateAccountLimitExceeded(_) => RpcError {
+                    // <https://eips.ethereum.org/EIPS/eip-1474>
+                    code: ErrorCode::ServerError(-32005),
+                    message: err.to_string().into(),
+                    data: None,
+                },
             }
             .into(),
         }
@@ -41,6 +41,17 @@ pub fn default_elasticity() -> f64 {
     1f64 / BaseFeeParams::ethereum().elasticity_multiplier as f64
 }
 
+/// How the node suggests a legacy gas price via `eth_gasPrice`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GasPriceOracle {
+    /// Suggest the base fee of the next block, or the configured gas price pre London
+    #[default]
+    Fixed,
+    /// Suggest the median gas price paid in recent blocks, never less than the next block's base
+    /// fee
+    Dynamic,
+}
+
 /// Stores the fee related information
 #[derive(Clone, Debug)]
 pub struct FeeManager {
@@ -173,6 +184,12 @@ pub fn calculate_next_block_base_fee(gas_used: u64, gas_limit: u64, base_fee: u6
     calc_next_block_base_fee(gas_used, gas_limit, base_fee, BaseFeeParams::ethereum())
 }
 
+/// Returns the gas target of a block, the base fee of the next block rises if the block used more
+/// gas and falls if it used less
+pub fn calculate_gas_target(gas_limit: u64) -> u64 {
+    gas_limit / BaseFeeParams::ethereum().elasticity_multiplier as u64
+}
+
 /// An async service that takes care of the `FeeHistory` cache
 pub struct FeeHistoryService {
     /// blob parameters for the current spec
@@ -27,6 +27,8 @@ pub struct Miner {
     /// Transactions included into the pool before any others are.
     /// Done once on startup.
     force_transactions: Option<Vec<Arc<PoolTransaction>>>,
+    /// Whether the miner skips blocks that would not contain any transactions
+    skip_empty_blocks: bool,
 }
 
 impl Miner {
@@ -36,9 +38,17 @@ pub fn new(mode: MiningMode) -> Self {
             mode: Arc::new(RwLock::new(mode)),
             inner: Default::default(),
             force_transactions: None,
+            skip_empty_blocks: false,
         }
     }
 
+    /// Sets whether the miner skips blocks that would not contain any transactions, e.g. the
+    /// ticks of interval mining while the pool is empty.
+    pub fn with_skip_empty_blocks(mut self, skip_empty_blocks: bool) -> Self {
+        self.skip_empty_blocks = skip_empty_blocks;
+        self
+    }
+
     /// Provide transactions that will cause a block to be mined with transactions
     /// as soon as the miner is polled.
     /// Providing an empty list of transactions will cause the miner to mine an empty block assuming
@@ -79,22 +89,32 @@ pub fn set_mining_mode(&self, mode: MiningMode) {
         self.inner.wake();
     }
 
+    /// Wakes the task polling the miner, e.g. to resume mining after it was halted
+    pub fn wake(&self) {
+        self.inner.wake();
+    }
+
     /// polls the [Pool] and returns those transactions that should be put in a block according to
     /// the current mode.
     ///
-    /// May return an empty list, if no transactions are ready.
+    /// May return an empty list, if no transactions are ready and empty blocks aren't skipped.
     pub fn poll(
         &mut self,
         pool: &Arc<Pool>,
         cx: &mut Context<'_>,
     ) -> Poll<Vec<Arc<PoolTransaction>>> {
         self.inner.register(cx);
-        let next = ready!(self.mode.write().poll(pool, cx));
-        if let Some(mut transactions) = self.force_transactions.take() {
-            transactions.extend(next);
-            Poll::Ready(transactions)
-        } else {
-            Poll::Ready(next)
+        loop {
+            let next = ready!(self.mode.write().poll(pool, cx));
+            if let Some(mut transactions) = self.force_transactions.take() {
+                transactions.extend(next);
+                return Poll::Ready(transactions);
+            }
+            // poll the mode again, so that we're woken up for its next tick
+            if next.is_empty() && self.skip_empty_blocks {
+                continue;
+            }
+            return Poll::Ready(next);
         }
     }
 }
@@ -36,12 +36,12 @@
     },
     mem::storage::MinedBlockOutcome,
 };
-use alloy_primitives::{Address, TxHash};
+use alloy_primitives::{Address, TxHash, map::HashMap};
 use alloy_rpc_types::txpool::TxpoolStatus;
 use anvil_core::eth::transaction::PendingTransaction;
 use futures::channel::mpsc::{Receiver, Sender, channel};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::VecDeque, fmt, sync::Arc};
+use std::{collections::VecDeque, fmt, sync::Arc, time::Duration};
 
 pub mod transactions;
 
@@ -57,6 +57,34 @@ pub struct Pool {
 // == impl Pool ==
 
 impl Pool {
+    /// Sets whether transactions with a future nonce are queued until they become ready, or
+    /// rejected
+    pub fn with_queue_future_nonce(mut self, queue_future_nonce: bool) -> Self {
+        self.inner.get_mut().reject_future_nonce = !queue_future_nonce;
+        self
+    }
+
+    /// Sets the time after which transactions that weren't mined are evicted from the pool
+    pub fn with_tx_ttl(mut self, tx_ttl: Option<Duration>) -> Self {
+        self.inner.get_mut().tx_ttl = tx_ttl;
+        self
+    }
+
+    /// Returns the time after which transactions that weren't mined are evicted, if any
+    pub fn tx_ttl(&self) -> Option<Duration> {
+        self.inner.read().tx_ttl
+    }
+
+    /// Evicts all transactions that have been in the pool for longer than the configured TTL,
+    /// including the transactions that depend on them
+    pub fn evict_expired(&self) -> Vec<Arc<PoolTransaction>> {
+        let evicted = self.inner.write().evict_expired();
+        if !evicted.is_empty() {
+            debug!(target: "txpool", "evicted {} expired transactions", evicted.len());
+        }
+        evicted
+    }
+
     /// Returns an iterator that yields all transactions that are currently ready
     pub fn ready_transactions(&self) -> TransactionsIterator {
         self.inner.read().ready_transactions()
@@ -109,6 +137,12 @@ pub fn prune_markers(
         self.inner.write().prune_markers(markers)
     }
 
+    /// Returns the error adding the transaction to the pool would fail with right now, without
+    /// adding it
+    pub fn validate_transaction(&self, tx: &PoolTransaction) -> Result<(), PoolError> {
+        self.inner.read().validate_transaction(tx)
+    }
+
     /// Adds a new transaction to the pool
     pub fn add_transaction(&self, tx: PoolTransaction) -> Result<AddedTransaction, PoolError> {
         let added = self.inner.write().add_transaction(tx)?;
@@ -166,6 +200,12 @@ pub fn drop_transaction(&self, tx: TxHash) -> Option<Arc<PoolTransaction>> {
         dropped
     }
 
+    /// Returns the hashes of all transactions that occupied the `(sender, nonce)` slot, in the
+    /// order they were added, if any of them got replaced.
+    pub fn replacements(&self, sender: Address, nonce: u64) -> Vec<TxHash> {
+        self.inner.read().replacements.get(&(sender, nonce)).cloned().unwrap_or_default()
+    }
+
     /// Removes all transactions from the pool
     pub fn clear(&self) {
         let mut pool = self.inner.write();
@@ -200,6 +240,14 @@ fn notify_listener(&self, hash: TxHash) {
     }
 }
 
+/// Maximum number of `(sender, nonce)` slots whose replacement history is kept, the history of the
+/// oldest slot is dropped first
+const MAX_REPLACEMENT_SLOTS: usize = 1024;
+
+/// Maximum number of transactions kept in the replacement history of a single slot, the oldest
+/// ones are dropped first
+const MAX_REPLACEMENTS_PER_SLOT: usize = 64;
+
 /// A Transaction Pool
 ///
 /// Contains all transactions that are ready to be executed
@@ -207,6 +255,14 @@ fn notify_listener(&self, hash: TxHash) {
 struct PoolInner {
     ready_transactions: ReadyTransactions,
     pending_transactions: PendingTransactions,
+    /// history of transactions that replaced each other, keyed by `(sender, nonce)`
+    replacements: HashMap<(Address, u64), Vec<TxHash>>,
+    /// slots in `replacements`, oldest first
+    replacement_slots: VecDeque<(Address, u64)>,
+    /// whether transactions that are not ready are rejected instead of queued
+    reject_future_nonce: bool,
+    /// time after which transactions that weren't mined are evicted
+    tx_ttl: Option<Duration>,
 }
 
 // == impl PoolInner ==
@@ -258,6 +314,21 @@ fn contains(&self, tx_hash: &TxHash) -> bool {
         self.pending_transactions.contains(tx_hash) || self.ready_transactions.contains(tx_hash)
     }
 
+    fn validate_transaction(&self, tx: &PoolTransaction) -> Result<(), PoolError> {
+        if self.contains(&tx.hash()) {
+            return Err(PoolError::AlreadyImported(Box::new(tx.clone())));
+        }
+        let tx =
+            PendingPoolTransaction::new(tx.clone(), self.ready_transactions.provided_markers());
+        if tx.is_ready() {
+            self.ready_transactions.ensure_replaceable(&tx.transaction)
+        } else if self.reject_future_nonce {
+            Err(PoolError::FutureNonce(tx.transaction.hash()))
+        } else {
+            self.pending_transactions.ensure_replaceable(&tx)
+        }
+    }
+
     fn add_transaction(&mut self, tx: PoolTransaction) -> Result<AddedTransaction, PoolError> {
         if self.contains(&tx.hash()) {
             warn!(target: "txpool", "[{:?}] Already imported", tx.hash());
@@ -269,6 +340,10 @@ fn add_transaction(&mut self, tx: PoolTransaction) -> Result<AddedTransaction, P
 
         // If all markers are not satisfied import to future
         if !tx.is_ready() {
+            if self.reject_future_nonce {
+                warn!(target: "txpool", "[{:?}] Rejected future nonce", tx.transaction.hash());
+                return Err(PoolError::FutureNonce(tx.transaction.hash()));
+            }
             let hash = tx.transaction.hash();
             self.pending_transactions.add_transaction(tx)?;
             return Ok(AddedTransaction::Pending { hash });
@@ -297,10 +372,20 @@ fn add_ready_transaction(
             );
 
             let current_hash = current_tx.transaction.hash();
+            let current_slot = (
+                *current_tx.transaction.pending_transaction.sender(),
+                current_tx.transaction.pending_transaction.nonce(),
+            );
             // try to add the transaction to the ready pool
             match self.ready_transactions.add_transaction(current_tx) {
                 Ok(replaced_transactions) => {
-                    if !is_new_tx {
+                    if is_new_tx {
+                        self.record_replacements(
+                            current_slot,
+                            current_hash,
+                            &replaced_transactions,
+                        );
+                    } else {
                         ready.promoted.push(current_hash);
                     }
                     // tx removed from ready pool
@@ -331,6 +416,43 @@ fn add_ready_transaction(
         Ok(AddedTransaction::Ready(ready))
     }
 
+    /// Tracks the transactions that `hash` replaced in its `(sender, nonce)` slot
+    fn record_replacements(
+        &mut self,
+        slot: (Address, u64),
+        hash: TxHash,
+        replaced: &[Arc<PoolTransaction>],
+    ) {
+        let mut replaced = replaced
+            .iter()
+            .filter(|tx| (*tx.pending_transaction.sender(), tx.pending_transaction.nonce()) == slot)
+            .map(|tx| tx.hash())
+            .peekable();
+        if replaced.peek().is_none() {
+            return;
+        }
+
+        if !self.replacements.contains_key(&slot) {
+            if self.replacement_slots.len() >= MAX_REPLACEMENT_SLOTS
+                && let Some(oldest) = self.replacement_slots.pop_front()
+            {
+                self.replacements.remove(&oldest);
+            }
+            self.replacement_slots.push_back(slot);
+        }
+
+        let history = self.replacements.entry(slot).or_default();
+        for old in replaced {
+            if !history.contains(&old) {
+                history.push(old);
+            }
+        }
+        history.push(hash);
+        if history.len() > MAX_REPLACEMENTS_PER_SLOT {
+            history.drain(..history.len() - MAX_REPLACEMENTS_PER_SLOT);
+        }
+    }
+
     /// Prunes the transactions that provide the given markers
     ///
     /// This will effectively remove those transactions that satisfy the markers and transactions
@@ -378,6 +500,14 @@ pub fn remove_invalid(&mut self, tx_hashes: Vec<TxHash>) -> Vec<Arc<PoolTransact
         removed
     }
 
+    /// Removes all transactions that were added more than the TTL ago
+    fn evict_expired(&mut self) -> Vec<Arc<PoolTransaction>> {
+        let Some(ttl) = self.tx_ttl else { return vec![] };
+        let mut expired = self.ready_transactions.expired(ttl);
+        expired.extend(self.pending_transactions.expired(ttl));
+        self.remove_invalid(expired)
+    }
+
     /// Remove transactions by sender address
     pub fn remove_transactions_by_address(&mut self, sender: Address) -> Vec<Arc<PoolTransaction>> {
         let tx_hashes =
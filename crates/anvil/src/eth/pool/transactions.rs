@@ -6,7 +6,14 @@
 };
 use anvil_core::eth::transaction::{PendingTransaction, TypedTransaction};
 use parking_lot::RwLock;
-use std::{cmp::Ordering, collections::BTreeSet, fmt, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    cmp::Ordering,
+    collections::BTreeSet,
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// A unique identifying marker for a transaction
 pub type TxMarker = Vec<u8>;
@@ -167,6 +174,15 @@ pub fn transactions(&self) -> impl Iterator<Item = Arc<PoolTransaction>> + '_ {
         self.waiting_queue.values().map(|tx| tx.transaction.clone())
     }
 
+    /// Returns the hashes of all transactions that were added more than `ttl` ago
+    pub fn expired(&self, ttl: Duration) -> Vec<TxHash> {
+        self.waiting_queue
+            .iter()
+            .filter(|(_, tx)| tx.added_at.elapsed() > ttl)
+            .map(|(hash, _)| *hash)
+            .collect()
+    }
+
     /// Adds a transaction to Pending queue of transactions
     pub fn add_transaction(&mut self, tx: PendingPoolTransaction) -> Result<(), PoolError> {
         assert!(!tx.is_ready(), "transaction must not be ready");
@@ -175,6 +191,24 @@ pub fn add_transaction(&mut self, tx: PendingPoolTransaction) -> Result<(), Pool
             "transaction is already added"
         );
 
+        self.ensure_replaceable(&tx)?;
+
+        // add all missing markers
+        for marker in &tx.missing_markers {
+            self.required_markers.entry(marker.clone()).or_default().insert(tx.transaction.hash());
+        }
+
+        // also track identifying markers
+        self.waiting_markers.insert(tx.transaction.provides.clone(), tx.transaction.hash());
+        // add tx to the queue
+        self.waiting_queue.insert(tx.transaction.hash(), tx);
+
+        Ok(())
+    }
+
+    /// Returns an error if the transaction would replace a pending transaction with a higher gas
+    /// price
+    pub fn ensure_replaceable(&self, tx: &PendingPoolTransaction) -> Result<(), PoolError> {
         if let Some(replace) = self
             .waiting_markers
             .get(&tx.transaction.provides)
@@ -188,17 +222,6 @@ pub fn add_transaction(&mut self, tx: PendingPoolTransaction) -> Result<(), Pool
                 )));
             }
         }
-
-        // add all missing markers
-        for marker in &tx.missing_markers {
-            self.required_markers.entry(marker.clone()).or_default().insert(tx.transaction.hash());
-        }
-
-        // also track identifying markers
-        self.waiting_markers.insert(tx.transaction.provides.clone(), tx.transaction.hash());
-        // add tx to the queue
-        self.waiting_queue.insert(tx.transaction.hash(), tx);
-
         Ok(())
     }
 
@@ -432,6 +455,16 @@ fn next_id(&mut self) -> u64 {
         id
     }
 
+    /// Returns the hashes of all transactions that were added to the pool more than `ttl` ago
+    pub fn expired(&self, ttl: Duration) -> Vec<TxHash> {
+        self.ready_tx
+            .read()
+            .iter()
+            .filter(|(_, tx)| tx.added_at.elapsed() > ttl)
+            .map(|(hash, _)| *hash)
+            .collect()
+    }
+
     /// Adds a new transactions to the ready queue.
     ///
     /// # Panics
@@ -474,6 +507,7 @@ pub fn add_transaction(
             self.provided_markers.insert(mark, hash);
         }
 
+        let added_at = tx.added_at;
         let transaction = PoolTransactionRef { id, transaction: tx.transaction };
 
         // add to the independent set
@@ -482,11 +516,36 @@ pub fn add_transaction(
         }
 
         // insert to ready queue
-        ready.insert(hash, ReadyTransaction { transaction, unlocks, requires_offset });
+        ready.insert(hash, ReadyTransaction { transaction, unlocks, requires_offset, added_at });
 
         Ok(replaced_tx)
     }
 
+    /// Returns an error if the transaction would replace a ready transaction with the same sender
+    /// and nonce without paying a higher gas price
+    pub fn ensure_replaceable(&self, tx: &PoolTransaction) -> Result<(), PoolError> {
+        let ready = self.ready_tx.read();
+        let replaced = tx
+            .provides
+            .iter()
+            .filter_map(|mark| self.provided_markers.get(mark))
+            .filter_map(|hash| ready.get(hash));
+        for to_remove in replaced {
+            // if we're attempting to replace a transaction that provides the exact same markers
+            // (addr + nonce) then we check for gas price
+            if to_remove.provides() == tx.provides {
+                // check if underpriced
+                if tx.pending_transaction.transaction.gas_price() <= to_remove.gas_price() {
+                    warn!(target: "txpool", "ready replacement transaction underpriced [{:?}]", tx.hash());
+                    return Err(PoolError::ReplacementUnderpriced(Box::new(tx.clone())));
+                } else {
+                    trace!(target: "txpool", "replacing ready transaction [{:?}] with higher gas price [{:?}]", to_remove.transaction.transaction.hash(), tx.hash());
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Removes and returns those transactions that got replaced by the `tx`
     fn replaced_transactions(
         &mut self,
@@ -502,25 +561,13 @@ fn replaced_transactions(
         }
 
         // check if we're replacing the same transaction and if it can be replaced
+        self.ensure_replaceable(tx)?;
 
+        // construct a list of unlocked transactions
         let mut unlocked_tx = Vec::new();
         {
-            // construct a list of unlocked transactions
-            // also check for transactions that shouldn't be replaced because underpriced
             let ready = self.ready_tx.read();
             for to_remove in remove_hashes.iter().filter_map(|hash| ready.get(*hash)) {
-                // if we're attempting to replace a transaction that provides the exact same markers
-                // (addr + nonce) then we check for gas price
-                if to_remove.provides() == tx.provides {
-                    // check if underpriced
-                    if tx.pending_transaction.transaction.gas_price() <= to_remove.gas_price() {
-                        warn!(target: "txpool", "ready replacement transaction underpriced [{:?}]", tx.hash());
-                        return Err(PoolError::ReplacementUnderpriced(Box::new(tx.clone())));
-                    } else {
-                        trace!(target: "txpool", "replacing ready transaction [{:?}] with higher gas price [{:?}]", to_remove.transaction.transaction.hash(), tx.hash());
-                    }
-                }
-
                 unlocked_tx.extend(to_remove.unlocks.iter().copied())
             }
         }
@@ -704,6 +751,8 @@ pub struct ReadyTransaction {
     pub unlocks: Vec<TxHash>,
     /// amount of required markers that are inherently provided
     pub requires_offset: usize,
+    /// timestamp when the tx was added to the pool
+    pub added_at: Instant,
 }
 
 impl ReadyTransaction {
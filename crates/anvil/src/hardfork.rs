@@ -84,6 +84,39 @@ pub fn ethereum_hardfork_from_block_tag(block: impl Into<BlockNumberOrTag>) -> E
     EthereumHardfork::from_mainnet_block_number(num)
 }
 
+/// Returns the static block reward in wei paid to miners under the given spec, or `None` for
+/// proof-of-stake specs.
+pub fn base_block_reward(spec: SpecId) -> Option<u128> {
+    const ETH: u128 = 1_000_000_000_000_000_000;
+    if spec.is_enabled_in(SpecId::MERGE) {
+        None
+    } else if spec.is_enabled_in(SpecId::CONSTANTINOPLE) {
+        Some(2 * ETH)
+    } else if spec.is_enabled_in(SpecId::BYZANTIUM) {
+        Some(3 * ETH)
+    } else {
+        Some(5 * ETH)
+    }
+}
+
+/// Computes the reward of an uncle with number `uncle_number` included in block `block_number`
+/// under the given spec: `(uncle_number + 8 - block_number) * block_reward / 8`.
+///
+/// Returns `None` if the spec is post-merge or the uncle isn't one of the six ancestors of the
+/// including block.
+pub fn uncle_reward(spec: SpecId, block_number: u64, uncle_number: u64) -> Option<u128> {
+    let block_reward = base_block_reward(spec)?;
+    let depth = block_number.checked_sub(uncle_number).filter(|depth| (1..=6).contains(depth))?;
+    Some((8 - depth) as u128 * block_reward / 8)
+}
+
+/// Computes the reward paid to the miner of a block including `uncles` uncles under the given
+/// spec: the block reward plus `block_reward / 32` per uncle.
+pub fn miner_reward(spec: SpecId, uncles: usize) -> Option<u128> {
+    let block_reward = base_block_reward(spec)?;
+    Some(block_reward + uncles as u128 * block_reward / 32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +155,24 @@ fn test_hardfork_from_block_tag_numbers() {
             EthereumHardfork::London
         );
     }
+
+    #[test]
+    fn test_uncle_reward() {
+        // frontier: 5 ETH block reward
+        assert_eq!(uncle_reward(SpecId::FRONTIER, 190, 189), Some(4_375_000_000_000_000_000));
+        // byzantium: 3 ETH block reward
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 98), Some(2_250_000_000_000_000_000));
+        // uncles can be at most 6 blocks older than the including block
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 91), None);
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 93), None);
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 94), Some(750_000_000_000_000_000));
+        // an uncle can't be the including block or a descendant of it
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 100), None);
+        assert_eq!(uncle_reward(SpecId::BYZANTIUM, 100, 120), None);
+        // no rewards after the merge
+        assert_eq!(uncle_reward(SpecId::MERGE, 100, 99), None);
+
+        assert_eq!(miner_reward(SpecId::PETERSBURG, 2), Some(2_125_000_000_000_000_000));
+        assert_eq!(miner_reward(SpecId::CANCUN, 0), None);
+    }
 }
@@ -20,7 +20,8 @@
     tasks::TaskManager,
 };
 use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::{Address, U256};
+use alloy_network::EthereumWallet;
+use alloy_primitives::{Address, B256, U256};
 use alloy_signer_local::PrivateKeySigner;
 use eth::backend::fork::ClientFork;
 use eyre::Result;
@@ -31,9 +32,11 @@
 use server::try_spawn_ipc;
 use std::{
     net::SocketAddr,
+    path::PathBuf,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use tokio::{
     runtime::Handle,
@@ -68,6 +71,8 @@
 mod shutdown;
 /// additional task management
 mod tasks;
+/// Notifications sent to an external endpoint
+mod webhook;
 
 /// contains cli command
 #[cfg(feature = "cmd")]
@@ -145,6 +150,16 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
     }
 
     let fork = backend.get_fork();
+    let fork_details = fork.as_ref().map(|fork| {
+        let env = backend.env().read();
+        ForkDetails {
+            block_number: env.evm_env.block_env.number.saturating_to(),
+            block_hash: fork.block_hash(),
+            chain_id: env.evm_env.cfg_env.chain_id,
+            base_fee: env.evm_env.block_env.basefee,
+            eth_rpc_url: fork.eth_rpc_url(),
+        }
+    });
 
     let NodeConfig {
         signer_accounts,
@@ -156,10 +171,26 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
         transaction_order,
         genesis,
         mixed_mining,
+        max_txs_per_block,
+        queue_future_nonce,
+        tx_ttl,
+        mine_on_send,
+        skip_empty_blocks,
+        allowed_tx_types,
+        gas_price_oracle,
+        call_result_cache,
+        estimate_ignore_block_limit,
+        inclusion_delay,
+        max_concurrent_calls,
+        tx_webhook,
         ..
     } = config.clone();
 
-    let pool = Arc::new(Pool::default());
+    let max_transactions =
+        max_txs_per_block.map_or(max_transactions, |max| max.min(max_transactions));
+
+    let pool =
+        Arc::new(Pool::default().with_queue_future_nonce(queue_future_nonce).with_tx_ttl(tx_ttl));
 
     let mode = if let Some(block_time) = block_time {
         if mixed_mining {
@@ -168,7 +199,7 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
         } else {
             MiningMode::interval(block_time)
         }
-    } else if no_mining {
+    } else if no_mining || mine_on_send {
         MiningMode::None
     } else {
         // get a listener for ready transactions
@@ -181,7 +212,8 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
             Miner::new(mode).with_forced_transactions(fork.config.read().force_transactions.clone())
         }
         _ => Miner::new(mode),
-    };
+    }
+    .with_skip_empty_blocks(skip_empty_blocks);
 
     let dev_signer: Box<dyn EthSigner> = Box::new(DevSigner::new(signer_accounts));
     let mut signers = vec![dev_signer];
@@ -215,6 +247,11 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
 
     let filters = Filters::default();
 
+    // subscribe before the node service starts so no transaction is missed
+    let webhook = tx_webhook.map(|url| {
+        (url, Arc::clone(&backend), pool.add_ready_listener(), backend.new_block_notifications())
+    });
+
     // create the cloneable api wrapper
     let api = EthApi::new(
         Arc::clone(&pool),
@@ -226,7 +263,14 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
         logger,
         filters.clone(),
         transaction_order,
-    );
+    )
+    .with_mine_on_send(mine_on_send)
+    .with_allowed_tx_types(allowed_tx_types)
+    .with_gas_price_oracle(gas_price_oracle)
+    .with_call_result_cache(call_result_cache)
+    .with_estimate_ignore_block_limit(estimate_ignore_block_limit)
+    .with_inclusion_delay(inclusion_delay)
+    .with_max_concurrent_calls(max_concurrent_calls);
 
     // spawn the node service
     let node_service =
@@ -254,8 +298,17 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
     let ipc_task =
         config.get_ipc_path().map(|path| try_spawn_ipc(api.clone(), path)).transpose()?;
 
+    if let Some((interval, path)) = config.state_autosave.clone() {
+        task_manager.spawn(autosave_state(api.clone(), interval, path));
+    }
+
+    if let Some((url, backend, received, mined)) = webhook {
+        task_manager.spawn(webhook::tx_webhook(url, backend, received, mined));
+    }
+
     let handle = NodeHandle {
         config,
+        fork_details,
         node_service,
         servers,
         ipc_task,
@@ -269,13 +322,59 @@ pub async fn try_spawn(mut config: NodeConfig) -> Result<(EthApi, NodeHandle)> {
     Ok((api, handle))
 }
 
+/// Endless task that dumps the full state of the node to `path` every `interval`.
+///
+/// The state is written to a temporary file next to `path` first and then renamed, so a crash
+/// mid-write never leaves a corrupt file behind.
+async fn autosave_state(api: EthApi, interval: Duration, path: PathBuf) {
+    let mut tmp_path = path.clone().into_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        interval.tick().await;
+        trace!(?path, "autosaving state");
+        match api.serialized_state(false).await {
+            Ok(state) => {
+                let res = foundry_common::fs::write_json_file(&tmp_path, &state)
+                    .map_err(|err| err.to_string())
+                    .and_then(|_| std::fs::rename(&tmp_path, &path).map_err(|err| err.to_string()));
+                if let Err(err) = res {
+                    error!(?err, "Failed to autosave state");
+                }
+            }
+            Err(err) => {
+                error!(?err, "Failed to extract state for autosave");
+            }
+        }
+    }
+}
+
 type IpcTask = JoinHandle<()>;
 
+/// The fork a node was spawned with, see [`NodeHandle::fork_details`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkDetails {
+    /// Number of the forked block
+    pub block_number: u64,
+    /// Hash of the forked block
+    pub block_hash: B256,
+    /// Chain id of the node
+    pub chain_id: u64,
+    /// Base fee of the forked block
+    pub base_fee: u64,
+    /// The RPC url the fork was fetched from
+    pub eth_rpc_url: String,
+}
+
 /// A handle to the spawned node and server tasks.
 ///
 /// This future will resolve if either the node or server task resolve/fail.
 pub struct NodeHandle {
     config: NodeConfig,
+    /// The resolved fork, if the node was spawned in fork mode.
+    fork_details: Option<ForkDetails>,
     /// The address of the running rpc server.
     addresses: Vec<SocketAddr>,
     /// Join handle for the Node Service.
@@ -305,6 +404,14 @@ pub fn config(&self) -> &NodeConfig {
         &self.config
     }
 
+    /// Returns the block, chain id and RPC url the node forked off, resolved when the node was
+    /// spawned, or `None` if it isn't forking.
+    ///
+    /// Unlike `anvil_nodeInfo` this does not reflect later resets of the fork.
+    pub fn fork_details(&self) -> Option<ForkDetails> {
+        self.fork_details.clone()
+    }
+
     /// Prints the launch info.
     pub(crate) fn print(&self, fork: Option<&ClientFork>) -> Result<()> {
         self.config.print(fork)?;
@@ -372,6 +479,11 @@ pub fn dev_wallets(&self) -> impl Iterator<Item = PrivateKeySigner> + '_ {
         self.config.signer_accounts.iter().cloned()
     }
 
+    /// Dev accounts as [`EthereumWallet`]s, ready to be used as the signer of a provider.
+    pub fn dev_signers(&self) -> impl Iterator<Item = EthereumWallet> + '_ {
+        self.dev_wallets().map(EthereumWallet::from)
+    }
+
     /// Accounts that will be initialised with `genesis_balance` in the genesis block.
     pub fn genesis_accounts(&self) -> impl Iterator<Item = Address> + '_ {
         self.config.genesis_accounts.iter().map(|w| w.address())
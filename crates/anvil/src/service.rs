@@ -16,9 +16,13 @@
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use tokio::{task::JoinHandle, time::Interval};
 
+/// The maximum interval at which the pool is checked for expired transactions
+const MAX_TX_EVICTION_INTERVAL: Duration = Duration::from_secs(1);
+
 /// The type that drives the blockchain's state
 ///
 /// This service is basically an endless future that continuously polls the miner which returns
@@ -28,6 +32,8 @@
 pub struct NodeService {
     /// The pool that holds all transactions.
     pool: Arc<Pool>,
+    /// Holds the registered invariant check that may halt mining.
+    backend: Arc<Backend>,
     /// Creates new blocks.
     block_producer: BlockProducer,
     /// The miner responsible to select transactions from the `pool`.
@@ -38,6 +44,8 @@ pub struct NodeService {
     filters: Filters,
     /// The interval at which to check for filters that need to be evicted
     filter_eviction_interval: Interval,
+    /// The interval at which to check for transactions that expired, if they expire
+    tx_eviction_interval: Option<Interval>,
 }
 
 impl NodeService {
@@ -50,12 +58,18 @@ pub fn new(
     ) -> Self {
         let start = tokio::time::Instant::now() + filters.keep_alive();
         let filter_eviction_interval = tokio::time::interval_at(start, filters.keep_alive());
+        let tx_eviction_interval = pool.tx_ttl().map(|ttl| {
+            let period = ttl.min(MAX_TX_EVICTION_INTERVAL);
+            tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+        });
         Self {
             pool,
-            block_producer: BlockProducer::new(backend),
+            block_producer: BlockProducer::new(backend.clone()),
+            backend,
             miner,
             fee_history,
             filter_eviction_interval,
+            tx_eviction_interval,
             filters,
         }
     }
@@ -77,6 +91,11 @@ fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
                 pin.pool.on_mined_block(outcome);
             }
 
+            // a failed invariant check halts mining until a new check is registered
+            if pin.backend.invariant_violation().is_some() {
+                break;
+            }
+
             if let Poll::Ready(transactions) = pin.miner.poll(&pin.pool, cx) {
                 // miner returned a set of transaction that we feed to the producer
                 pin.block_producer.queued.push_back(transactions);
@@ -89,6 +108,13 @@ fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // poll the fee history task
         let _ = pin.fee_history.poll_unpin(cx);
 
+        if let Some(interval) = &mut pin.tx_eviction_interval
+            && interval.poll_tick(cx).is_ready()
+        {
+            // evict transactions that weren't mined in time
+            pin.pool.evict_expired();
+        }
+
         if pin.filter_eviction_interval.poll_tick(cx).is_ready() {
             let filters = pin.filters.clone();
 
@@ -135,7 +161,9 @@ fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::It
                     handle.block_on(async move {
                         trace!(target: "miner", "creating new block");
                         let block = backend.mine_block(transactions).await;
-                        trace!(target: "miner", "created new block: {}", block.block_number);
+                        if let Ok(block) = &block {
+                            trace!(target: "miner", "created new block: {}", block.block_number);
+                        }
                         (block, backend)
                     })
                 });
@@ -146,10 +174,17 @@ fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::It
         if let Some(mut mining) = pin.block_mining.take() {
             if let Poll::Ready(res) = mining.poll_unpin(cx) {
                 return match res {
-                    Ok((outcome, backend)) => {
+                    Ok((Ok(outcome), backend)) => {
                         pin.idle_backend = Some(backend);
                         Poll::Ready(Some(outcome))
                     }
+                    Ok((Err(err), backend)) => {
+                        // the transactions stay in the pool until mining resumes
+                        trace!(target: "miner", %err, "didn't create new block");
+                        pin.idle_backend = Some(backend);
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
                     Err(err) => {
                         panic!("miner task failed: {err}");
                     }
@@ -0,0 +1,78 @@
+//! Notifies an external endpoint about received and mined transactions
+
+use crate::eth::backend::{mem::Backend, notifications::NewBlockNotifications};
+use alloy_primitives::{B256, TxHash};
+use futures::{StreamExt, channel::mpsc::Receiver, stream};
+use serde::Serialize;
+use std::sync::Arc;
+use url::Url;
+
+/// What happened to the transaction a webhook is sent for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxWebhookEvent {
+    /// The transaction was added to the pool and is ready to be mined
+    Received,
+    /// The transaction was included in a block
+    Mined,
+}
+
+/// JSON payload POSTed to the transaction webhook
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxWebhookPayload {
+    /// What happened to the transaction
+    pub event: TxWebhookEvent,
+    /// Hash of the transaction
+    pub transaction_hash: TxHash,
+    /// Number of the block the transaction was mined in, unset for received transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Hash of the block the transaction was mined in, unset for received transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+}
+
+/// Endless task that POSTs a [`TxWebhookPayload`] to `url` for every transaction that becomes
+/// ready in the pool and every transaction that is mined.
+///
+/// Failed deliveries are logged and not retried.
+pub(crate) async fn tx_webhook(
+    url: Url,
+    backend: Arc<Backend>,
+    received: Receiver<TxHash>,
+    mined: NewBlockNotifications,
+) {
+    let client = reqwest::Client::new();
+
+    let received = received.map(|hash| {
+        vec![TxWebhookPayload {
+            event: TxWebhookEvent::Received,
+            transaction_hash: hash,
+            block_number: None,
+            block_hash: None,
+        }]
+    });
+    let mined = mined.map(|notification| {
+        let Some(block) = backend.get_block(notification.hash) else { return Vec::new() };
+        block
+            .transactions
+            .iter()
+            .map(|tx| TxWebhookPayload {
+                event: TxWebhookEvent::Mined,
+                transaction_hash: tx.hash(),
+                block_number: Some(notification.header.number),
+                block_hash: Some(notification.hash),
+            })
+            .collect()
+    });
+
+    let mut payloads = stream::select(received, mined).flat_map(stream::iter);
+    while let Some(payload) = payloads.next().await {
+        trace!(target: "node", ?payload, "sending transaction webhook");
+        let res = client.post(url.clone()).json(&payload).send().await;
+        if let Err(err) = res.and_then(|res| res.error_for_status()) {
+            warn!(target: "node", %err, "failed to send transaction webhook");
+        }
+    }
+}
@@ -3,8 +3,10 @@
 use alloy_consensus::EMPTY_ROOT_HASH;
 use alloy_eips::BlockNumberOrTag;
 use alloy_hardforks::EthereumHardfork;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, keccak256};
 use alloy_provider::Provider;
+use alloy_rpc_types::TransactionRequest;
+use alloy_serde::WithOtherFields;
 use anvil::{NodeConfig, spawn};
 
 #[tokio::test(flavor = "multi_thread")]
@@ -39,6 +41,40 @@ async fn test_can_change_mining_mode() {
     assert_eq!(num, 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_can_skip_empty_blocks() {
+    let (api, handle) = spawn(
+        NodeConfig::test()
+            .with_blocktime(Some(std::time::Duration::from_millis(200)))
+            .with_skip_empty_blocks(true),
+    )
+    .await;
+    let provider = handle.http_provider();
+
+    // several intervals pass without any transactions
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    assert_eq!(provider.get_block_number().await.unwrap(), 0);
+
+    let tx = TransactionRequest::default()
+        .from(handle.dev_accounts().next().unwrap())
+        .to(Address::random());
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert_eq!(receipt.block_number, Some(1));
+
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    assert_eq!(provider.get_block_number().await.unwrap(), 1);
+
+    // explicitly mined blocks may be empty
+    api.evm_mine(None).await.unwrap();
+    assert_eq!(provider.get_block_number().await.unwrap(), 2);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_get_default_dev_keys() {
     let (_api, handle) = spawn(NodeConfig::test()).await;
@@ -85,7 +121,7 @@ async fn test_can_handle_large_timestamp() {
     let (api, _handle) = spawn(NodeConfig::test()).await;
     let num = 317071597274;
     api.evm_set_next_block_timestamp(num).unwrap();
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert_eq!(block.header.timestamp, num);
@@ -95,7 +131,7 @@ async fn test_can_handle_large_timestamp() {
 async fn test_shanghai_fields() {
     let (api, _handle) =
         spawn(NodeConfig::test().with_hardfork(Some(EthereumHardfork::Shanghai.into()))).await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert_eq!(block.header.withdrawals_root, Some(EMPTY_ROOT_HASH));
@@ -108,7 +144,7 @@ async fn test_shanghai_fields() {
 async fn test_cancun_fields() {
     let (api, _handle) =
         spawn(NodeConfig::test().with_hardfork(Some(EthereumHardfork::Cancun.into()))).await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert_eq!(block.header.withdrawals_root, Some(EMPTY_ROOT_HASH));
@@ -135,3 +171,20 @@ async fn test_can_use_default_genesis_block_number() {
 
     assert_eq!(0, provider.get_block(0.into()).await.unwrap().unwrap().header.number);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deterministic_prevrandao_seed() {
+    let seed = 1337u64;
+    let (api, handle) = spawn(NodeConfig::test().with_deterministic_prevrandao_seed(seed)).await;
+    let provider = handle.http_provider();
+
+    for _ in 0..3 {
+        api.mine_one().await.unwrap();
+    }
+
+    for number in 1..=3u64 {
+        let block = provider.get_block(number.into()).await.unwrap().unwrap();
+        let expected = keccak256([seed.to_be_bytes(), number.to_be_bytes()].concat());
+        assert_eq!(block.header.mix_hash, expected);
+    }
+}
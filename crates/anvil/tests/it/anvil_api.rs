@@ -7,8 +7,10 @@
 };
 use alloy_consensus::{SignableTransaction, TxEip1559};
 use alloy_hardforks::EthereumHardfork;
-use alloy_network::{EthereumWallet, TransactionBuilder, TxSignerSync};
-use alloy_primitives::{Address, Bytes, TxKind, U256, address, fixed_bytes, utils::Unit};
+use alloy_network::{EthereumWallet, TransactionBuilder, TransactionResponse, TxSignerSync};
+use alloy_primitives::{
+    Address, B256, Bytes, TxKind, U256, address, b256, bytes, fixed_bytes, keccak256, utils::Unit,
+};
 use alloy_provider::{Provider, ext::TxPoolApi};
 use alloy_rpc_types::{
     BlockId, BlockNumberOrTag, TransactionRequest,
@@ -17,11 +19,15 @@
     },
 };
 use alloy_serde::WithOtherFields;
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
 use anvil::{
     NodeConfig,
     eth::{
         api::CLIENT_VERSION,
-        backend::mem::{EXECUTOR, P256_DELEGATION_CONTRACT, P256_DELEGATION_RUNTIME_CODE},
+        backend::mem::{
+            EXECUTOR, MAX_STORAGE_CURSORS, P256_DELEGATION_CONTRACT, P256_DELEGATION_RUNTIME_CODE,
+        },
     },
     spawn,
 };
@@ -30,8 +36,12 @@
         EthRequest,
         wallet::{Capabilities, DelegationCapability, WalletCapabilities},
     },
-    types::{ReorgOptions, TransactionData},
+    types::{
+        InvariantViolation, OverrideScope, ReorgKind, ReorgOptions, SlotHistoryEntry, StorageType,
+        StorageValue, TransactionData, TransactionDependency, TransactionHashOrRequest,
+    },
 };
+use futures::StreamExt;
 use revm::primitives::hardfork::SpecId;
 use std::{
     str::FromStr,
@@ -57,7 +67,7 @@ async fn can_set_block_gas_limit() {
     let block_gas_limit = U256::from(1337);
     assert!(api.evm_set_block_gas_limit(block_gas_limit).unwrap());
     // Mine a new block, and check the new block gas limit
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let latest_block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert_eq!(block_gas_limit.to::<u64>(), latest_block.header.gas_limit);
 }
@@ -470,7 +480,8 @@ async fn can_get_node_info() {
         },
     };
 
-    assert_eq!(node_info, expected_node_info);
+    assert_eq!(*node_info, expected_node_info);
+    assert!(node_info.chain_specific_env_changes.is_empty());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -593,16 +604,16 @@ async fn test_fork_revert_next_block_timestamp() {
     let (api, _handle) = spawn(fork_config()).await;
 
     // Mine a new block, and check the new block gas limit
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let latest_block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
 
     let state_snapshot = api.evm_snapshot().await.unwrap();
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     api.evm_revert(state_snapshot).await.unwrap();
     let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert_eq!(block, latest_block);
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
     assert!(block.header.timestamp >= latest_block.header.timestamp);
 }
@@ -615,11 +626,11 @@ async fn test_fork_revert_call_latest_block_timestamp() {
     let provider = handle.http_provider();
 
     // Mine a new block, and check the new block gas limit
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let latest_block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
 
     let state_snapshot = api.evm_snapshot().await.unwrap();
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     api.evm_revert(state_snapshot).await.unwrap();
 
     let multicall_contract =
@@ -753,8 +764,8 @@ async fn test_reorg() {
     let value = storage.getValue().call().await.unwrap();
     assert_eq!("initial value".to_string(), value);
 
-    api.mine_one().await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
+    api.mine_one().await.unwrap();
 
     // Test raw transaction data
     let mut tx = TxEip1559 {
@@ -802,7 +813,7 @@ async fn test_rollback() {
 
     // Mine 5 blocks
     for _ in 0..5 {
-        api.mine_one().await;
+        api.mine_one().await.unwrap();
     }
 
     // Get block 4 for later comparison
@@ -901,8 +912,8 @@ async fn test_arb_get_block() {
     let (api, _handle) = spawn(NodeConfig::test().with_chain_id(Some(421611u64))).await;
 
     // Mine two blocks
-    api.mine_one().await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
+    api.mine_one().await.unwrap();
 
     let best_number = api.block_number().unwrap().to::<u64>();
 
@@ -928,7 +939,7 @@ async fn test_mine_blk_with_prev_timestamp() {
     // mock timestamp
     api.evm_set_next_block_timestamp(init_timestamp).unwrap();
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
 
@@ -942,7 +953,7 @@ async fn test_mine_blk_with_prev_timestamp() {
     tokio::time::sleep(Duration::from_secs(1)).await;
 
     // Subsequent block should have a greater timestamp than previous block
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
 
@@ -968,7 +979,7 @@ async fn test_increase_time_by_zero() {
 
     let _ = api.evm_increase_time(U256::ZERO).await;
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
 
@@ -1056,6 +1067,22 @@ async fn test_mine_first_block_with_interval() {
     assert_eq!(second_block.header.timestamp, init_timestamp + 120);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_persist_impersonation_across_reset() {
+    let impersonate = Address::random();
+
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    api.anvil_impersonate_account(impersonate).await.unwrap();
+    assert!(api.accounts().unwrap().contains(&impersonate));
+    api.anvil_reset(None).await.unwrap();
+    assert!(api.accounts().unwrap().contains(&impersonate));
+
+    let (api, _handle) = spawn(NodeConfig::test().with_persist_impersonation(false)).await;
+    api.anvil_impersonate_account(impersonate).await.unwrap();
+    api.anvil_reset(None).await.unwrap();
+    assert!(!api.accounts().unwrap().contains(&impersonate));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_anvil_reset_non_fork() {
     let (api, handle) = spawn(NodeConfig::test()).await;
@@ -1071,7 +1098,7 @@ async fn test_anvil_reset_non_fork() {
 
     // Mine some blocks and make transactions
     for _ in 0..5 {
-        api.mine_one().await;
+        api.mine_one().await.unwrap();
     }
 
     // Send a transaction
@@ -1112,7 +1139,7 @@ async fn test_anvil_reset_non_fork() {
     assert_eq!(to_balance_after_reset, U256::ZERO);
 
     // Test we can continue mining after reset
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let new_block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
     assert_eq!(new_block.header.number, 1);
 }
@@ -1128,7 +1155,7 @@ async fn test_anvil_reset_fork_to_non_fork() {
 
     // Mine some blocks
     for _ in 0..3 {
-        api.mine_one().await;
+        api.mine_one().await.unwrap();
     }
 
     // Reset to non-fork mode
@@ -1143,7 +1170,1092 @@ async fn test_anvil_reset_fork_to_non_fork() {
     assert_eq!(block.header.number, 0);
 
     // Verify we can still mine blocks
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let new_block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
     assert_eq!(new_block.header.number, 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reorg_history_records_resets() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    assert!(api.anvil_reorg_history().unwrap().is_empty());
+
+    api.mine_one().await.unwrap();
+    api.mine_one().await.unwrap();
+    let first_head = api.backend.best_hash();
+    api.anvil_reset(None).await.unwrap();
+    let genesis_hash = api.backend.best_hash();
+
+    api.mine_one().await.unwrap();
+    let second_head = api.backend.best_hash();
+    api.anvil_reset(None).await.unwrap();
+
+    let history = api.anvil_reorg_history().unwrap();
+    assert_eq!(history.len(), 2);
+
+    assert_eq!(history[0].kind, ReorgKind::Reset);
+    assert_eq!(history[0].old_block_number, 2);
+    assert_eq!(history[0].old_block_hash, first_head);
+    assert_eq!(history[0].new_block_number, 0);
+    assert_eq!(history[0].new_block_hash, genesis_hash);
+
+    assert_eq!(history[1].kind, ReorgKind::Reset);
+    assert_eq!(history[1].old_block_number, 1);
+    assert_eq!(history[1].old_block_hash, second_head);
+    assert_eq!(history[1].new_block_number, 0);
+    assert!(history[0].timestamp <= history[1].timestamp);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_block_author() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+
+    let signer = Address::random();
+    api.anvil_set_coinbase(signer).await.unwrap();
+    api.mine_one().await.unwrap();
+
+    let author = api.anvil_block_author(BlockNumberOrTag::Latest).await.unwrap();
+    assert_eq!(author, Some(signer));
+
+    let author = api.anvil_block_author(BlockNumberOrTag::Number(100)).await.unwrap();
+    assert_eq!(author, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_block_author_of_sealed_blocks() {
+    let signer = PrivateKeySigner::random();
+    let (api, handle) = spawn(NodeConfig::test().with_block_signer(signer.clone())).await;
+    let provider = handle.http_provider();
+
+    let coinbase = Address::random();
+    api.anvil_set_coinbase(coinbase).await.unwrap();
+    api.mine_one().await.unwrap();
+
+    // the seal is the signature of the header with an empty 32 byte vanity as `extraData`
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    let extra_data = block.header.inner.extra_data.clone();
+    assert_eq!(extra_data.len(), 97);
+    let mut header = block.header.inner.clone();
+    header.extra_data = Bytes::from(vec![0; 32]);
+    assert_eq!(extra_data[..32], [0; 32]);
+    let seal = signer.sign_hash_sync(&header.hash_slow()).unwrap();
+    assert_eq!(extra_data[32..96], seal.as_bytes()[..64]);
+    assert_eq!(extra_data[96], seal.v() as u8);
+
+    let author = api.anvil_block_author(BlockNumberOrTag::Latest).await.unwrap();
+    assert_eq!(author, Some(signer.address()));
+
+    // the genesis block isn't sealed
+    let genesis = provider.get_block(BlockId::number(0)).await.unwrap().unwrap();
+    let author = api.anvil_block_author(BlockNumberOrTag::Earliest).await.unwrap();
+    assert_eq!(author, Some(genesis.header.beneficiary));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_gas_by_opcode() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // writes to 10 fresh storage slots in a loop
+    let target = Address::random();
+    api.anvil_set_code(target, bytes!("0x600a5b60019003600181558060025700")).await.unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(target);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let gas = api.anvil_gas_by_opcode(receipt.transaction_hash).unwrap();
+    assert!(gas.storage > 10 * 20_000);
+    assert!(gas.storage > gas.compute + gas.memory + gas.calls);
+    assert_eq!(gas.calls, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_gas_by_opcode_with_refunds_and_value_transfers() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // clears storage slot 0, which earns a refund
+    let target = Address::random();
+    api.anvil_set_code(target, bytes!("0x600060005500")).await.unwrap();
+    api.anvil_set_storage_at(target, U256::ZERO, B256::with_last_byte(1)).await.unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(target);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let gas = api.anvil_gas_by_opcode(receipt.transaction_hash).unwrap();
+    assert_eq!(gas.refunded, 4_800);
+    // cold SSTORE resetting a slot, minus the refund
+    assert_eq!(gas.storage, 5_000 - 4_800);
+    assert_eq!(21_000 + gas.storage + gas.memory + gas.calls + gas.compute, receipt.gas_used);
+
+    // sends 1 wei to a fresh account with a CALL forwarding no gas besides the stipend
+    let target = Address::random();
+    let callee = Address::random();
+    let code =
+        [&bytes!("0x6000600060006000600173")[..], callee.as_slice(), &bytes!("0x6000f100")[..]]
+            .concat();
+    api.anvil_set_code(target, code.into()).await.unwrap();
+    api.anvil_set_balance(target, U256::from(1)).await.unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(target);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    // cold account access, value transfer and account creation, the stipend isn't forwarded gas
+    let gas = api.anvil_gas_by_opcode(receipt.transaction_hash).unwrap();
+    assert_eq!(gas.calls, 2_600 + 9_000 + 25_000);
+    assert_eq!(gas.refunded, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_state_at_transaction() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let mut accounts = handle.dev_accounts();
+    let from = accounts.next().unwrap();
+    let to = accounts.next().unwrap();
+    let initial = provider.get_balance(to).await.unwrap();
+
+    api.anvil_set_auto_mine(false).await.unwrap();
+    let tx = TransactionRequest::default().with_from(from).with_to(to);
+    let first = provider
+        .send_transaction(WithOtherFields::new(tx.clone().with_value(U256::from(100))))
+        .await
+        .unwrap();
+    let second = provider
+        .send_transaction(WithOtherFields::new(tx.with_value(U256::from(200)).with_nonce(1)))
+        .await
+        .unwrap();
+    api.mine_one().await.unwrap();
+
+    let first = first.get_receipt().await.unwrap();
+    let second = second.get_receipt().await.unwrap();
+    assert_eq!(first.block_number, second.block_number);
+    let block = BlockNumberOrTag::Number(second.block_number.unwrap());
+
+    let before_first = api.anvil_state_at_transaction(block, 0usize.into(), to).await.unwrap();
+    assert_eq!(before_first.balance, initial);
+
+    assert_eq!(second.transaction_index, Some(1));
+    let before_second = api.anvil_state_at_transaction(block, 1usize.into(), to).await.unwrap();
+    assert_eq!(before_second.balance, initial + U256::from(100));
+
+    let sender = api.anvil_state_at_transaction(block, 1usize.into(), from).await.unwrap();
+    assert_eq!(sender.nonce, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_tx_count_in_range() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let mut accounts = handle.dev_accounts();
+    let from = accounts.next().unwrap();
+    let to = accounts.next().unwrap();
+
+    api.anvil_set_auto_mine(false).await.unwrap();
+    let mut nonce = 0;
+    for count in [3, 0, 2] {
+        for _ in 0..count {
+            let tx = TransactionRequest::default().with_from(from).with_to(to).with_nonce(nonce);
+            provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+            nonce += 1;
+        }
+        api.mine_one().await.unwrap();
+    }
+
+    let count = |from: u64, to: BlockNumberOrTag| {
+        api.anvil_tx_count_in_range(BlockNumberOrTag::Number(from), to)
+    };
+    assert_eq!(count(0, BlockNumberOrTag::Latest).await.unwrap(), U256::from(5));
+    assert_eq!(count(1, BlockNumberOrTag::Number(1)).await.unwrap(), U256::from(3));
+    assert_eq!(count(2, BlockNumberOrTag::Number(3)).await.unwrap(), U256::from(2));
+    assert!(count(3, BlockNumberOrTag::Number(1)).await.is_err());
+    assert!(count(1, BlockNumberOrTag::Number(4)).await.is_err());
+
+    let (api, _handle) = spawn(NodeConfig::test().with_max_log_block_range(2)).await;
+    api.evm_mine(None).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    let err = api
+        .anvil_tx_count_in_range(BlockNumberOrTag::Number(0), BlockNumberOrTag::Latest)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("exceeds max block range 2"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_code_at_block() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+
+    let multicall = Multicall::deploy(&provider).await.unwrap();
+    let address = *multicall.address();
+    let original = provider.get_code_at(address).await.unwrap();
+    let deployed_at = provider.get_block_number().await.unwrap();
+
+    let new_code = bytes!("0x600160005260206000f3");
+    api.anvil_set_code(address, new_code.clone()).await.unwrap();
+
+    // the change isn't part of a mined block yet
+    let code = api.anvil_code_at(address, BlockNumberOrTag::Number(deployed_at)).await.unwrap();
+    assert_eq!(code, original);
+
+    api.mine_one().await.unwrap();
+
+    let code = api.anvil_code_at(address, BlockNumberOrTag::Number(deployed_at)).await.unwrap();
+    assert_eq!(code, original);
+    let code = api.anvil_code_at(address, BlockNumberOrTag::Latest).await.unwrap();
+    assert_eq!(code, new_code);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn code_at_fails_for_pruned_state() {
+    let (api, handle) = spawn(NodeConfig::test().set_pruned_history(Some(Some(1)))).await;
+    let provider = handle.http_provider();
+
+    let multicall = Multicall::deploy(&provider).await.unwrap();
+    let address = *multicall.address();
+    let deployed_at = provider.get_block_number().await.unwrap();
+    api.anvil_set_code(address, bytes!("0x600160005260206000f3")).await.unwrap();
+    api.anvil_mine(Some(U256::from(5)), None).await.unwrap();
+
+    // the current state isn't served in place of the pruned one
+    let err = api.anvil_code_at(address, BlockNumberOrTag::Number(deployed_at)).await.unwrap_err();
+    assert!(err.to_string().contains("Required data unavailable"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_block_rlp() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+
+    let tx = TransactionRequest::default()
+        .with_from(accounts[0].address())
+        .with_to(accounts[1].address())
+        .with_value(U256::from(1337));
+    let tx = WithOtherFields::new(tx);
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    let number = receipt.block_number.unwrap();
+
+    let rlp = api.anvil_block_rlp(BlockNumberOrTag::Number(number)).await.unwrap().unwrap();
+    let decoded: alloy_consensus::Block<alloy_consensus::TxEnvelope> =
+        alloy_rlp::Decodable::decode(&mut rlp.as_ref()).unwrap();
+
+    let block = provider.get_block(number.into()).await.unwrap().unwrap();
+    assert_eq!(decoded.header.hash_slow(), block.header.hash);
+    assert_eq!(decoded.body.transactions.len(), 1);
+    assert_eq!(*decoded.body.transactions[0].tx_hash(), receipt.transaction_hash);
+
+    assert!(api.anvil_block_rlp(BlockNumberOrTag::Number(number + 1)).await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_txs_by_selector() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+
+    let mut hashes = Vec::new();
+    for input in [bytes!("0xa9059cbb0000"), bytes!("0x095ea7b30000")] {
+        let tx = TransactionRequest::default()
+            .with_from(accounts[0].address())
+            .with_to(accounts[1].address())
+            .with_input(input);
+        let tx = WithOtherFields::new(tx);
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        hashes.push(receipt.transaction_hash);
+    }
+
+    let txs = api
+        .anvil_txs_by_selector(
+            fixed_bytes!("a9059cbb"),
+            BlockNumberOrTag::Earliest,
+            BlockNumberOrTag::Latest,
+        )
+        .await
+        .unwrap();
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].tx_hash(), hashes[0]);
+
+    let txs = api
+        .anvil_txs_by_selector(
+            fixed_bytes!("23b872dd"),
+            BlockNumberOrTag::Earliest,
+            BlockNumberOrTag::Latest,
+        )
+        .await
+        .unwrap();
+    assert!(txs.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_logs_by_topic0() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_wallets().next().unwrap().address();
+
+    // emits a single `LOG1` with the calldata as its topic
+    let emitter = Address::random();
+    api.anvil_set_code(emitter, bytes!("60003560006000a100")).await.unwrap();
+
+    let transfer = keccak256("Transfer(address,address,uint256)");
+    let approval = keccak256("Approval(address,address,uint256)");
+    let mut hashes = Vec::new();
+    for topic in [transfer, approval, transfer] {
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(emitter)
+            .with_input(Bytes::from(topic));
+        let tx = WithOtherFields::new(tx);
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        hashes.push(receipt.transaction_hash);
+    }
+
+    let logs = api
+        .anvil_logs_by_topic0(transfer, BlockNumberOrTag::Earliest, BlockNumberOrTag::Latest)
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 2);
+    assert!(logs.iter().all(|log| log.topics()[0] == transfer));
+    assert_eq!(logs[0].transaction_hash, Some(hashes[0]));
+    assert_eq!(logs[1].transaction_hash, Some(hashes[2]));
+
+    let logs = api
+        .anvil_logs_by_topic0(approval, BlockNumberOrTag::Earliest, BlockNumberOrTag::Latest)
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].transaction_hash, Some(hashes[1]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_storage_changes_since() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    let address = Address::random();
+
+    let initial = api.anvil_storage_changes_since(address, None).await.unwrap();
+    assert!(initial.changes.is_empty());
+
+    let value = fixed_bytes!("0000000000000000000000000000000000000000000000000000000000000001");
+    api.anvil_set_storage_at(address, U256::from(1), value).await.unwrap();
+    api.mine_one().await.unwrap();
+
+    let first = api.anvil_storage_changes_since(address, Some(initial.cursor)).await.unwrap();
+    assert_eq!(first.changes.len(), 1);
+    assert_eq!(first.changes[&B256::with_last_byte(1)], value);
+
+    api.anvil_set_storage_at(address, U256::from(2), value).await.unwrap();
+    api.mine_one().await.unwrap();
+
+    let second = api.anvil_storage_changes_since(address, Some(first.cursor)).await.unwrap();
+    assert_eq!(second.changes.len(), 1);
+    assert_eq!(second.changes[&B256::with_last_byte(2)], value);
+
+    let third = api.anvil_storage_changes_since(address, Some(second.cursor)).await.unwrap();
+    assert!(third.changes.is_empty());
+
+    // cursors are consumed
+    assert!(api.anvil_storage_changes_since(address, Some(first.cursor)).await.is_err());
+
+    // unconsumed cursors are evicted oldest first
+    for _ in 0..MAX_STORAGE_CURSORS {
+        api.anvil_storage_changes_since(address, None).await.unwrap();
+    }
+    assert!(api.anvil_storage_changes_since(address, Some(third.cursor)).await.is_err());
+    let latest = api.anvil_storage_changes_since(address, None).await.unwrap();
+    assert!(api.anvil_storage_changes_since(address, Some(latest.cursor)).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_check_bloom_matches() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // PUSH1 0x2a PUSH1 0 PUSH1 0 LOG1
+    let emitter = Address::random();
+    api.anvil_set_code(emitter, bytes!("0x602a60006000a1")).await.unwrap();
+    let tx = TransactionRequest::default().with_from(from).with_to(emitter);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert_eq!(receipt.inner.inner.logs().len(), 1);
+    let block = BlockNumberOrTag::Number(receipt.block_number.unwrap());
+    let topic = B256::with_last_byte(0x2a);
+
+    assert!(api.anvil_bloom_matches(block, Some(emitter), vec![]).await.unwrap());
+    assert!(api.anvil_bloom_matches(block, Some(emitter), vec![topic]).await.unwrap());
+    assert!(api.anvil_bloom_matches(block, None, vec![topic]).await.unwrap());
+    assert!(!api.anvil_bloom_matches(block, Some(Address::random()), vec![]).await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_block_time_stats() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    assert!(api.anvil_block_time_stats(5).is_err());
+
+    api.mine_one().await.unwrap();
+    api.evm_set_block_timestamp_interval(12).unwrap();
+    for _ in 0..4 {
+        api.mine_one().await.unwrap();
+    }
+
+    let stats = api.anvil_block_time_stats(4).unwrap();
+    assert_eq!(stats.intervals, 4);
+    assert_eq!(stats.min, 12);
+    assert_eq!(stats.max, 12);
+    assert_eq!(stats.avg, 12.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_halt_mining_on_invariant_violation() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+
+    // returns `balance(watched) == 0`
+    let watched = address!("0x00000000000000000000000000000000000000ab");
+    let invariant = address!("0x00000000000000000000000000000000000000cd");
+    let code =
+        [&[0x73][..], watched.as_slice(), &bytes!("0x3115600052602060006000f3")[..]].concat();
+    api.anvil_set_code(invariant, code.into()).await.unwrap();
+    let _: () = provider
+        .raw_request("anvil_setInvariantCheck".into(), (invariant, fixed_bytes!("12345678")))
+        .await
+        .unwrap();
+
+    api.mine_one().await.unwrap();
+    let violation: Option<InvariantViolation> =
+        provider.raw_request("anvil_invariantViolation".into(), ()).await.unwrap();
+    assert!(violation.is_none());
+
+    let mut violations = api.invariant_violation_notifications();
+    let tx = TransactionRequest::default()
+        .with_from(accounts[0].address())
+        .with_to(watched)
+        .with_value(U256::from(1));
+    let tx = WithOtherFields::new(tx);
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    let block_number = receipt.block_number.unwrap();
+
+    let violation: Option<InvariantViolation> =
+        provider.raw_request("anvil_invariantViolation".into(), ()).await.unwrap();
+    let violation = violation.unwrap();
+    assert_eq!(violation.block_number, block_number);
+    assert_eq!(violation.block_hash, receipt.block_hash.unwrap());
+    assert_eq!(violation.reason, "returned false");
+    assert_eq!(violations.next().await.unwrap(), violation);
+
+    // automine no longer produces blocks
+    let tx = TransactionRequest::default()
+        .with_from(accounts[0].address())
+        .with_to(accounts[1].address())
+        .with_value(U256::from(1));
+    let pending = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(provider.get_block_number().await.unwrap(), block_number);
+
+    // neither does mining manually
+    let err = api.evm_mine(None).await.unwrap_err();
+    assert!(err.to_string().contains("mining is halted"), "{err}");
+    assert!(api.anvil_mine(Some(U256::from(2)), None).await.is_err());
+    assert!(api.anvil_mine_to_target_gas(30_000_000).await.is_err());
+    assert_eq!(provider.get_block_number().await.unwrap(), block_number);
+
+    // removing the check resumes mining
+    let _: () = provider.raw_request("anvil_clearInvariantCheck".into(), ()).await.unwrap();
+    let receipt = pending.get_receipt().await.unwrap();
+    assert_eq!(receipt.block_number.unwrap(), block_number + 1);
+    let violation: Option<InvariantViolation> =
+        provider.raw_request("anvil_invariantViolation".into(), ()).await.unwrap();
+    assert!(violation.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_decode_storage() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    let contract = address!("0x00000000000000000000000000000000000000cd");
+    let owner = address!("0x00000000000000000000000000000000000000ab");
+
+    api.anvil_set_storage_at(contract, U256::ZERO, B256::from(U256::from(1337))).await.unwrap();
+    api.anvil_set_storage_at(contract, U256::from(1), owner.into_word()).await.unwrap();
+
+    let values = api
+        .anvil_decode_storage(
+            contract,
+            vec![
+                (B256::ZERO, StorageType::Uint256),
+                (B256::from(U256::from(1)), StorageType::Address),
+                (B256::from(U256::from(1)), StorageType::Bool),
+                (B256::from(U256::from(2)), StorageType::Bool),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        values,
+        vec![
+            StorageValue::Uint256(U256::from(1337)),
+            StorageValue::Address(owner),
+            StorageValue::Bool(true),
+            StorageValue::Bool(false),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_slot_history() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    let contract = Address::random();
+
+    for value in [1u64, 2] {
+        api.anvil_set_storage_at(contract, U256::ZERO, B256::from(U256::from(value)))
+            .await
+            .unwrap();
+        api.mine_one().await.unwrap();
+    }
+    api.mine_one().await.unwrap();
+
+    let history = api
+        .anvil_slot_history(
+            contract,
+            U256::ZERO,
+            BlockNumberOrTag::Number(1),
+            BlockNumberOrTag::Latest,
+        )
+        .await
+        .unwrap();
+    let expected = [(1, 1u64), (2, 2), (3, 2)]
+        .map(|(block_number, value)| SlotHistoryEntry {
+            block_number,
+            value: B256::from(U256::from(value)),
+        })
+        .to_vec();
+    assert_eq!(history, expected);
+
+    assert!(
+        api.anvil_slot_history(
+            contract,
+            U256::ZERO,
+            BlockNumberOrTag::Latest,
+            BlockNumberOrTag::Number(1),
+        )
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slot_history_range_is_capped() {
+    let (api, _handle) = spawn(NodeConfig::test().with_max_log_block_range(2)).await;
+    api.anvil_mine(Some(U256::from(3)), None).await.unwrap();
+
+    let history = api
+        .anvil_slot_history(
+            Address::random(),
+            U256::ZERO,
+            BlockNumberOrTag::Number(1),
+            BlockNumberOrTag::Number(2),
+        )
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+
+    let err = api
+        .anvil_slot_history(
+            Address::random(),
+            U256::ZERO,
+            BlockNumberOrTag::Number(1),
+            BlockNumberOrTag::Number(3),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("query exceeds max block range 2"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn difficulty_history_range_is_capped() {
+    let (api, _handle) = spawn(NodeConfig::test().with_max_log_block_range(2)).await;
+    api.anvil_mine(Some(U256::from(3)), None).await.unwrap();
+
+    let history = api
+        .anvil_difficulty_history(BlockNumberOrTag::Number(1), BlockNumberOrTag::Number(2))
+        .await
+        .unwrap();
+    assert_eq!(history.iter().map(|entry| entry.number).collect::<Vec<_>>(), vec![1, 2]);
+
+    let err = api
+        .anvil_difficulty_history(BlockNumberOrTag::Number(0), BlockNumberOrTag::Number(3))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("query exceeds max block range 2"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_block_gas_limit() {
+    let (api, _handle) = spawn(NodeConfig::test().with_gas_limit(Some(10_000_000))).await;
+
+    api.mine_one().await.unwrap();
+    api.evm_set_block_gas_limit(U256::from(20_000_000)).unwrap();
+    api.mine_one().await.unwrap();
+
+    let limit = api.anvil_block_gas_limit(BlockNumberOrTag::Number(1)).await.unwrap();
+    assert_eq!(limit, 10_000_000);
+    let limit = api.anvil_block_gas_limit(BlockNumberOrTag::Latest).await.unwrap();
+    assert_eq!(limit, 20_000_000);
+    assert!(api.anvil_block_gas_limit(BlockNumberOrTag::Number(100)).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_tx_index() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    let mut hashes = Vec::new();
+    for _ in 0..2 {
+        let tx = TransactionRequest::default().with_from(accounts[0]).with_to(accounts[1]);
+        let pending = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+        hashes.push(*pending.tx_hash());
+    }
+    assert!(api.anvil_tx_index(hashes[0]).await.unwrap().is_none());
+
+    api.mine_one().await.unwrap();
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    for (index, hash) in hashes.into_iter().enumerate() {
+        let position = api.anvil_tx_index(hash).await.unwrap().unwrap();
+        assert_eq!(position.block_number, 1);
+        assert_eq!(position.block_hash, block.header.hash);
+        assert_eq!(position.index, index as u64);
+    }
+    assert!(api.anvil_tx_index(B256::random()).await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_wallet_info() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+
+    let wallets = api.anvil_wallet_info().await.unwrap();
+    assert_eq!(wallets.len(), handle.dev_accounts().count());
+    assert_eq!(wallets[0].address, address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+    assert_eq!(wallets[0].derivation_path.as_deref(), Some("m/44'/60'/0'/0/0"));
+    assert_eq!(wallets[1].derivation_path.as_deref(), Some("m/44'/60'/0'/0/1"));
+    assert!(wallets.iter().all(|wallet| wallet.private_key.is_none()));
+
+    let (api, _handle) = spawn(NodeConfig::test().with_expose_private_keys(true)).await;
+    let wallets = api.anvil_wallet_info().await.unwrap();
+    assert_eq!(
+        wallets[0].private_key,
+        Some(b256!("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_mine_to_target_gas() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    for from in &accounts[..3] {
+        let tx = TransactionRequest::default()
+            .with_from(*from)
+            .with_to(Address::random())
+            .with_gas_limit(21_000);
+        provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    }
+
+    let target_gas = 50_000;
+    let number = api.anvil_mine_to_target_gas(target_gas).await.unwrap();
+    let block = provider.get_block(BlockId::number(number)).await.unwrap().unwrap();
+    assert_eq!(block.transactions.len(), 2);
+    assert!(block.header.gas_used <= target_gas);
+
+    let status = provider.txpool_status().await.unwrap();
+    assert_eq!(status.pending, 1);
+
+    // the largest target fits everything without overflowing
+    let number = api.anvil_mine_to_target_gas(u64::MAX).await.unwrap();
+    let block = provider.get_block(BlockId::number(number)).await.unwrap().unwrap();
+    assert_eq!(block.transactions.len(), 1);
+    assert_eq!(provider.txpool_status().await.unwrap().pending, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_mine_to_target_gas_with_max_txs_per_block() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_txs_per_block(2)).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    for from in &accounts[..3] {
+        let tx = TransactionRequest::default()
+            .with_from(*from)
+            .with_to(Address::random())
+            .with_gas_limit(21_000);
+        provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    }
+
+    let number = api.anvil_mine_to_target_gas(u64::MAX).await.unwrap();
+    let block = provider.get_block(BlockId::number(number)).await.unwrap().unwrap();
+    assert_eq!(block.transactions.len(), 2);
+    assert_eq!(block.header.gas_used, 42_000);
+    assert_eq!(provider.txpool_status().await.unwrap().pending, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_tx_dependencies() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+
+    // increments slot 0 on every call
+    let counter = Address::random();
+    api.anvil_set_code(counter, bytes!("0x60005460010160005500")).await.unwrap();
+
+    let tx = TransactionRequest::default().with_from(accounts[0]).with_to(counter);
+    let mined = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let transactions = vec![
+        TransactionHashOrRequest::Hash(mined.transaction_hash),
+        TransactionHashOrRequest::Request(
+            TransactionRequest::default().with_from(accounts[1]).with_to(counter),
+        ),
+        TransactionHashOrRequest::Request(
+            TransactionRequest::default()
+                .with_from(accounts[2])
+                .with_to(Address::random())
+                .with_value(U256::from(1)),
+        ),
+        TransactionHashOrRequest::Request(
+            TransactionRequest::default()
+                .with_from(accounts[3])
+                .with_to(Address::random())
+                .with_value(U256::from(1)),
+        ),
+    ];
+    let dependencies = api.anvil_tx_dependencies(transactions).await.unwrap();
+    assert_eq!(dependencies, vec![TransactionDependency { index: 1, depends_on: 0 }]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_scope_overrides_to_block() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+
+    let address = Address::random();
+    let slot = U256::ZERO;
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(1)).await.unwrap();
+
+    api.anvil_set_override_scope(OverrideScope::Block).await.unwrap();
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(2)).await.unwrap();
+    assert_eq!(provider.get_storage_at(address, slot).await.unwrap(), U256::from(2));
+
+    api.evm_mine(None).await.unwrap();
+    assert_eq!(provider.get_storage_at(address, slot).await.unwrap(), U256::from(1));
+    // the state stored for the mined block doesn't include the override either
+    let mined = provider.get_block_number().await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    let value = provider.get_storage_at(address, slot).block_id(mined.into()).await.unwrap();
+    assert_eq!(value, U256::from(1));
+
+    // pending overrides are discarded by reverting to a snapshot
+    let snapshot = api.evm_snapshot().await.unwrap();
+    api.anvil_set_override_scope(OverrideScope::Permanent).await.unwrap();
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(4)).await.unwrap();
+    api.anvil_set_override_scope(OverrideScope::Block).await.unwrap();
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(5)).await.unwrap();
+    assert!(api.evm_revert(snapshot).await.unwrap());
+    api.evm_mine(None).await.unwrap();
+    assert_eq!(provider.get_storage_at(address, slot).await.unwrap(), U256::from(1));
+
+    // and by resetting
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(6)).await.unwrap();
+    api.anvil_reset(None).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    assert_eq!(provider.get_storage_at(address, slot).await.unwrap(), U256::ZERO);
+
+    // permanent overrides survive mining
+    api.anvil_set_override_scope(OverrideScope::Permanent).await.unwrap();
+    api.anvil_set_storage_at(address, slot, B256::with_last_byte(3)).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    assert_eq!(provider.get_storage_at(address, slot).await.unwrap(), U256::from(3));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_creation_code() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+
+    let greeter = Greeter::deploy(&provider, "Hello World!".to_string()).await.unwrap();
+    let address = *greeter.address();
+
+    let creation_code = api.anvil_creation_code(address).await.unwrap().unwrap();
+    assert!(creation_code.starts_with(&Greeter::BYTECODE));
+
+    // running the creation code yields the deployed runtime code
+    let tx = TransactionRequest::default().with_deploy_code(creation_code);
+    let runtime_code = provider.call(WithOtherFields::new(tx)).await.unwrap();
+    assert_eq!(runtime_code, provider.get_code_at(address).await.unwrap());
+
+    assert!(api.anvil_creation_code(Address::random()).await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_balance_at_timestamp() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    let address = Address::random();
+    let start = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() + 100;
+
+    api.anvil_set_balance(address, U256::from(1)).await.unwrap();
+    api.evm_set_next_block_timestamp(start).unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    api.anvil_set_balance(address, U256::from(2)).await.unwrap();
+    api.evm_set_next_block_timestamp(start + 100).unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    assert_eq!(api.anvil_balance_at_timestamp(address, start).await.unwrap(), U256::from(1));
+    assert_eq!(api.anvil_balance_at_timestamp(address, start + 50).await.unwrap(), U256::from(1));
+    assert_eq!(api.anvil_balance_at_timestamp(address, start + 100).await.unwrap(), U256::from(2));
+    assert_eq!(api.anvil_balance_at_timestamp(address, start + 1000).await.unwrap(), U256::from(2));
+
+    // there is no block before genesis
+    assert!(api.anvil_balance_at_timestamp(address, 0).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_memory_usage() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+
+    let initial = api.anvil_memory_usage().await.unwrap();
+    assert!(initial.accounts > 0);
+    assert_eq!(initial.state_snapshots, 0);
+
+    for _ in 0..3 {
+        api.evm_snapshot().await.unwrap();
+    }
+    let usage = api.anvil_memory_usage().await.unwrap();
+    assert_eq!(usage.state_snapshots, 3);
+    assert!(usage.approximate_bytes > initial.approximate_bytes);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_auto_revert_with_guard() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let address = Address::random();
+
+    let snapshot = api.evm_snapshot().await.unwrap();
+    {
+        let _guard = api.with_auto_revert_guard(snapshot);
+        api.anvil_set_balance(address, U256::from(1)).await.unwrap();
+        api.evm_mine(None).await.unwrap();
+        assert_eq!(provider.get_balance(address).await.unwrap(), U256::from(1));
+        assert_eq!(provider.get_block_number().await.unwrap(), 1);
+    }
+
+    assert_eq!(provider.get_balance(address).await.unwrap(), U256::ZERO);
+    assert_eq!(provider.get_block_number().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn can_auto_revert_on_current_thread_runtime() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let address = Address::random();
+
+    // reverting explicitly applies right away
+    let guard = api.with_auto_revert_guard(api.evm_snapshot().await.unwrap());
+    api.anvil_set_balance(address, U256::from(1)).await.unwrap();
+    assert!(guard.revert().await.unwrap());
+    assert_eq!(provider.get_balance(address).await.unwrap(), U256::ZERO);
+
+    // dropping doesn't panic, the revert is applied once the runtime gets to it
+    let guard = api.with_auto_revert_guard(api.evm_snapshot().await.unwrap());
+    api.anvil_set_balance(address, U256::from(1)).await.unwrap();
+    drop(guard);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(provider.get_balance(address).await.unwrap(), U256::ZERO);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_deploy_with_dev_signers() {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+
+    let signers = handle.dev_signers().collect::<Vec<_>>();
+    assert_eq!(signers.len(), handle.dev_accounts().count());
+
+    let signer = signers.into_iter().next().unwrap();
+    let provider = http_provider_with_signer(&handle.http_endpoint(), signer);
+    let greeter = Greeter::deploy(&provider, "Hello World!".to_string()).await.unwrap();
+    assert_eq!(greeter.greet().call().await.unwrap(), "Hello World!");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_limit_state_accounts() {
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    let initial = api.anvil_memory_usage().await.unwrap().accounts;
+
+    let (api, handle) = spawn(NodeConfig::test().with_max_state_accounts(initial + 3)).await;
+    let provider = handle.http_provider();
+
+    // forwards the call value to `target`
+    let forwarder = address!("0x00000000000000000000000000000000000000cd");
+    let target = address!("0x00000000000000000000000000000000000000ef");
+    let code = [&bytes!("0x60006000600060003473")[..], target.as_slice(), &bytes!("0x5af100")[..]]
+        .concat();
+    api.anvil_set_code(forwarder, code.into()).await.unwrap();
+    for _ in 0..2 {
+        api.anvil_set_balance(Address::random(), U256::from(1)).await.unwrap();
+    }
+
+    let err = api.anvil_set_balance(Address::random(), U256::from(1)).await.unwrap_err();
+    assert!(err.to_string().contains("state account limit"), "{err}");
+    let err = api.anvil_add_balance(Address::random(), U256::from(1)).await.unwrap_err();
+    assert!(err.to_string().contains("state account limit"), "{err}");
+
+    // transfers to new accounts are rejected as well, existing accounts can still be modified
+    let from = handle.dev_accounts().next().unwrap();
+    let tx = TransactionRequest::default().with_from(from).with_to(Address::random());
+    let err = api.send_transaction(WithOtherFields::new(tx)).await.unwrap_err();
+    assert!(err.to_string().contains("state account limit"), "{err}");
+
+    // accounts created while executing a transaction count against the limit when it's mined
+    let tx =
+        TransactionRequest::default().with_from(from).with_to(forwarder).with_value(U256::from(1));
+    let hash = api.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    assert!(provider.get_transaction_receipt(hash).await.unwrap().is_none());
+    assert_eq!(provider.get_balance(target).await.unwrap(), U256::ZERO);
+
+    api.anvil_set_balance(from, U256::from(1)).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_pay_block_and_uncle_rewards() {
+    let (api, handle) = spawn(
+        NodeConfig::test()
+            .with_hardfork(Some(EthereumHardfork::Byzantium.into()))
+            .with_block_rewards(true),
+    )
+    .await;
+    let provider = handle.http_provider();
+
+    let coinbase = Address::random();
+    let uncle_miner = Address::random();
+    api.anvil_set_coinbase(coinbase).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    // byzantium pays a 3 ETH block reward
+    let block_reward = Unit::ETHER.wei() * U256::from(3);
+    assert_eq!(provider.get_balance(coinbase).await.unwrap(), block_reward * U256::from(2));
+
+    // include an uncle of height 1 in block 3
+    let uncle_hash = api.anvil_inject_uncle(uncle_miner, 1).unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    let block = provider.get_block(BlockId::number(3)).await.unwrap().unwrap();
+    assert_eq!(block.uncles, vec![uncle_hash]);
+    let uncle = provider.get_uncle(BlockId::number(3), 0).await.unwrap().unwrap();
+    assert_eq!(uncle.header.hash, uncle_hash);
+    assert_eq!(uncle.header.beneficiary, uncle_miner);
+
+    // the uncle miner is paid `(1 + 8 - 3) * block_reward / 8`
+    let uncle_reward = U256::from(6) * block_reward / U256::from(8);
+    assert_eq!(provider.get_balance(uncle_miner).await.unwrap(), uncle_reward);
+    assert_eq!(
+        api.anvil_uncle_reward(BlockNumberOrTag::Number(3), 0usize.into()).await.unwrap(),
+        Some(uncle_reward)
+    );
+
+    // the miner is paid an additional 1/32 of the block reward per included uncle
+    assert_eq!(
+        provider.get_balance(coinbase).await.unwrap(),
+        block_reward * U256::from(3) + block_reward / U256::from(32)
+    );
+
+    // uncles must be among the 6 ancestors of the next block
+    assert!(api.anvil_inject_uncle(uncle_miner, 4).is_err());
+    assert!(api.anvil_inject_uncle(uncle_miner, 0).is_ok());
+    assert!(api.anvil_inject_uncle(uncle_miner, 3).is_ok());
+
+    // queued uncles are discarded when reverting to a snapshot
+    let snapshot = api.evm_snapshot().await.unwrap();
+    api.evm_mine(None).await.unwrap();
+    api.anvil_inject_uncle(uncle_miner, 4).unwrap();
+    assert!(api.evm_revert(snapshot).await.unwrap());
+    api.evm_mine(None).await.unwrap();
+    let block = provider.get_block(BlockId::number(4)).await.unwrap().unwrap();
+    assert!(block.uncles.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn does_not_pay_block_rewards_by_default() {
+    let (api, handle) =
+        spawn(NodeConfig::test().with_hardfork(Some(EthereumHardfork::Byzantium.into()))).await;
+    let provider = handle.http_provider();
+
+    let coinbase = Address::random();
+    api.anvil_set_coinbase(coinbase).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    assert_eq!(provider.get_balance(coinbase).await.unwrap(), U256::ZERO);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reorg_ignores_max_txs_per_block() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_txs_per_block(1)).await;
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    api.evm_mine(None).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    // the transactions of a reorged block are all mined into that block
+    let txs = (0..3)
+        .map(|i| {
+            let tx = TransactionRequest::default()
+                .from(accounts[0].address())
+                .to(accounts[1].address())
+                .value(U256::from(i));
+            (TransactionData::JSON(tx), 0)
+        })
+        .collect();
+    api.anvil_reorg(ReorgOptions { depth: 1, tx_block_pairs: txs }).await.unwrap();
+
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    assert_eq!(block.header.number, 2);
+    assert_eq!(block.transactions.len(), 3);
+}
@@ -7,15 +7,16 @@
 use alloy_consensus::{SignableTransaction, Transaction, TxEip1559};
 use alloy_network::{EthereumWallet, TransactionBuilder, TxSignerSync};
 use alloy_primitives::{
-    Address, B256, ChainId, U256, b256, bytes,
+    Address, B256, ChainId, U256, b256, bytes, keccak256,
     map::{AddressHashMap, B256HashMap, HashMap},
 };
 use alloy_provider::Provider;
 use alloy_rpc_types::{
-    BlockId, BlockNumberOrTag, BlockTransactions, request::TransactionRequest,
+    BlockId, BlockNumberOrTag, BlockTransactions, Index, request::TransactionRequest,
     state::AccountOverride,
 };
 use alloy_serde::WithOtherFields;
+use alloy_sol_types::SolCall;
 use anvil::{CHAIN_ID, EthereumHardfork, NodeConfig, eth::api::CLIENT_VERSION, spawn};
 use foundry_test_utils::rpc;
 use futures::join;
@@ -46,6 +47,28 @@ async fn can_dev_get_balance() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_balances() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+
+    let tx =
+        TransactionRequest::default().from(accounts[0]).to(accounts[1]).value(U256::from(1337));
+    provider.send_transaction(WithOtherFields::new(tx)).await.unwrap().get_receipt().await.unwrap();
+
+    let balances = api.anvil_balances(accounts.clone(), None).await.unwrap();
+    assert_eq!(balances.len(), accounts.len());
+    for (account, balance) in accounts.iter().zip(balances) {
+        assert_eq!(balance, provider.get_balance(*account).await.unwrap());
+    }
+
+    // balances before the transaction
+    let balances =
+        api.anvil_balances(accounts[..2].to_vec(), Some(BlockId::number(0))).await.unwrap();
+    assert_eq!(balances, vec![handle.genesis_balance(); 2]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_get_price() {
     let (_api, handle) = spawn(NodeConfig::test()).await;
@@ -466,3 +489,228 @@ async fn can_get_code_by_hash() {
     let code = api.debug_code_by_hash(code_hash, None).await.unwrap();
     assert_eq!(&code.unwrap(), foundry_evm::constants::DEFAULT_CREATE2_DEPLOYER_RUNTIME_CODE);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_deny_calls_to_addresses() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let wallet = handle.dev_wallets().next().unwrap();
+    let account = wallet.address();
+    let signer: EthereumWallet = wallet.into();
+
+    let provider = http_provider_with_signer(&handle.http_endpoint(), signer);
+
+    let multicall = Multicall::deploy(&provider).await.unwrap();
+    let forbidden = Multicall::deploy(&provider).await.unwrap();
+
+    let calls = vec![Multicall::Call {
+        target: *forbidden.address(),
+        callData: Multicall::getEthBalanceCall { addr: account }.abi_encode().into(),
+    }];
+    multicall.aggregate(calls.clone()).call().await.unwrap();
+
+    api.anvil_set_call_denylist(vec![*forbidden.address()]).unwrap();
+    let err = multicall.aggregate(calls.clone()).call().await.unwrap_err();
+    assert!(err.to_string().contains("execution reverted"), "{err}");
+
+    let err = multicall.aggregate(calls.clone()).send().await.unwrap_err();
+    assert!(err.to_string().contains("execution reverted"), "{err}");
+
+    api.anvil_set_call_denylist(vec![]).unwrap();
+    multicall.aggregate(calls).call().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_limit_returndata_size() {
+    let (_api, handle) = spawn(NodeConfig::test().with_max_returndata_size(1024)).await;
+    let wallet = handle.dev_wallets().next().unwrap();
+    let account = wallet.address();
+    let signer: EthereumWallet = wallet.into();
+
+    let provider = http_provider_with_signer(&handle.http_endpoint(), signer);
+
+    let multicall = Multicall::deploy(&provider).await.unwrap();
+
+    let call = Multicall::Call {
+        target: *multicall.address(),
+        callData: Multicall::getEthBalanceCall { addr: account }.abi_encode().into(),
+    };
+    multicall.aggregate(vec![call.clone()]).call().await.unwrap();
+
+    // each result takes up 96 bytes of the returned array
+    let calls = vec![call; 32];
+    let err = multicall.aggregate(calls.clone()).call().await.unwrap_err();
+    assert!(err.to_string().contains("exceeds limit of 1024 bytes"), "{err}");
+
+    let err = multicall.aggregate(calls).send().await.unwrap_err();
+    assert!(err.to_string().contains("execution reverted"), "{err}");
+
+    // deployed code isn't returndata, returns 2048 zero bytes as the runtime code
+    let tx =
+        TransactionRequest::default().with_from(account).with_deploy_code(bytes!("0x6108006000f3"));
+    let receipt = provider.send_transaction(tx.into()).await.unwrap().get_receipt().await.unwrap();
+    assert!(receipt.status());
+    let code = provider.get_code_at(receipt.contract_address.unwrap()).await.unwrap();
+    assert_eq!(code.len(), 2048);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_offset_block_number() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let signer: EthereumWallet = handle.dev_wallets().next().unwrap().into();
+    let provider = http_provider_with_signer(&handle.http_endpoint(), signer);
+
+    // `aggregate` returns `block.number`
+    let multicall = Multicall::deploy(&provider).await.unwrap();
+    let block_number = multicall.aggregate(vec![]).call().await.unwrap().blockNumber;
+    let height = provider.get_block_number().await.unwrap();
+
+    api.anvil_set_block_number_offset(1000).unwrap();
+    let offset = multicall.aggregate(vec![]).call().await.unwrap().blockNumber;
+    assert_eq!(offset, block_number + U256::from(1000));
+    // the chain height is unchanged
+    assert_eq!(provider.get_block_number().await.unwrap(), height);
+
+    api.anvil_set_block_number_offset(-1).unwrap();
+    let offset = multicall.aggregate(vec![]).call().await.unwrap().blockNumber;
+    assert_eq!(offset, block_number - U256::from(1));
+
+    api.anvil_set_block_number_offset(0).unwrap();
+    let offset = multicall.aggregate(vec![]).call().await.unwrap().blockNumber;
+    assert_eq!(offset, block_number);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_call_with_coinbase() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+
+    // COINBASE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    let target = Address::random();
+    api.anvil_set_code(target, bytes!("0x4160005260206000f3")).await.unwrap();
+    let call = WithOtherFields::new(TransactionRequest::default().to(target));
+
+    let out = api.call(call.clone(), None, Default::default()).await.unwrap();
+    assert_eq!(Address::from_word(B256::from_slice(&out)), api.author().unwrap());
+
+    let coinbase = Address::random();
+    let out = api.anvil_call_with_coinbase(call, coinbase, None, None).await.unwrap();
+    assert_eq!(Address::from_word(B256::from_slice(&out)), coinbase);
+
+    // mined blocks are unaffected
+    api.mine_one().await.unwrap();
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    assert_eq!(block.header.beneficiary, api.author().unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_cache_call_results() {
+    let (api, handle) = spawn(NodeConfig::test().with_call_result_cache(true)).await;
+    let provider = handle.http_provider();
+
+    // returns the sum of storage slot 0 and the balance of the contract
+    let target = Address::random();
+    api.anvil_set_code(target, bytes!("0x60005430310160005260206000f3")).await.unwrap();
+    let call = WithOtherFields::new(TransactionRequest::default().to(target));
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::ZERO);
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::ZERO);
+
+    // changing state through the api invalidates the cache
+    api.anvil_set_storage_at(target, U256::ZERO, B256::from(U256::from(1))).await.unwrap();
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::from(1));
+
+    api.anvil_set_balance(target, U256::from(2)).await.unwrap();
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::from(3));
+
+    // so does mining a block that changes the state
+    let from = handle.dev_accounts().next().unwrap();
+    let tx =
+        TransactionRequest::default().with_from(from).with_to(target).with_value(U256::from(4));
+    provider.send_transaction(WithOtherFields::new(tx)).await.unwrap().get_receipt().await.unwrap();
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::from(7));
+
+    // and resetting the chain
+    api.anvil_reset(None).await.unwrap();
+    assert_eq!(U256::from_be_slice(&provider.call(call.clone()).await.unwrap()), U256::ZERO);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_predict_create2_address() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // deploys a contract returning 42
+    let init_code = bytes!("0x600a600c600039600a6000f3602a60005260206000f3");
+    let salt = B256::with_last_byte(7);
+    let deployer = foundry_evm::constants::DEFAULT_CREATE2_DEPLOYER;
+    let predicted =
+        api.anvil_predict_create2_address(deployer, salt, keccak256(&init_code)).unwrap();
+    assert!(provider.get_code_at(predicted).await.unwrap().is_empty());
+
+    let input = [salt.as_slice(), init_code.as_ref()].concat();
+    let tx = TransactionRequest::default().with_from(from).with_to(deployer).with_input(input);
+    let tx = WithOtherFields::new(tx);
+    let receipt = provider.send_transaction(tx.clone()).await.unwrap().get_receipt().await.unwrap();
+    assert!(receipt.status());
+    assert_eq!(provider.get_code_at(predicted).await.unwrap(), bytes!("0x602a60005260206000f3"));
+
+    // deploying again with the same salt collides, which makes the deployer revert
+    assert!(provider.call(tx).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_receipt_by_block_and_index() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(Address::random());
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    let number = receipt.block_number.unwrap();
+    let hash = receipt.block_hash.unwrap();
+
+    let by_number = api
+        .anvil_receipt_by_block_and_index(BlockId::number(number), Index::from(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_number.transaction_hash, receipt.transaction_hash);
+
+    let by_hash =
+        api.anvil_receipt_by_block_and_index(BlockId::hash(hash), Index::from(0)).await.unwrap();
+    assert_eq!(by_hash.unwrap().transaction_hash, receipt.transaction_hash);
+
+    let missing =
+        api.anvil_receipt_by_block_and_index(BlockId::number(number), Index::from(1)).await;
+    assert!(missing.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_bound_concurrent_calls() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_concurrent_calls(2)).await;
+    let signer = handle.dev_signers().next().unwrap();
+    let provider = http_provider_with_signer(&handle.http_endpoint(), signer);
+
+    let simple_storage = SimpleStorage::deploy(&provider, "init".to_string()).await.unwrap();
+    let calls = (0..50).map(|_| {
+        let tx = TransactionRequest::default()
+            .with_to(*simple_storage.address())
+            .with_input(SimpleStorage::getValueCall::new(()).abi_encode());
+        api.call(WithOtherFields::new(tx), None, Default::default())
+    });
+
+    // the calls beyond the bound are queued and all of them complete
+    let results = futures::future::join_all(calls).await;
+    let expected = results[0].as_ref().unwrap().clone();
+    assert!(!expected.is_empty());
+    assert_eq!(results.len(), 50);
+    for res in results {
+        assert_eq!(res.unwrap(), expected);
+    }
+}
@@ -5,23 +5,43 @@
     utils::{http_provider, http_provider_with_signer},
 };
 use alloy_chains::NamedChain;
+use alloy_hardforks::EthereumHardfork;
 use alloy_network::{EthereumWallet, ReceiptResponse, TransactionBuilder, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, TxKind, U64, U256, address, b256, bytes, uint};
+use alloy_primitives::{
+    Address, B256, Bytes, TxHash, TxKind, U64, U256, address, b256, bytes, keccak256, uint,
+};
 use alloy_provider::Provider;
 use alloy_rpc_types::{
     BlockId, BlockNumberOrTag,
     anvil::Forking,
     request::{TransactionInput, TransactionRequest},
-    state::EvmOverrides,
+    state::{AccountOverride, EvmOverrides, StateOverride},
+    trace::geth::{
+        CallConfig, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace,
+    },
 };
 use alloy_serde::WithOtherFields;
 use alloy_signer_local::PrivateKeySigner;
-use anvil::{NodeConfig, NodeHandle, eth::EthApi, spawn};
+use anvil::{
+    NodeConfig, NodeHandle,
+    eth::{EthApi, api::EIP1967_IMPLEMENTATION_SLOT},
+    spawn,
+};
+use anvil_core::types::{AccountKind, DifficultyHistoryEntry, GasEstimateRange, ResetOptions};
 use foundry_common::provider::get_http_provider;
 use foundry_config::Config;
+use foundry_evm::fork::RetryConfig;
 use foundry_test_utils::rpc::{self, next_http_rpc_endpoint, next_rpc_endpoint};
 use futures::StreamExt;
-use std::{sync::Arc, thread::sleep, time::Duration};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::sleep,
+    time::Duration,
+};
 
 const BLOCK_NUMBER: u64 = 14_608_400u64;
 const DEAD_BALANCE_AT_BLOCK_NUMBER: u128 = 12_556_069_338_441_120_059_867u128;
@@ -526,6 +546,68 @@ async fn can_reset_properly() {
     assert!(fork_tx_provider.get_transaction_by_hash(tx.transaction_hash).await.unwrap().is_none())
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txs_by_selector_skips_forked_blocks() {
+    let (_origin_api, origin_handle) = spawn(NodeConfig::test()).await;
+    let origin_provider = origin_handle.http_provider();
+    let accounts = origin_handle.dev_accounts().collect::<Vec<_>>();
+    let transfer = TransactionRequest::default()
+        .with_from(accounts[0])
+        .with_to(accounts[1])
+        .with_input(bytes!("0xa9059cbb0000"));
+    origin_provider
+        .send_transaction(WithOtherFields::new(transfer.clone()))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let (fork_api, fork_handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(origin_handle.http_endpoint()))).await;
+    let receipt = fork_handle
+        .http_provider()
+        .send_transaction(WithOtherFields::new(transfer))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let txs = fork_api
+        .anvil_txs_by_selector(
+            "0xa9059cbb".parse().unwrap(),
+            BlockNumberOrTag::Earliest,
+            BlockNumberOrTag::Latest,
+        )
+        .await
+        .unwrap();
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].tx_hash(), receipt.transaction_hash);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_orphaned_transactions_after_reset() {
+    let (_origin_api, origin_handle) = spawn(NodeConfig::test()).await;
+    let (fork_api, fork_handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(origin_handle.http_endpoint()))).await;
+    let provider = fork_handle.http_provider();
+    let account = fork_handle.dev_accounts().next().unwrap();
+
+    assert!(fork_api.anvil_orphaned_transactions().unwrap().is_empty());
+
+    let tx = TransactionRequest::default().from(account).to(Address::random()).value(U256::from(1));
+    let tx = WithOtherFields::new(tx);
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+
+    // nothing is orphaned while the tx is still part of the chain
+    assert!(fork_api.anvil_orphaned_transactions().unwrap().is_empty());
+
+    fork_api.anvil_reset(Some(Forking::default())).await.unwrap();
+
+    assert_eq!(fork_api.anvil_orphaned_transactions().unwrap(), vec![receipt.transaction_hash]);
+}
+
 // Ref: <https://github.com/foundry-rs/foundry/issues/8684>
 #[tokio::test(flavor = "multi_thread")]
 async fn can_reset_fork_to_new_fork() {
@@ -811,7 +893,7 @@ async fn test_fork_init_base_fee() {
     let init_base_fee = block.header.base_fee_per_gas.unwrap();
     assert_eq!(init_base_fee, 63739886069);
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
 
@@ -819,6 +901,48 @@ async fn test_fork_init_base_fee() {
     assert!(next_base_fee < init_base_fee);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_base_fee_explain() {
+    let (api, handle) = spawn(fork_config().with_fork_block_number(Some(13184859u64))).await;
+
+    let provider = handle.http_provider();
+
+    let explained = api.anvil_base_fee_explain(BlockNumberOrTag::Latest).await.unwrap();
+    // <https://etherscan.io/block/13184859>
+    assert_eq!(explained.parent_number, 13184859u64);
+    assert_eq!(explained.parent_base_fee, 63739886069);
+    assert_eq!(explained.gas_target, explained.parent_gas_limit / 2);
+    assert!(explained.parent_gas_used < explained.gas_target);
+
+    api.mine_one().await.unwrap();
+
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    assert_eq!(block.header.base_fee_per_gas, Some(explained.next_base_fee));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_next_base_fee() {
+    let (api, handle) = spawn(fork_config().with_fork_block_number(Some(13184859u64))).await;
+
+    let provider = handle.http_provider();
+
+    let next_base_fee = api.anvil_next_base_fee().unwrap();
+
+    api.mine_one().await.unwrap();
+
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    assert_eq!(next_base_fee, U256::from(block.header.base_fee_per_gas.unwrap()));
+
+    // an explicitly set base fee is picked up by the next block
+    api.anvil_set_next_block_base_fee_per_gas(U256::from(1_000_000_000u64)).await.unwrap();
+    assert_eq!(api.anvil_next_base_fee().unwrap(), U256::from(1_000_000_000u64));
+
+    api.mine_one().await.unwrap();
+
+    let block = provider.get_block(BlockId::latest()).await.unwrap().unwrap();
+    assert_eq!(block.header.base_fee_per_gas.unwrap(), 1_000_000_000u64);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_reset_fork_on_new_blocks() {
     let (api, handle) =
@@ -879,6 +1003,43 @@ async fn test_fork_call() {
     assert_eq!(res0, res1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_debug_trace_call_with_overrides() {
+    let (api, _) = spawn(fork_config()).await;
+
+    let from = Address::random();
+    let target = Address::random();
+    let mut state = StateOverride::default();
+    state.insert(from, AccountOverride { balance: Some(U256::from(1e18)), ..Default::default() });
+    // PUSH1 0 PUSH1 0 REVERT
+    state
+        .insert(target, AccountOverride { code: Some(bytes!("60006000fd")), ..Default::default() });
+
+    let tx = TransactionRequest::default().from(from).to(target).value(U256::from(1));
+    let trace = api
+        .debug_trace_call(
+            WithOtherFields::new(tx),
+            None,
+            EvmOverrides::new(Some(state), None),
+            GethDebugTracingCallOptions::default().with_tracing_options(
+                GethDebugTracingOptions::default()
+                    .with_tracer(GethDebugTracerType::from(GethDebugBuiltInTracerType::CallTracer))
+                    .with_call_config(CallConfig::default()),
+            ),
+        )
+        .await
+        .unwrap();
+
+    let GethTrace::CallTracer(frame) = trace else { unreachable!() };
+    assert_eq!(frame.to, Some(target));
+    assert_eq!(frame.value, Some(U256::from(1)));
+    assert_eq!(frame.error.as_deref(), Some("execution reverted"));
+
+    // the overrides are discarded after the call
+    assert!(api.get_code(target, None).await.unwrap().is_empty());
+    assert_eq!(api.balance(from, None).await.unwrap(), U256::ZERO);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_fork_block_timestamp() {
     let (api, _) = spawn(fork_config()).await;
@@ -944,6 +1105,311 @@ async fn test_fork_uncles_fetch() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_reset_verifies_state_root() {
+    let (api, handle) = spawn(fork_config().with_verify_state_root_on_reset(true)).await;
+    let provider = handle.http_provider();
+
+    api.mine_one().await.unwrap();
+
+    let block_number = BLOCK_NUMBER - 10;
+    api.anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(block_number) }))
+        .await
+        .unwrap();
+    assert_eq!(block_number, provider.get_block_number().await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_reset_fails_on_state_root_mismatch() {
+    let (_remote_api, remote_handle) = spawn(NodeConfig::test()).await;
+    let remote = remote_handle.http_provider();
+    let accounts = remote_handle.dev_wallets().collect::<Vec<_>>();
+    for _ in 0..3 {
+        let tx = TransactionRequest::default()
+            .from(accounts[0].address())
+            .to(accounts[1].address())
+            .value(U256::from(1));
+        remote
+            .send_transaction(WithOtherFields::new(tx))
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap();
+    }
+
+    // forwards all requests to the remote, optionally serving tampered account proofs or balances
+    let tamper = Arc::new(AtomicBool::new(false));
+    let tamper_balances = Arc::new(AtomicBool::new(false));
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post({
+            let (tamper, tamper_balances) = (tamper.clone(), tamper_balances.clone());
+            move |axum::Json(mut request): axum::Json<serde_json::Value>| {
+                let (remote, tamper, tamper_balances) =
+                    (remote.clone(), tamper.clone(), tamper_balances.clone());
+                async move {
+                    let method = request["method"].as_str().unwrap().to_string();
+                    let mut result: serde_json::Value = remote
+                        .raw_request(method.clone().into(), request["params"].take())
+                        .await
+                        .unwrap();
+                    if method == "eth_getProof" && tamper.load(Ordering::Relaxed) {
+                        result["balance"] = "0x1".into();
+                    }
+                    if method == "eth_getBalance" && tamper_balances.load(Ordering::Relaxed) {
+                        result = "0x1".into();
+                    }
+                    axum::Json(
+                        serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                    )
+                }
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (api, handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(url)).with_verify_state_root_on_reset(true))
+            .await;
+    let provider = handle.http_provider();
+
+    // consistent proofs pass
+    api.anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(2) })).await.unwrap();
+    assert_eq!(provider.get_block_number().await.unwrap(), 2);
+    api.mine_one().await.unwrap();
+
+    // proofs that don't verify against the state root fail the reset before anything is reset
+    tamper.store(true, Ordering::Relaxed);
+    let err = api
+        .anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(1) }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("state root mismatch"), "{err}");
+    assert_eq!(provider.get_block_number().await.unwrap(), 3);
+
+    // valid proofs don't help if the fork database serves different accounts, e.g. from a
+    // corrupted cache, the reset is carried out but reports the mismatch
+    tamper.store(false, Ordering::Relaxed);
+    tamper_balances.store(true, Ordering::Relaxed);
+    let err = api
+        .anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(1) }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("fork cache may be corrupted"), "{err}");
+    assert_eq!(provider.get_block_number().await.unwrap(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_setup_retries_failed_requests() {
+    let (_remote_api, remote_handle) = spawn(NodeConfig::test()).await;
+    let remote = remote_handle.http_provider();
+
+    // forwards all requests to the remote, but fails the first chain id requests and doesn't
+    // return the block for the first block requests
+    let chain_id_failures = Arc::new(AtomicUsize::new(0));
+    let block_failures = Arc::new(AtomicUsize::new(0));
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post({
+            let (chain_id_failures, block_failures) =
+                (chain_id_failures.clone(), block_failures.clone());
+            move |axum::Json(mut request): axum::Json<serde_json::Value>| {
+                let remote = remote.clone();
+                let (chain_id_failures, block_failures) =
+                    (chain_id_failures.clone(), block_failures.clone());
+                async move {
+                    let fail = |failures: &AtomicUsize| {
+                        failures
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                            .is_ok()
+                    };
+                    let (id, method) = (request["id"].clone(), request["method"].clone());
+                    let response = if method == "eth_chainId" && fail(&chain_id_failures) {
+                        serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": -32603, "message": "internal error" },
+                        })
+                    } else if method == "eth_getBlockByNumber" && fail(&block_failures) {
+                        serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": null })
+                    } else {
+                        let result: serde_json::Value = remote
+                            .raw_request(
+                                method.as_str().unwrap().to_string().into(),
+                                request["params"].take(),
+                            )
+                            .await
+                            .unwrap();
+                        serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result })
+                    };
+                    axum::Json(response)
+                }
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let config = NodeConfig::test()
+        .with_eth_rpc_url(Some(url))
+        .with_fork_block_number(Some(0u64))
+        .fork_request_retries(Some(0));
+
+    // by default every setup request is made once
+    chain_id_failures.store(2, Ordering::SeqCst);
+    let Err(err) = anvil::try_spawn(config.clone()).await else {
+        panic!("fork setup should fail without retries")
+    };
+    assert!(err.to_string().contains("failed to fetch network chain ID"), "{err}");
+
+    // the failed chain id requests and the missing block are retried
+    chain_id_failures.store(2, Ordering::SeqCst);
+    block_failures.store(2, Ordering::SeqCst);
+    let retry = RetryConfig {
+        max_retries: 2,
+        initial_backoff: Duration::from_millis(10),
+        backoff_multiplier: 2,
+    };
+    let (api, _handle) = spawn(config.with_fork_retry_config(retry)).await;
+    assert_eq!(chain_id_failures.load(Ordering::SeqCst), 0);
+    assert_eq!(block_failures.load(Ordering::SeqCst), 0);
+    assert_eq!(api.get_fork().unwrap().block_number(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_base_block() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let base = api.anvil_fork_base_block().unwrap().unwrap();
+    assert_eq!(base.number, BLOCK_NUMBER);
+    assert_eq!(base.timestamp, BLOCK_TIMESTAMP);
+
+    let block = provider.get_block(BlockId::number(BLOCK_NUMBER)).await.unwrap().unwrap();
+    assert_eq!(base.hash, block.header.hash);
+    assert_eq!(base.state_root, block.header.state_root);
+    assert_eq!(base.base_fee, block.header.base_fee_per_gas.map(|fee| fee as u128));
+
+    // mining doesn't change the base block
+    api.mine_one().await.unwrap();
+    assert_eq!(api.anvil_fork_base_block().unwrap().unwrap(), base);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_contract_info() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    // the AAVE token is deployed behind an EIP-1967 proxy
+    let aave = address!("0x7Fc66500c84A76Ad7e9c93437bFc5Ac33E2DDaE9");
+    let info = api.anvil_contract_info(aave).await.unwrap();
+    let code = provider.get_code_at(aave).await.unwrap();
+    assert_eq!(info.code_size, code.len() as u64);
+    assert_eq!(info.code_hash, keccak256(&code));
+    assert!(info.is_proxy);
+
+    let slot = provider
+        .get_storage_at(aave, U256::from_be_bytes(EIP1967_IMPLEMENTATION_SLOT.0))
+        .await
+        .unwrap();
+    let implementation = Address::from_word(slot.into());
+    assert_eq!(info.implementation, Some(implementation));
+    assert!(!provider.get_code_at(implementation).await.unwrap().is_empty());
+
+    // DAI is not a proxy
+    let dai = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    let info = api.anvil_contract_info(dai).await.unwrap();
+    assert!(info.code_size > 0);
+    assert!(!info.is_proxy);
+    assert_eq!(info.implementation, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_masked_accounts() {
+    let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    let (api, handle) = spawn(fork_config().with_masked_accounts(vec![weth])).await;
+    let provider = handle.http_provider();
+
+    let code = provider.get_code_at(weth).await.unwrap();
+    assert!(code.is_empty());
+    let balance = provider.get_balance(weth).await.unwrap();
+    assert_eq!(balance, U256::ZERO);
+    // slot 0 holds the token name
+    let name = provider.get_storage_at(weth, U256::ZERO).await.unwrap();
+    assert_eq!(name, U256::ZERO);
+
+    // other accounts still resolve the remote state
+    let dai = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    assert!(!provider.get_code_at(dai).await.unwrap().is_empty());
+
+    // masking survives a reset
+    api.anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(BLOCK_NUMBER) }))
+        .await
+        .unwrap();
+    assert!(provider.get_code_at(weth).await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_estimate_gas_range() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let from = handle.dev_wallets().next().unwrap().address();
+    let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+    // sending ether to WETH executes its deposit fallback
+    let tx = TransactionRequest::default().from(from).to(weth).value(U256::from(1));
+    let tx = WithOtherFields::new(tx);
+
+    let range = api.anvil_estimate_gas_range(tx.clone(), None).await.unwrap();
+    assert!(range.lower <= range.estimate);
+    assert!(range.estimate <= range.upper);
+
+    let estimate = provider.estimate_gas(tx).await.unwrap();
+    assert_eq!(range.estimate, estimate);
+
+    // plain transfers have no margin
+    let to = Address::random();
+    let tx = TransactionRequest::default().from(from).to(to).value(U256::from(1));
+    let range = api.anvil_estimate_gas_range(WithOtherFields::new(tx), None).await.unwrap();
+    assert_eq!(range, GasEstimateRange::exact(21_000));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_uncle_reward() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    // Block on ETH mainnet with 2 uncles, mined under frontier's 5 ETH block reward
+    let block_with_uncles = 190u64;
+    let block_reward = U256::from(5_000_000_000_000_000_000u128);
+
+    for uncle_idx in 0..2usize {
+        let uncle = provider
+            .get_uncle(BlockId::number(block_with_uncles), uncle_idx as u64)
+            .await
+            .unwrap()
+            .unwrap();
+        let expected =
+            U256::from(uncle.header.number + 8 - block_with_uncles) * block_reward / U256::from(8);
+
+        let reward = api
+            .anvil_uncle_reward(BlockNumberOrTag::Number(block_with_uncles), uncle_idx.into())
+            .await
+            .unwrap();
+        assert_eq!(reward, Some(expected));
+    }
+
+    // no such uncle
+    let reward =
+        api.anvil_uncle_reward(BlockNumberOrTag::Number(block_with_uncles), 2usize.into()).await;
+    assert_eq!(reward.unwrap(), None);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_fork_block_transaction_count() {
     let (api, handle) = spawn(fork_config()).await;
@@ -1044,8 +1510,8 @@ async fn test_total_difficulty_fork() {
     assert_eq!(block.header.total_difficulty, Some(total_difficulty));
     assert_eq!(block.header.difficulty, difficulty);
 
-    api.mine_one().await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
+    api.mine_one().await.unwrap();
 
     let next_total_difficulty = total_difficulty + difficulty;
 
@@ -1172,7 +1638,7 @@ async fn test_fork_reset_basefee() {
     // <https://etherscan.io/block/18835000>
     let (api, _handle) = spawn(fork_config().with_fork_block_number(Some(18835000u64))).await;
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let latest = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
 
     // basefee of +1 block: <https://etherscan.io/block/18835001>
@@ -1183,7 +1649,7 @@ async fn test_fork_reset_basefee() {
         .await
         .unwrap();
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let latest = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
 
     // basefee of the forked block: <https://etherscan.io/block/18835000>
@@ -1222,7 +1688,7 @@ async fn test_arb_fork_mining() {
     let init_blk_num = api.block_number().unwrap().to::<u64>();
 
     // Mine one
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let mined_blk_num = api.block_number().unwrap().to::<u64>();
 
     assert_eq!(mined_blk_num, init_blk_num + 1);
@@ -1257,7 +1723,7 @@ async fn test_arbitrum_fork_block_number() {
     let snapshot_state = api.evm_snapshot().await.unwrap();
 
     // mine new block and check block number returned by `eth_blockNumber`
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let block_number = api.block_number().unwrap().to::<u64>();
     assert_eq!(block_number, initial_block_number + 1);
 
@@ -1282,6 +1748,65 @@ async fn test_arbitrum_fork_block_number() {
     assert_eq!(block_number, initial_block_number - 2);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_arbitrum_fork_disable_chain_specific_env() {
+    let fork_block_number = 266137031u64;
+
+    let (api, _handle) = spawn(
+        fork_config()
+            .with_fork_block_number(Some(fork_block_number))
+            .with_eth_rpc_url(Some(next_rpc_endpoint(NamedChain::Arbitrum))),
+    )
+    .await;
+    let info = api.anvil_node_info().await.unwrap();
+    assert!(info.chain_specific_env_changes.contains(&"number".to_string()));
+    // the env uses the L1 block number
+    assert_ne!(api.backend.env().read().evm_env.block_env.number, U256::from(fork_block_number));
+
+    let (api, _handle) = spawn(
+        fork_config()
+            .with_fork_block_number(Some(fork_block_number))
+            .with_eth_rpc_url(Some(next_rpc_endpoint(NamedChain::Arbitrum)))
+            .with_disable_chain_specific_env(true),
+    )
+    .await;
+    let info = api.anvil_node_info().await.unwrap();
+    assert!(info.chain_specific_env_changes.is_empty());
+    // the env keeps the L2 block number
+    assert_eq!(api.backend.env().read().evm_env.block_env.number, U256::from(fork_block_number));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_arbitrum_fork_override_chain_id() {
+    let fork_block_number = 266137031u64;
+    let chain_id = 1234u64;
+
+    let (api, _handle) = spawn(
+        fork_config()
+            .with_fork_block_number(Some(fork_block_number))
+            .with_eth_rpc_url(Some(next_rpc_endpoint(NamedChain::Arbitrum)))
+            .with_chain_id(Some(chain_id)),
+    )
+    .await;
+    assert_eq!(api.eth_chain_id().unwrap(), Some(U64::from(chain_id)));
+
+    // the chain specifics are still detected from the remote chain id
+    let info = api.anvil_node_info().await.unwrap();
+    assert!(info.chain_specific_env_changes.contains(&"number".to_string()));
+
+    api.mine_one().await.unwrap();
+    let block = api.block_by_number(BlockNumberOrTag::Latest).await.unwrap().unwrap();
+    assert_eq!(block.header.number, fork_block_number + 1);
+    assert!(block.other.get("l1BlockNumber").is_some());
+
+    // and again after resetting the fork
+    api.anvil_reset(Some(Forking { json_rpc_url: None, block_number: Some(fork_block_number) }))
+        .await
+        .unwrap();
+    let info = api.anvil_node_info().await.unwrap();
+    assert!(info.chain_specific_env_changes.contains(&"number".to_string()));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_base_fork_gas_limit() {
     // fork to get initial block for test
@@ -1490,6 +2015,173 @@ async fn test_set_erc20_balance() {
     assert_eq!(new_balance, value);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_storage_proof_of_dealt_erc20() {
+    let config: NodeConfig = fork_config();
+    let holder = config.genesis_accounts[0].address();
+    let (api, _handle) = spawn(config).await;
+
+    let dai = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    let value = U256::from(500);
+
+    // unmodified accounts are served from upstream at the fork block
+    let upstream = api.get_proof(dai, Vec::new(), None).await.unwrap();
+
+    api.anvil_deal_erc20(holder, dai, value).await.unwrap();
+
+    // DAI keeps balances in the mapping at slot 2
+    let slot = keccak256([holder.into_word().0, B256::with_last_byte(2).0].concat());
+    let proof = api.get_proof(dai, vec![slot], None).await.unwrap();
+    assert_ne!(proof.storage_hash, upstream.storage_hash);
+
+    let storage = &proof.storage_proof[0];
+    assert_eq!(storage.value, value);
+    alloy_trie::proof::verify_proof(
+        proof.storage_hash,
+        alloy_trie::Nibbles::unpack(keccak256(slot)),
+        Some(alloy_rlp::encode(storage.value)),
+        &storage.proof,
+    )
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_proof_without_upstream_proofs() {
+    let (_remote_api, remote_handle) = spawn(NodeConfig::test()).await;
+    let remote = remote_handle.http_provider();
+    let proof_requests = Arc::new(AtomicUsize::new(0));
+
+    // forwards all requests to the remote, except for proofs which it can't serve
+    let requests = proof_requests.clone();
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post(move |axum::Json(mut request): axum::Json<serde_json::Value>| {
+            let remote = remote.clone();
+            let requests = requests.clone();
+            async move {
+                let method = request["method"].as_str().unwrap().to_string();
+                let response = if method == "eth_getProof" {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "error": { "code": -32000, "message": "missing trie node" },
+                    })
+                } else {
+                    let result: serde_json::Value =
+                        remote.raw_request(method.into(), request["params"].take()).await.unwrap();
+                    serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+                };
+                axum::Json(response)
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (api, _handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(url)).fork_request_retries(Some(0))).await;
+
+    // accounts changed locally are proven from the local state without asking upstream
+    let address = Address::random();
+    let balance = U256::from(1337);
+    api.anvil_set_balance(address, balance).await.unwrap();
+    let proof = api.get_proof(address, Vec::new(), None).await.unwrap();
+    assert_eq!(proof.address, address);
+    assert_eq!(proof.balance, balance);
+    assert_eq!(proof_requests.load(Ordering::SeqCst), 0);
+
+    // the proof is synthesized locally instead of failing the request
+    let untouched = Address::random();
+    let proof = api.get_proof(untouched, Vec::new(), None).await.unwrap();
+    assert_eq!(proof.balance, U256::ZERO);
+    assert_eq!(proof_requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_max_priority_fee_is_fetched_once() {
+    let (_remote_api, remote_handle) = spawn(NodeConfig::test()).await;
+    let remote = remote_handle.http_provider();
+    let fee_requests = Arc::new(AtomicUsize::new(0));
+
+    // forwards all requests to the remote and counts the priority fee requests
+    let requests = fee_requests.clone();
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post(move |axum::Json(mut request): axum::Json<serde_json::Value>| {
+            let remote = remote.clone();
+            let requests = requests.clone();
+            async move {
+                let method = request["method"].as_str().unwrap().to_string();
+                if method == "eth_maxPriorityFeePerGas" {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                }
+                let result: serde_json::Value =
+                    remote.raw_request(method.into(), request["params"].take()).await.unwrap();
+                axum::Json(
+                    serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                )
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (api, _handle) = spawn(NodeConfig::test().with_eth_rpc_url(Some(url))).await;
+
+    let tip = api.max_priority_fee_per_gas().await.unwrap();
+    assert_eq!(api.max_priority_fee_per_gas().await.unwrap(), tip);
+    assert_eq!(fee_requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_decode_erc20_transfer_logs() {
+    let config: NodeConfig = fork_config();
+    let from = config.genesis_accounts[0].address();
+    let to = config.genesis_accounts[1].address();
+    let (api, handle) = spawn(config).await;
+
+    let provider = handle.http_provider();
+
+    alloy_sol_types::sol! {
+       #[sol(rpc)]
+       contract ERC20 {
+            function transfer(address to, uint256 amount) external returns (bool);
+       }
+    }
+    let dai = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    let erc20 = ERC20::new(dai, provider);
+    let value = U256::from(500);
+
+    api.anvil_deal_erc20(from, dai, value).await.unwrap();
+    let receipt =
+        erc20.transfer(to, value).from(from).send().await.unwrap().get_receipt().await.unwrap();
+
+    let abi = r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+        {"name":"src","type":"address","indexed":true},
+        {"name":"dst","type":"address","indexed":true},
+        {"name":"wad","type":"uint256","indexed":false}
+    ]}]"#;
+    let logs =
+        api.anvil_decode_receipt_logs(receipt.transaction_hash, abi.to_string()).await.unwrap();
+
+    assert_eq!(logs.len(), 1);
+    let log = &logs[0];
+    assert_eq!(log.address, dai);
+    assert_eq!(log.event, "Transfer");
+    let params = log.params.iter().map(|p| (p.name.clone(), p.value.clone())).collect::<Vec<_>>();
+    assert_eq!(
+        params,
+        vec![
+            ("src".to_string(), from.to_string()),
+            ("dst".to_string(), to.to_string()),
+            ("wad".to_string(), value.to_string()),
+        ]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_set_erc20_allowance() {
     let config: NodeConfig = fork_config();
@@ -1515,6 +2207,34 @@ async fn test_set_erc20_allowance() {
     assert_eq!(allowance, value);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deal_erc20_and_approve() {
+    let config: NodeConfig = fork_config();
+    let holder = config.genesis_accounts[0].address();
+    let spender = config.genesis_accounts[1].address();
+    let (api, handle) = spawn(config).await;
+
+    let provider = handle.http_provider();
+
+    alloy_sol_types::sol! {
+       #[sol(rpc)]
+       contract ERC20 {
+            function balanceOf(address owner) external view returns (uint256);
+            function allowance(address owner, address spender) external view returns (uint256);
+       }
+    }
+    let dai = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    let erc20 = ERC20::new(dai, provider);
+    let value = U256::from(500);
+
+    api.anvil_deal_erc20_and_approve(holder, dai, value, spender).await.unwrap();
+
+    let balance = erc20.balanceOf(holder).call().await.unwrap();
+    assert_eq!(balance, value);
+    let allowance = erc20.allowance(holder, spender).call().await.unwrap();
+    assert_eq!(allowance, value);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_add_balance() {
     let config: NodeConfig = fork_config();
@@ -1607,3 +2327,609 @@ async fn test_fork_get_account() {
 
     assert_eq!(alice_acc_init, alice_acc_prev_block);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_account_proof_at_block() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let address = address!("0x000000000000000000000000000000000000dEaD");
+    let block = BlockId::number(BLOCK_NUMBER - 1);
+    let proof = api.anvil_account_proof(address, block).await.unwrap();
+    assert!(proof.storage_proof.is_empty());
+    assert_eq!(proof.balance, provider.get_balance(address).block_id(block).await.unwrap());
+
+    let state_root = provider.get_block(block).await.unwrap().unwrap().header.state_root;
+    let account = alloy_trie::TrieAccount {
+        nonce: proof.nonce,
+        balance: proof.balance,
+        storage_root: proof.storage_hash,
+        code_hash: proof.code_hash,
+    };
+    alloy_trie::proof::verify_proof(
+        state_root,
+        alloy_trie::Nibbles::unpack(keccak256(address)),
+        Some(alloy_rlp::encode(account)),
+        &proof.account_proof,
+    )
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_offline_fork_fallback_serves_cached_state() {
+    let cached = Address::random();
+    let balance = U256::from(1337);
+
+    // run the remote on its own runtime so it can be taken down mid-run
+    let (endpoint_tx, endpoint_rx) = std::sync::mpsc::channel();
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    let remote = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (api, handle) = runtime.block_on(spawn(NodeConfig::test()));
+        runtime.block_on(api.anvil_set_balance(cached, balance)).unwrap();
+        endpoint_tx.send(handle.http_endpoint()).unwrap();
+        let _ = stop_rx.recv();
+        drop(handle);
+        runtime.shutdown_background();
+    });
+    let endpoint = endpoint_rx.recv().unwrap();
+
+    let (_api, handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(endpoint)).with_offline_fork_fallback(true))
+            .await;
+    let provider = handle.http_provider();
+
+    // warm the cache
+    assert_eq!(provider.get_balance(cached).await.unwrap(), balance);
+
+    stop_tx.send(()).unwrap();
+    remote.join().unwrap();
+
+    assert_eq!(provider.get_balance(cached).await.unwrap(), balance);
+    let err = provider.get_balance(Address::random()).await.unwrap_err();
+    assert!(err.to_string().contains("offline"), "{err}");
+
+    // once the remote is known to be offline, cache misses aren't sent at all
+    let err = provider.get_balance(Address::random()).await.unwrap_err();
+    assert!(err.to_string().contains("the request was not sent"), "{err}");
+    assert_eq!(provider.get_balance(cached).await.unwrap(), balance);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_forked_chain_genesis() {
+    let (api, _handle) = spawn(fork_config()).await;
+
+    let genesis = api.anvil_forked_chain_genesis().await.unwrap().unwrap();
+    assert_eq!(genesis.chain_id, 1);
+    assert_eq!(genesis.chain.as_deref(), Some("mainnet"));
+    assert!(!genesis.is_testnet);
+    assert_eq!(
+        genesis.genesis_hash,
+        b256!("0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+    );
+
+    // the config is that of the remote chain even if the local chain id is overridden
+    let (api, _handle) = spawn(fork_config().with_chain_id(Some(1337u64))).await;
+    let genesis = api.anvil_forked_chain_genesis().await.unwrap().unwrap();
+    assert_eq!(genesis.chain_id, 1);
+    assert_eq!(genesis.chain.as_deref(), Some("mainnet"));
+
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    assert!(api.anvil_forked_chain_genesis().await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_remote_block_number() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let local = provider.get_block_number().await.unwrap();
+    assert_eq!(local, BLOCK_NUMBER);
+    let remote = api.anvil_remote_block_number().await.unwrap().unwrap();
+    assert!(remote > local);
+
+    let (api, _handle) = spawn(NodeConfig::test()).await;
+    assert!(api.anvil_remote_block_number().await.unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_confirmation_depth() {
+    let depth = 5;
+    let (api, handle) = spawn(
+        fork_config().with_fork_block_number(None::<u64>).with_fork_confirmation_depth(depth),
+    )
+    .await;
+    let provider = handle.http_provider();
+
+    let pinned = provider.get_block_number().await.unwrap();
+    let remote = api.anvil_remote_block_number().await.unwrap().unwrap();
+    // the remote head may have advanced a block or two since the fork was created
+    assert!(pinned + depth <= remote);
+    assert!(remote - (pinned + depth) <= 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_optimism_l2_gas_accounting() {
+    let (api, handle) = spawn(
+        NodeConfig::test()
+            .with_eth_rpc_url(Some(rpc::next_rpc_endpoint(NamedChain::Optimism)))
+            .with_l2_gas_accounting(true),
+    )
+    .await;
+    assert!(api.backend.is_optimism());
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let value = U256::from(1);
+    let balance_before = provider.get_balance(from).await.unwrap();
+    let tx = TransactionRequest::default().from(from).to(Address::random()).value(value);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    let balance_after = provider.get_balance(from).await.unwrap();
+
+    let execution_fee = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+    let total_fee = balance_before - balance_after - value;
+    // the remainder is the L1 data fee
+    assert!(total_fee > execution_fee, "{total_fee} <= {execution_fee}");
+
+    // the effective fee of the local transaction reports the L1 data fee it was charged
+    let fee = api.anvil_effective_fee(receipt.transaction_hash).await.unwrap().unwrap();
+    assert_eq!(fee.execution_fee, execution_fee);
+    assert_eq!(fee.l1_fee, Some(total_fee - execution_fee));
+    assert_eq!(fee.total_fee, total_fee);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_l2_gas_accounting_rejects_unsupported_chains() {
+    let (_remote_api, remote_handle) =
+        spawn(NodeConfig::test().with_chain_id(Some(NamedChain::Arbitrum as u64))).await;
+
+    let Err(err) = anvil::try_spawn(
+        NodeConfig::test()
+            .with_eth_rpc_url(Some(remote_handle.http_endpoint()))
+            .with_l2_gas_accounting(true),
+    )
+    .await
+    else {
+        panic!("L2 gas accounting should be rejected on Arbitrum")
+    };
+    assert!(err.to_string().contains("only supported on OP stack chains"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_uses_spec_at_block() {
+    // a Berlin block, London activated at 12_965_000
+    let fork_block = 12_500_000u64;
+    let (api, _handle) = spawn(
+        fork_config()
+            .with_fork_block_number(Some(fork_block))
+            .with_hardfork(Some(EthereumHardfork::London.into())),
+    )
+    .await;
+    api.evm_mine(None).await.unwrap();
+
+    assert_eq!(api.anvil_spec_at_block(BlockNumberOrTag::Number(fork_block)).unwrap(), "Berlin");
+    assert_eq!(api.anvil_spec_at_block(BlockNumberOrTag::Latest).unwrap(), "London");
+
+    // returns the base fee of the block, `BASEFEE` does not exist before London
+    let tx = WithOtherFields::new(
+        TransactionRequest::default().with_deploy_code(bytes!("0x4860005260206000f3")),
+    );
+    let res =
+        api.call(tx.clone(), Some(BlockId::number(fork_block)), EvmOverrides::default()).await;
+    assert!(res.is_err());
+    api.call(tx, None, EvmOverrides::default()).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_storage_root() {
+    let (api, _handle) = spawn(fork_config()).await;
+    let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+    let historical =
+        api.anvil_storage_root(weth, Some(BlockId::number(BLOCK_NUMBER - 1))).await.unwrap();
+    assert_ne!(historical, alloy_trie::EMPTY_ROOT_HASH);
+
+    let base = api.anvil_storage_root(weth, None).await.unwrap();
+    assert_ne!(base, alloy_trie::EMPTY_ROOT_HASH);
+
+    api.anvil_set_storage_at(weth, U256::from(1337), B256::with_last_byte(1)).await.unwrap();
+    api.evm_mine(None).await.unwrap();
+
+    let modified = api.anvil_storage_root(weth, None).await.unwrap();
+    assert_ne!(modified, base);
+    // the fork base is unaffected by the local modification
+    let fork_base =
+        api.anvil_storage_root(weth, Some(BlockId::number(BLOCK_NUMBER - 1))).await.unwrap();
+    assert_eq!(fork_base, historical);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_storage_root_of_untouched_account() {
+    let (origin_api, origin_handle) = spawn(NodeConfig::test()).await;
+    let address = Address::random();
+    origin_api.anvil_set_storage_at(address, U256::ZERO, B256::with_last_byte(1)).await.unwrap();
+    origin_api.evm_mine(None).await.unwrap();
+    let origin_root = origin_api.anvil_storage_root(address, None).await.unwrap();
+    assert_ne!(origin_root, alloy_trie::EMPTY_ROOT_HASH);
+
+    let (api, _handle) =
+        spawn(NodeConfig::test().with_eth_rpc_url(Some(origin_handle.http_endpoint()))).await;
+    api.evm_mine(None).await.unwrap();
+
+    // the account has no local entry, so its root comes from the remote
+    assert_eq!(api.anvil_storage_root(address, None).await.unwrap(), origin_root);
+
+    // an account that doesn't exist anywhere has an empty root
+    let root = api.anvil_storage_root(Address::random(), None).await.unwrap();
+    assert_eq!(root, alloy_trie::EMPTY_ROOT_HASH);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_block() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let receipts = provider.get_block_receipts(BLOCK_NUMBER.into()).await.unwrap().unwrap();
+    let replayed = api.anvil_replay_block(BLOCK_NUMBER, None).await.unwrap();
+    assert_eq!(replayed.len(), receipts.len());
+
+    let successful = receipts.iter().filter(|receipt| receipt.status()).count();
+    assert_eq!(replayed.iter().filter(|tx| tx.success).count(), successful);
+    for (tx, receipt) in replayed.iter().zip(&receipts) {
+        assert_eq!(tx.transaction_hash, receipt.transaction_hash);
+        assert_eq!(tx.gas_used, receipt.gas_used);
+    }
+
+    // blocks mined locally can't be replayed
+    assert!(api.anvil_replay_block(BLOCK_NUMBER + 1, None).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_set_storage_at_range() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+    let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    let start = U256::from(1000);
+
+    let values: Vec<B256> = (1..=64u64).map(|i| B256::from(U256::from(i))).collect();
+    let written = api.anvil_set_storage_at_range(weth, start, values.clone()).await.unwrap();
+    assert_eq!(written, 64);
+
+    for (i, value) in values.iter().enumerate() {
+        let stored = provider.get_storage_at(weth, start + U256::from(i)).await.unwrap();
+        assert_eq!(B256::from(stored), *value);
+    }
+
+    // the remote state before the fork is untouched
+    let remote = provider
+        .get_storage_at(weth, start)
+        .block_id(BlockId::number(BLOCK_NUMBER - 1))
+        .await
+        .unwrap();
+    assert_eq!(remote, U256::ZERO);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_effective_fee_optimism() {
+    let tx_hash = b256!("0xfcb864b5a50f0f0b111dbbf9e9167b2cb6179dfd6270e1ad53aac6049c0ec038");
+    let (api, _handle) = spawn(
+        NodeConfig::test()
+            .with_blocktime(Some(Duration::from_millis(500)))
+            .with_eth_rpc_url(Some(next_rpc_endpoint(NamedChain::Optimism)))
+            .with_fork_transaction_hash(Some(tx_hash)),
+    )
+    .await;
+
+    // wait for the forked transaction to be replayed into the first local block
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while api.block_number().unwrap().to::<u64>() < 125777954 {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    })
+    .await
+    .expect("forked transaction wasn't replayed");
+
+    let fee = api.anvil_effective_fee(tx_hash).await.unwrap().unwrap();
+    assert!(fee.execution_fee > U256::ZERO);
+    assert!(fee.l1_fee.unwrap() > U256::ZERO);
+    assert_eq!(fee.total_fee, fee.execution_fee + fee.l1_fee.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_warm_cache_from() {
+    let cached = Address::random();
+    let balance = U256::from(1337);
+    let tmp = tempfile::tempdir().unwrap();
+    let cache = tmp.path().join("cache.json");
+
+    // run the remote on its own runtime so it can be taken down mid-run
+    let (endpoint_tx, endpoint_rx) = std::sync::mpsc::channel();
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    let remote = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (api, handle) = runtime.block_on(spawn(NodeConfig::test()));
+        runtime.block_on(api.anvil_set_balance(cached, balance)).unwrap();
+        endpoint_tx.send(handle.http_endpoint()).unwrap();
+        let _ = stop_rx.recv();
+        drop(handle);
+        runtime.shutdown_background();
+    });
+    let endpoint = endpoint_rx.recv().unwrap();
+
+    // populate the cache file from a first node
+    let (api, handle) = spawn(
+        NodeConfig::test().with_eth_rpc_url(Some(endpoint.clone())).with_warm_cache_from(&cache),
+    )
+    .await;
+    assert_eq!(handle.http_provider().get_balance(cached).await.unwrap(), balance);
+    api.backend.get_db().read().await.maybe_flush_cache().unwrap();
+    drop(handle);
+    assert!(cache.exists());
+
+    let (_api, handle) = spawn(
+        NodeConfig::test()
+            .with_eth_rpc_url(Some(endpoint))
+            .with_warm_cache_from(&cache)
+            .with_offline_fork_fallback(true),
+    )
+    .await;
+    let provider = handle.http_provider();
+
+    stop_tx.send(()).unwrap();
+    remote.join().unwrap();
+
+    // served from the preloaded cache without reaching the remote
+    assert_eq!(provider.get_balance(cached).await.unwrap(), balance);
+    let err = provider.get_balance(Address::random()).await.unwrap_err();
+    assert!(err.to_string().contains("offline"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_timestamp() {
+    // blocks are mined seconds apart, so the timestamp right after the block still resolves to it
+    for timestamp in [BLOCK_TIMESTAMP, BLOCK_TIMESTAMP + 1] {
+        let (api, _handle) = spawn(
+            NodeConfig::test()
+                .with_eth_rpc_url(Some(rpc::next_http_archive_rpc_url()))
+                .with_fork_timestamp(Some(timestamp)),
+        )
+        .await;
+        let info = api.anvil_node_info().await.unwrap();
+        assert_eq!(info.fork_config.fork_block_number, Some(BLOCK_NUMBER));
+    }
+
+    // a timestamp in the future forks the latest block
+    let (api, _handle) = spawn(
+        NodeConfig::test()
+            .with_eth_rpc_url(Some(rpc::next_http_archive_rpc_url()))
+            .with_fork_timestamp(Some(u64::MAX)),
+    )
+    .await;
+    let forked = api.anvil_node_info().await.unwrap().fork_config.fork_block_number.unwrap();
+    let remote = api.anvil_remote_block_number().await.unwrap().unwrap();
+    assert!(forked > BLOCK_NUMBER && forked <= remote);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_reset_with_options() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let impersonated = Address::random();
+    let contract = Address::random();
+    let balance = U256::from(1e18);
+    let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+    api.anvil_impersonate_account(impersonated).await.unwrap();
+    api.anvil_set_balance(impersonated, balance).await.unwrap();
+    api.anvil_set_code(contract, code.clone()).await.unwrap();
+
+    let options = ResetOptions {
+        preserve_balances: true,
+        preserve_impersonations: true,
+        preserve_code: true,
+    };
+    // a failed reset keeps the overrides for the next one
+    let unreachable = Forking {
+        json_rpc_url: Some("http://127.0.0.1:1".to_string()),
+        block_number: Some(BLOCK_NUMBER),
+    };
+    assert!(api.anvil_reset_with_options(unreachable, options).await.is_err());
+
+    api.anvil_reset_with_options(
+        Forking { json_rpc_url: None, block_number: Some(BLOCK_NUMBER + 100) },
+        options,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(provider.get_block_number().await.unwrap(), BLOCK_NUMBER + 100);
+    assert_eq!(provider.get_balance(impersonated).await.unwrap(), balance);
+    assert_eq!(provider.get_code_at(contract).await.unwrap(), code);
+
+    // the account is still impersonated on top of the new fork block
+    let tx = TransactionRequest::default().from(impersonated).to(contract).value(U256::from(1));
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+    assert_eq!(receipt.from, impersonated);
+
+    // without options the overrides are dropped
+    api.anvil_reset_with_options(
+        Forking { json_rpc_url: None, block_number: Some(BLOCK_NUMBER) },
+        ResetOptions::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(provider.get_balance(impersonated).await.unwrap(), U256::ZERO);
+    assert!(provider.get_code_at(contract).await.unwrap().is_empty());
+}
+
+// <https://etherscan.io/block/14608400>
+#[tokio::test(flavor = "multi_thread")]
+async fn test_difficulty_history() {
+    let (api, _handle) = spawn(fork_config()).await;
+    api.mine_one().await.unwrap();
+
+    let total_difficulty = U256::from(46_673_965_560_973_856_260_636u128);
+    let difficulty = U256::from(13_680_435_288_526_144u128);
+
+    let history = api
+        .anvil_difficulty_history(
+            BlockNumberOrTag::Number(BLOCK_NUMBER - 1),
+            BlockNumberOrTag::Number(BLOCK_NUMBER + 1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 3);
+
+    // pre-merge blocks are resolved from the remote
+    let parent = &history[0];
+    assert_eq!(parent.number, BLOCK_NUMBER - 1);
+    assert_eq!(parent.total_difficulty, Some(total_difficulty - difficulty));
+    assert_eq!(
+        history[1],
+        DifficultyHistoryEntry {
+            number: BLOCK_NUMBER,
+            difficulty,
+            total_difficulty: Some(total_difficulty),
+        }
+    );
+
+    // the mined block accumulates on top of the forked total difficulty
+    let mined = &history[2];
+    assert_eq!(mined.number, BLOCK_NUMBER + 1);
+    assert_eq!(mined.total_difficulty, Some(total_difficulty + mined.difficulty));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_details() {
+    let (_api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+
+    let details = handle.fork_details().unwrap();
+    let block = provider.get_block(BlockId::Number(BLOCK_NUMBER.into())).await.unwrap().unwrap();
+    assert_eq!(details.block_number, BLOCK_NUMBER);
+    assert_eq!(details.block_hash, block.header.hash);
+    assert_eq!(details.chain_id, 1);
+    assert_eq!(Some(details.base_fee), block.header.base_fee_per_gas);
+    assert_eq!(Some(details.eth_rpc_url), handle.config().eth_rpc_url);
+
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    assert!(handle.fork_details().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deal_erc721() {
+    let (api, handle) = spawn(fork_config().with_fork_block_number(Some(14812197u64))).await;
+    let provider = handle.http_provider();
+
+    let nouns_addr: Address = "0x9c8ff314c9bc7f6e59a9d9225fb22946427edc03".parse().unwrap();
+    let real_owner: Address = "0x052564eb0fd8b340803df55def89c25c432f43f4".parse().unwrap();
+    let token_id = U256::from(154u64);
+    let nouns = ERC721::new(nouns_addr, provider.clone());
+    let real_owner_balance = nouns.balanceOf(real_owner).call().await.unwrap();
+
+    let owner = Address::random();
+    api.anvil_deal_erc721(owner, nouns_addr, token_id).await.unwrap();
+
+    assert_eq!(nouns.ownerOf(token_id).call().await.unwrap(), owner);
+    assert_eq!(nouns.balanceOf(owner).call().await.unwrap(), U256::from(1));
+    assert_eq!(
+        nouns.balanceOf(real_owner).call().await.unwrap(),
+        real_owner_balance - U256::from(1)
+    );
+
+    // contracts without an `ownerOf` slot are rejected
+    let err = api.anvil_deal_erc721(owner, Address::random(), token_id).await.unwrap_err();
+    assert!(err.to_string().contains("no slot found"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_account_type() {
+    let (api, handle) = spawn(fork_config()).await;
+
+    let dev_account = handle.dev_accounts().next().unwrap();
+    let account = api.anvil_account_type(dev_account).await.unwrap();
+    assert_eq!(account.kind, AccountKind::Eoa);
+    assert_eq!(account.implementation, None);
+
+    // code is resolved from the remote
+    let weth = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    let account = api.anvil_account_type(weth).await.unwrap();
+    assert_eq!(account.kind, AccountKind::Contract);
+    assert_eq!(account.implementation, None);
+
+    let account = api.anvil_account_type(Address::random()).await.unwrap();
+    assert_eq!(account.kind, AccountKind::Empty);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fork_from_state_file() {
+    let (api, handle) = spawn(fork_config()).await;
+    let provider = handle.http_provider();
+    let accounts = handle.dev_accounts().collect::<Vec<_>>();
+
+    let funded = Address::random();
+    api.anvil_set_balance(funded, U256::from(1337)).await.unwrap();
+    let tx = TransactionRequest::default()
+        .from(accounts[0])
+        .to(accounts[1])
+        .value(U256::from(1_000_000u64));
+    provider.send_transaction(WithOtherFields::new(tx)).await.unwrap().get_receipt().await.unwrap();
+
+    let addresses = [accounts[0], accounts[1], funded];
+    let mut balances = Vec::new();
+    for address in addresses {
+        balances.push(api.balance(address, None).await.unwrap());
+    }
+
+    let tmp = tempfile::tempdir().unwrap();
+    let state_file = tmp.path().join("state.json");
+    let state = api.serialized_state(false).await.unwrap();
+    foundry_common::fs::write_json_file(&state_file, &state).unwrap();
+    drop(handle);
+
+    let (api, _handle) = spawn(NodeConfig::test().with_fork_state_file(state_file.clone())).await;
+    assert!(api.is_fork());
+    assert_eq!(api.chain_id(), 1);
+    assert_eq!(api.block_number().unwrap(), U256::from(BLOCK_NUMBER + 1));
+    for (address, balance) in addresses.into_iter().zip(balances) {
+        assert_eq!(api.balance(address, None).await.unwrap(), balance);
+    }
+
+    // requests that would need the remote fail without any network access
+    let err = api.anvil_remote_block_number().await.unwrap_err();
+    assert!(err.to_string().contains("no remote endpoint"), "{err}");
+
+    // files without the fork block, its env or the chain id can't be forked from
+    let mut without_block = state.clone();
+    without_block.blocks.clear();
+    let mut without_block_env = state.clone();
+    without_block_env.block = None;
+    let mut without_chain_id = state;
+    without_chain_id.chain_id = None;
+    for (state, expected) in [
+        (without_block, "doesn't contain the fork block"),
+        (without_block_env, "missing the `block` field"),
+        (without_chain_id, "missing the `chain_id` field"),
+    ] {
+        foundry_common::fs::write_json_file(&state_file, &state).unwrap();
+        let Err(err) =
+            anvil::try_spawn(NodeConfig::test().with_fork_state_file(state_file.clone())).await
+        else {
+            panic!("forked from an incomplete state file");
+        };
+        assert!(format!("{err:#}").contains(expected), "{err:#}");
+    }
+}
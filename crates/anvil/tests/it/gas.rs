@@ -2,11 +2,15 @@
 
 use crate::utils::http_provider_with_signer;
 use alloy_network::{EthereumWallet, TransactionBuilder};
-use alloy_primitives::{Address, U64, U256, uint};
+use alloy_primitives::{Address, U64, U256, bytes, uint};
 use alloy_provider::Provider;
-use alloy_rpc_types::{BlockId, TransactionRequest};
+use alloy_rpc_types::{BlockId, TransactionRequest, state::EvmOverrides};
 use alloy_serde::WithOtherFields;
-use anvil::{NodeConfig, eth::fees::INITIAL_BASE_FEE, spawn};
+use anvil::{
+    NodeConfig,
+    eth::fees::{GasPriceOracle, INITIAL_BASE_FEE},
+    spawn,
+};
 
 const GAS_TRANSFER: u64 = 21_000;
 
@@ -129,7 +133,7 @@ async fn test_basefee_empty_block() {
         .unwrap();
 
     // mine empty block
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let next_base_fee = provider
         .get_block(BlockId::latest())
@@ -259,3 +263,111 @@ async fn test_estimate_gas_empty_data() {
     assert!(gas_with_data > U256::from(GAS_TRANSFER));
     assert_eq!(gas_without_data, gas_with_empty_data);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_without_gas_metering() {
+    let (api, _handle) = spawn(NodeConfig::test().with_gas_limit(Some(100_000))).await;
+
+    // counts down from 10_000 in a loop, costing more gas than the block gas limit
+    let looper = Address::random();
+    api.anvil_set_code(looper, bytes!("0x6127105b600190038060035700")).await.unwrap();
+
+    let tx = WithOtherFields::new(TransactionRequest::default().to(looper));
+    assert!(api.call(tx.clone(), None, EvmOverrides::default()).await.is_err());
+
+    let out = api.anvil_call_no_gas(tx, None, EvmOverrides::default()).await.unwrap();
+    assert!(out.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_priority_fee_suggestion() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let gwei = 1_000_000_000u128;
+    let tips = [2 * gwei, 3 * gwei, 4 * gwei];
+    for tip in tips {
+        let tx = TransactionRequest::default()
+            .from(from)
+            .with_to(Address::random())
+            .with_max_fee_per_gas(INITIAL_BASE_FEE as u128 + tip)
+            .with_max_priority_fee_per_gas(tip);
+        provider
+            .send_transaction(WithOtherFields::new(tx))
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap();
+    }
+    // empty blocks don't lower the suggestion
+    api.anvil_mine(Some(U256::from(3)), None).await.unwrap();
+    // the fee history cache is updated by the node service
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let suggestion = api.max_priority_fee_per_gas().await.unwrap().to::<u128>();
+    assert!((tips[0]..=tips[2]).contains(&suggestion), "{suggestion}");
+    assert_eq!(suggestion, tips[1]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dynamic_gas_price_oracle() {
+    let (api, handle) =
+        spawn(NodeConfig::test().with_gas_price_oracle(GasPriceOracle::Dynamic)).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let initial = provider.get_gas_price().await.unwrap();
+
+    let tip = 50_000_000_000u128;
+    for _ in 0..3 {
+        let tx = TransactionRequest::default()
+            .from(from)
+            .with_to(Address::random())
+            .with_max_fee_per_gas(INITIAL_BASE_FEE as u128 + tip)
+            .with_max_priority_fee_per_gas(tip);
+        provider
+            .send_transaction(WithOtherFields::new(tx))
+            .await
+            .unwrap()
+            .get_receipt()
+            .await
+            .unwrap();
+    }
+    // the fee history cache is updated by the node service
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let suggestion = provider.get_gas_price().await.unwrap();
+    assert!(suggestion > initial, "{suggestion} <= {initial}");
+    assert!(suggestion >= tip);
+    assert_eq!(api.gas_price(), suggestion);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_ignore_block_limit() {
+    // loops 8192 times, using more than 200k gas
+    let code = bytes!("0x6120005b600190038060035700");
+    let target = Address::random();
+    let tx = WithOtherFields::new(TransactionRequest::default().with_to(target));
+
+    let (api, _handle) = spawn(NodeConfig::test().with_gas_limit(Some(100_000))).await;
+    api.anvil_set_code(target, code.clone()).await.unwrap();
+    let err = api.estimate_gas(tx.clone(), None, Default::default()).await.unwrap_err();
+    assert!(err.to_string().contains("gas required exceeds block gas limit of 100000"), "{err}");
+
+    let (api, _handle) = spawn(
+        NodeConfig::test().with_gas_limit(Some(100_000)).with_estimate_ignore_block_limit(true),
+    )
+    .await;
+    api.anvil_set_code(target, code).await.unwrap();
+    let estimate = api.estimate_gas(tx.clone(), None, Default::default()).await.unwrap();
+    assert!(estimate > U256::from(200_000), "{estimate}");
+    assert!(estimate < U256::from(300_000), "{estimate}");
+
+    // expands memory to 2^48 bytes, which can never succeed
+    api.anvil_set_code(target, bytes!("0x60006601000000000000005200")).await.unwrap();
+    let err = api.estimate_gas(tx, None, Default::default()).await.unwrap_err();
+    assert!(!err.to_string().contains("exceeds block gas limit"), "{err}");
+    assert!(err.to_string().contains("Out of gas"), "{err}");
+}
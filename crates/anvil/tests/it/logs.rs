@@ -5,7 +5,7 @@
     utils::{http_provider_with_signer, ws_provider_with_signer},
 };
 use alloy_network::EthereumWallet;
-use alloy_primitives::{B256, map::B256HashSet};
+use alloy_primitives::{B256, U256, map::B256HashSet};
 use alloy_provider::Provider;
 use alloy_rpc_types::{BlockNumberOrTag, Filter};
 use anvil::{NodeConfig, spawn};
@@ -102,7 +102,7 @@ async fn get_all_events() {
     let tx = contract.setValue("hi".to_string()).from(account);
     for _ in 0..num_tx {
         let tx = tx.send().await.unwrap();
-        api.mine_one().await;
+        api.mine_one().await.unwrap();
         tx.get_receipt().await.unwrap();
     }
 
@@ -195,3 +195,21 @@ async fn watch_events() {
         assert_eq!(log.1.block_hash.unwrap(), hash);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_limit_log_block_range() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_log_block_range(2)).await;
+    let provider = handle.http_provider();
+
+    api.anvil_mine(Some(U256::from(5)), None).await.unwrap();
+
+    let filter = Filter::new().from_block(0).to_block(4);
+    let err = provider.get_logs(&filter).await.unwrap_err();
+    let err = err.as_error_resp().unwrap();
+    assert_eq!(err.code, -32005);
+    assert_eq!(err.message, "query exceeds max block range 2");
+
+    let filter = Filter::new().from_block(3).to_block(4);
+    let logs = provider.get_logs(&filter).await.unwrap();
+    assert!(logs.is_empty());
+}
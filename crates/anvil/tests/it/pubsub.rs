@@ -261,7 +261,7 @@ async fn test_sub_new_heads_fast() {
 
     let mut block_numbers = Vec::new();
     for _ in 0..num {
-        api.mine_one().await;
+        api.mine_one().await.unwrap();
         let block_number = blocks.next().await.unwrap().number;
         block_numbers.push(block_number);
     }
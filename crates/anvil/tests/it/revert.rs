@@ -28,6 +28,27 @@ async fn test_deploy_reverting() {
     assert!(!receipt.inner.inner.status());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deploy_allow_revert() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+
+    // reverts with `Error("nope")` copied from the end of the init code
+    let code = bytes!(
+        "6064600c60003960646000fd08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000"
+    );
+    let res = api.anvil_deploy_allow_revert(code, U256::ZERO).await.unwrap();
+    assert_eq!(res.revert_reason.as_deref(), Some("nope"));
+    assert!(res.address.is_none());
+    assert_eq!(provider.get_block_number().await.unwrap(), 0);
+
+    let res = api.anvil_deploy_allow_revert(bytes!("5f5ff3"), U256::ZERO).await.unwrap();
+    assert!(res.revert_reason.is_none());
+    let receipt =
+        provider.get_transaction_receipt(res.transaction_hash.unwrap()).await.unwrap().unwrap();
+    assert_eq!(receipt.contract_address, res.address);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_revert_messages() {
     sol!(
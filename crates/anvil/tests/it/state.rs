@@ -2,7 +2,7 @@
 
 use crate::abi::Greeter;
 use alloy_network::{ReceiptResponse, TransactionBuilder};
-use alloy_primitives::{Bytes, U256, Uint, address, b256, utils::Unit};
+use alloy_primitives::{Address, Bytes, U256, Uint, address, b256, utils::Unit};
 use alloy_provider::Provider;
 use alloy_rpc_types::{BlockId, TransactionRequest};
 use alloy_serde::WithOtherFields;
@@ -13,7 +13,7 @@
     primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE,
 };
 use serde_json::json;
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn can_load_state() {
@@ -22,8 +22,8 @@ async fn can_load_state() {
 
     let (api, _handle) = spawn(NodeConfig::test()).await;
 
-    api.mine_one().await;
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
+    api.mine_one().await.unwrap();
 
     let num = api.block_number().unwrap();
 
@@ -100,7 +100,7 @@ async fn test_make_sure_historical_state_is_not_cleared_on_dump() {
         .await
         .unwrap();
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let ser_state = api.serialized_state(true).await.unwrap();
     foundry_common::fs::write_json_file(&state_file, &ser_state).unwrap();
@@ -140,7 +140,7 @@ async fn can_preserve_historical_states_between_dump_and_load() {
 
     let change_greeting_blk_num = tx.block_number.unwrap();
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let ser_state = api.serialized_state(true).await.unwrap();
     foundry_common::fs::write_json_file(&state_file, &ser_state).unwrap();
@@ -262,7 +262,7 @@ async fn test_fork_load_state_with_greater_state_block() {
     )
     .await;
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block_number = api.block_number().unwrap();
 
@@ -752,3 +752,39 @@ async fn test_backward_compatibility_state_dump_deserialization_v1_2() {
     let contract_code = provider.get_code_at(contract_addr).await.unwrap();
     assert!(!contract_code.is_empty());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_autosave_and_resume_state() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state_file = tmp.path().join("autosave.json");
+
+    let (api, handle) = spawn(
+        NodeConfig::test().with_state_autosave(Duration::from_millis(100), state_file.clone()),
+    )
+    .await;
+    let provider = handle.http_provider();
+
+    let from = handle.dev_accounts().next().unwrap();
+    let to = Address::random();
+    let amount = U256::from(1337);
+    let tx = TransactionRequest::default().with_from(from).with_to(to).with_value(amount);
+    provider.send_transaction(WithOtherFields::new(tx)).await.unwrap().get_receipt().await.unwrap();
+    let from_balance = api.balance(from, None).await.unwrap();
+
+    // wait for at least one autosave to happen after the transfer
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(state_file.exists());
+
+    // simulate a restart
+    drop(handle);
+    let (api, _handle) = spawn(NodeConfig::test().with_state_autosave_load(&state_file)).await;
+
+    assert_eq!(api.balance(to, None).await.unwrap(), amount);
+    assert_eq!(api.balance(from, None).await.unwrap(), from_balance);
+
+    // a corrupt autosave fails the restart instead of silently starting from scratch
+    std::fs::write(&state_file, "{").unwrap();
+    let res = anvil::try_spawn(NodeConfig::test().with_state_autosave_load(&state_file)).await;
+    let Err(err) = res else { panic!("resumed from a corrupt autosave") };
+    assert!(format!("{err:#}").contains("failed to load autosaved state"), "{err:#}");
+}
@@ -979,6 +979,7 @@ async fn test_call_tracer_debug_trace_call_js_tracer() {
         .debug_trace_call(
             WithOtherFields::new(internal_call_tx),
             Some(BlockId::latest()),
+            alloy_rpc_types::state::EvmOverrides::default(),
             GethDebugTracingCallOptions::default()
                 .with_tracing_options(GethDebugTracingOptions::js_tracer(js_tracer_code)),
         )
@@ -1102,3 +1103,45 @@ async fn test_debug_trace_transaction_js_tracer() {
 
     assert_eq!(actual, expected);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receipt_with_trace() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let wallets = handle.dev_wallets().collect::<Vec<_>>();
+    let deployer: EthereumWallet = wallets[0].clone().into();
+    let provider = http_provider_with_signer(&handle.http_endpoint(), deployer);
+
+    let simple_storage_contract =
+        SimpleStorage::deploy(&provider, "init value".to_string()).await.unwrap();
+    let receipt = simple_storage_contract
+        .setValue("bar".to_string())
+        .send()
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let opts = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugTracerType::from(GethDebugBuiltInTracerType::CallTracer));
+    let receipt_with_trace =
+        api.anvil_receipt_with_trace(receipt.transaction_hash, opts).await.unwrap().unwrap();
+
+    let inner = receipt_with_trace.receipt.inner.as_receipt_with_bloom();
+    assert!(inner.receipt.status.coerce_status());
+    assert_eq!(receipt_with_trace.receipt.transaction_hash, receipt.transaction_hash);
+    match receipt_with_trace.trace {
+        GethTrace::CallTracer(call_frame) => {
+            assert_eq!(call_frame.to, Some(*simple_storage_contract.address()));
+            assert!(call_frame.error.is_none());
+        }
+        _ => unreachable!(),
+    }
+
+    assert!(
+        api.anvil_receipt_with_trace(Default::default(), Default::default())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -2,9 +2,14 @@
     abi::{Greeter, Multicall, SimpleStorage},
     utils::{connect_pubsub, http_provider_with_signer},
 };
+use alloy_consensus::{SignableTransaction, TxLegacy};
 use alloy_hardforks::EthereumHardfork;
-use alloy_network::{EthereumWallet, TransactionBuilder, TransactionResponse};
-use alloy_primitives::{Address, Bytes, FixedBytes, U256, address, hex, map::B256HashSet};
+use alloy_network::{
+    EthereumWallet, ReceiptResponse, TransactionBuilder, TransactionResponse, TxSignerSync,
+};
+use alloy_primitives::{
+    Address, Bytes, FixedBytes, I256, TxKind, U256, address, hex, map::B256HashSet,
+};
 use alloy_provider::{Provider, WsConnect};
 use alloy_rpc_types::{
     AccessList, AccessListItem, BlockId, BlockNumberOrTag, BlockOverrides, BlockTransactions,
@@ -87,7 +92,7 @@ async fn can_order_transactions() {
     let tx_higher = provider.send_transaction(tx).await.unwrap();
 
     // manually mine the block with the transactions
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let higher_price = tx_higher.get_receipt().await.unwrap().transaction_hash;
     let lower_price = tx_lower.get_receipt().await.unwrap().transaction_hash;
@@ -98,6 +103,43 @@ async fn can_order_transactions() {
     assert_eq!(block.transactions, BlockTransactions::Hashes(vec![higher_price, lower_price]))
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_get_balance_diff() {
+    // without a base fee nothing is burnt, so all balance changes add up to zero
+    let (api, handle) = spawn(NodeConfig::test().with_base_fee(Some(0))).await;
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+    let coinbase = Address::random();
+    api.anvil_set_coinbase(coinbase).await.unwrap();
+
+    let amount = U256::from(1_000);
+    let tx = TransactionRequest::default()
+        .to(to)
+        .value(amount)
+        .from(from)
+        .max_fee_per_gas(10)
+        .max_priority_fee_per_gas(10);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    let fee = I256::try_from(receipt.gas_used as u128 * receipt.effective_gas_price).unwrap();
+    let amount = I256::from_raw(amount);
+
+    let diff = api.anvil_balance_diff(receipt.transaction_hash).unwrap();
+    assert_eq!(diff.len(), 3);
+    assert_eq!(diff[&from], -(amount + fee));
+    assert_eq!(diff[&to], amount);
+    assert_eq!(diff[&coinbase], fee);
+    assert_eq!(diff.values().copied().fold(I256::ZERO, |acc, diff| acc + diff), I256::ZERO);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_respect_nonces() {
     let (api, handle) = spawn(NodeConfig::test()).await;
@@ -140,6 +182,37 @@ async fn can_respect_nonces() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_reject_future_nonce() {
+    let (api, handle) = spawn(NodeConfig::test().with_queue_future_nonce(false)).await;
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+
+    let nonce = provider.get_transaction_count(from).await.unwrap();
+    let tx = TransactionRequest::default().to(to).value(U256::from(1)).from(from);
+
+    // a gapped nonce is rejected instead of queued
+    let err = provider
+        .send_transaction(WithOtherFields::new(tx.clone().nonce(nonce + 1)))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("nonce too high"), "{err}");
+    assert_eq!(api.txpool_status().await.unwrap().queued, 0);
+
+    // the next nonce is still accepted
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx.nonce(nonce)))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_replace_transaction() {
     let (api, handle) = spawn(NodeConfig::test()).await;
@@ -171,7 +244,7 @@ async fn can_replace_transaction() {
 
     let higher_tx_hash = *higher_priced_pending_tx.tx_hash();
     // mine exactly one block
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     let block = provider.get_block(1.into()).await.unwrap().unwrap();
 
@@ -191,6 +264,129 @@ async fn can_replace_transaction() {
     // );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_track_transaction_replacements() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+
+    // disable auto mining
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+
+    let nonce = provider.get_transaction_count(from).await.unwrap();
+    let gas_price = provider.get_gas_price().await.unwrap();
+
+    // nothing replaced yet
+    assert!(api.anvil_tx_replacements(from, U256::from(nonce)).unwrap().is_empty());
+
+    let tx = TransactionRequest::default().to(to).value(U256::from(1)).from(from).nonce(nonce);
+    let mut tx = WithOtherFields::new(tx);
+
+    tx.set_gas_price(gas_price);
+    let lower_priced_pending_tx = provider.send_transaction(tx.clone()).await.unwrap();
+
+    tx.set_gas_price(gas_price + 1);
+    let higher_priced_pending_tx = provider.send_transaction(tx).await.unwrap();
+
+    let replacements = api.anvil_tx_replacements(from, U256::from(nonce)).unwrap();
+    assert_eq!(
+        replacements,
+        vec![*lower_priced_pending_tx.tx_hash(), *higher_priced_pending_tx.tx_hash()]
+    );
+
+    // other slots are unaffected
+    assert!(api.anvil_tx_replacements(from, U256::from(nonce + 1)).unwrap().is_empty());
+    assert!(api.anvil_tx_replacements(to, U256::from(nonce)).unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_cap_tx_replacement_history() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    api.anvil_set_auto_mine(false).await.unwrap();
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+    let nonce = provider.get_transaction_count(from).await.unwrap();
+    let gas_price = provider.get_gas_price().await.unwrap();
+
+    let mut hashes = Vec::new();
+    for bump in 0..70 {
+        let tx = TransactionRequest::default().to(to).value(U256::from(1)).from(from).nonce(nonce);
+        let mut tx = WithOtherFields::new(tx);
+        tx.set_gas_price(gas_price + bump);
+        hashes.push(*provider.send_transaction(tx).await.unwrap().tx_hash());
+    }
+
+    // only the most recent transactions of the slot are kept
+    let replacements = api.anvil_tx_replacements(from, U256::from(nonce)).unwrap();
+    assert_eq!(replacements, hashes[hashes.len() - 64..]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_limit_transactions_per_block() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_txs_per_block(2)).await;
+
+    // disable auto mining
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+
+    for _ in 0..5 {
+        let tx = TransactionRequest::default().to(to).value(U256::from(1)).from(from);
+        provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    }
+
+    for (number, expected) in [(1u64, 2usize), (2, 2), (3, 1)] {
+        api.mine_one().await.unwrap();
+        let block = provider.get_block(number.into()).await.unwrap().unwrap();
+        assert_eq!(block.transactions.len(), expected);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_estimate_transaction_inclusion() {
+    let (api, handle) = spawn(NodeConfig::test().with_max_txs_per_block(1)).await;
+
+    // disable auto mining
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    let provider = handle.http_provider();
+
+    let accounts = handle.dev_wallets().collect::<Vec<_>>();
+    let to = accounts[2].address();
+    let gas_price = provider.get_gas_price().await.unwrap();
+
+    let mut tx = WithOtherFields::new(
+        TransactionRequest::default().to(to).value(U256::from(1)).from(accounts[0].address()),
+    );
+    tx.set_gas_price(gas_price);
+    let low_fee_tx = *provider.send_transaction(tx).await.unwrap().tx_hash();
+
+    let mut tx = WithOtherFields::new(
+        TransactionRequest::default().to(to).value(U256::from(1)).from(accounts[1].address()),
+    );
+    tx.set_gas_price(gas_price * 2);
+    let high_fee_tx = *provider.send_transaction(tx).await.unwrap().tx_hash();
+
+    let low_fee_estimate = api.anvil_inclusion_estimate(low_fee_tx).unwrap().unwrap();
+    let high_fee_estimate = api.anvil_inclusion_estimate(high_fee_tx).unwrap().unwrap();
+    assert_eq!(high_fee_estimate, 1);
+    assert!(low_fee_estimate > high_fee_estimate);
+
+    // unknown transactions have no estimate
+    assert!(api.anvil_inclusion_estimate(Default::default()).unwrap().is_none());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_reject_too_high_gas_limits() {
     let (api, handle) = spawn(NodeConfig::test()).await;
@@ -285,7 +481,7 @@ async fn can_reject_underpriced_replacement() {
     assert!(replacement_err.to_string().contains("replacement transaction underpriced"));
 
     // mine exactly one block
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let higher_priced_receipt = higher_priced_pending_tx.get_receipt().await.unwrap();
 
     // ensure that only the higher priced tx was mined
@@ -430,7 +626,7 @@ async fn can_call_greeter_historic() {
     assert_eq!("Another Message", greeting);
 
     // min
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
 
     // returns previous state
     let greeting =
@@ -702,7 +898,7 @@ async fn can_get_pending_transaction() {
     let pending = provider.get_transaction_by_hash(*tx.tx_hash()).await;
     assert!(pending.is_ok());
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let mined = provider.get_transaction_by_hash(*tx.tx_hash()).await.unwrap().unwrap();
 
     assert_eq!(mined.tx_hash(), pending.unwrap().unwrap().tx_hash());
@@ -753,7 +949,7 @@ async fn can_get_raw_transaction() {
     let res1 = api.raw_transaction(*tx.tx_hash()).await;
     assert!(res1.is_ok());
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let res2 = api.raw_transaction(*tx.tx_hash()).await;
 
     assert_eq!(res1.unwrap(), res2.unwrap());
@@ -832,7 +1028,7 @@ async fn includes_pending_tx_for_transaction_count() {
         assert_eq!(nonce, idx);
     }
 
-    api.mine_one().await;
+    api.mine_one().await.unwrap();
     let nonce = provider.get_transaction_count(from).block_id(BlockId::pending()).await.unwrap();
     assert_eq!(nonce, tx_count);
 }
@@ -1088,6 +1284,37 @@ fn assert_access_list_eq(a: AccessList, b: AccessList) {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn can_disable_access_list_gas() {
+    async fn balance_call_gas_used(access_list_gas: bool) -> u64 {
+        let (_api, handle) = spawn(NodeConfig::test().with_access_list_gas(access_list_gas)).await;
+        let provider = handle.http_provider();
+
+        let sender = handle.dev_accounts().next().unwrap();
+        let other_acc = Address::random();
+        let multicall = Multicall::deploy(provider.clone()).await.unwrap();
+
+        let tx = TransactionRequest::default()
+            .from(sender)
+            .to(*multicall.address())
+            .with_input(multicall.getEthBalance(other_acc).calldata().to_owned())
+            .with_access_list(AccessList::from(vec![AccessListItem {
+                address: other_acc,
+                storage_keys: vec![],
+            }]));
+        let tx = WithOtherFields::new(tx);
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        receipt.gas_used
+    }
+
+    let with_discount = balance_call_gas_used(true).await;
+    let without_discount = balance_call_gas_used(false).await;
+
+    // with the discount, the listed address costs 2400 upfront and 100 for the warm `BALANCE`,
+    // without it the `BALANCE` is a cold access costing 2600
+    assert_eq!(without_discount - with_discount, 100);
+}
+
 // ensures that the gas estimate is running on pending block by default
 #[tokio::test(flavor = "multi_thread")]
 async fn estimates_gas_on_pending_by_default() {
@@ -1308,3 +1535,336 @@ async fn estimates_gas_prague() {
         .with_to(address!("0x70997970c51812dc3a010c7d01b50e0d17dc79c8"));
     api.estimate_gas(WithOtherFields::new(req), None, EvmOverrides::default()).await.unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_limit_initcode_size() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // one byte over the EIP-3860 limit of 49152 bytes
+    let initcode = Bytes::from(vec![0u8; 49153]);
+    let tx = TransactionRequest::default()
+        .from(from)
+        .with_deploy_code(initcode.clone())
+        .with_gas_limit(1_000_000);
+    let err = provider.send_transaction(WithOtherFields::new(tx.clone())).await.unwrap_err();
+    assert!(err.to_string().contains("max initcode size exceeded"), "{err}");
+
+    api.anvil_set_initcode_size_limit(Some(U256::from(49153))).unwrap();
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+
+    let (_api, handle) = spawn(NodeConfig::test().with_initcode_size_limit(Some(1024))).await;
+    let provider = handle.http_provider();
+    let tx = TransactionRequest::default()
+        .from(from)
+        .with_deploy_code(initcode.slice(..1025))
+        .with_gas_limit(1_000_000);
+    let err = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap_err();
+    assert!(err.to_string().contains("max initcode size exceeded"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejects_invalid_eip1559_fees() {
+    let base_fee = 1_000_000_000u128;
+    let (_api, handle) = spawn(NodeConfig::test().with_base_fee(Some(base_fee as u64))).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default()
+        .from(from)
+        .with_to(Address::random())
+        .with_value(U256::from(1))
+        .with_gas_limit(21_000);
+
+    // tip above the fee cap is rejected as is, not clamped to the fee cap
+    let inverted =
+        tx.clone().with_max_fee_per_gas(base_fee * 2).with_max_priority_fee_per_gas(base_fee * 3);
+    let err = provider.send_transaction(WithOtherFields::new(inverted)).await.unwrap_err();
+    assert!(err.to_string().contains("max priority fee per gas higher than max fee per gas"));
+
+    let underpriced =
+        tx.clone().with_max_fee_per_gas(base_fee - 1).with_max_priority_fee_per_gas(0);
+    let err = provider.send_transaction(WithOtherFields::new(underpriced)).await.unwrap_err();
+    assert!(err.to_string().contains("max fee per gas less than block base fee"));
+
+    let tx = tx.with_max_fee_per_gas(base_fee * 2).with_max_priority_fee_per_gas(base_fee);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_mine_on_send() {
+    let (_api, handle) = spawn(NodeConfig::test().with_mine_on_send(true)).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default()
+        .from(from)
+        .with_to(Address::random())
+        .with_value(U256::from(1));
+    let hash = *provider.send_transaction(WithOtherFields::new(tx)).await.unwrap().tx_hash();
+
+    // mined before the send call returned
+    let receipt = provider.get_transaction_receipt(hash).await.unwrap().unwrap();
+    assert_eq!(receipt.block_number, Some(1));
+    assert_eq!(provider.get_block_number().await.unwrap(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_reset_nonce_for_deployment() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let expected = api.anvil_reset_nonce_for_deployment(from, U256::from(42)).await.unwrap();
+    assert_eq!(expected, from.create(42));
+
+    let greeter = Greeter::deploy_builder(provider.clone(), "Hello World!".to_string()).from(from);
+    let tx = WithOtherFields::new(
+        TransactionRequest::default().from(from).with_input(greeter.calldata().to_owned()),
+    );
+    let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+    assert_eq!(receipt.contract_address, Some(expected));
+    assert_eq!(provider.get_transaction_count(from).await.unwrap(), 43);
+
+    // the nonce can also be moved back
+    let expected = api.anvil_reset_nonce_for_deployment(from, U256::from(7)).await.unwrap();
+    let address = greeter.deploy().await.unwrap();
+    assert_eq!(address, expected);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_restrict_allowed_tx_types() {
+    let (_api, handle) = spawn(NodeConfig::test().with_allowed_tx_types(vec![2])).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default().from(from).with_to(Address::random());
+
+    let legacy = tx.clone().with_gas_price(provider.get_gas_price().await.unwrap());
+    let err = provider.send_transaction(WithOtherFields::new(legacy)).await.unwrap_err();
+    assert!(err.to_string().contains("transaction type 0 is not allowed"), "{err}");
+
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert_eq!(receipt.inner.inner.r#type, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_require_eip155() {
+    let (_api, handle) = spawn(NodeConfig::test().with_require_eip155(true)).await;
+    let provider = handle.http_provider();
+    let wallet = handle.dev_wallets().next().unwrap().with_chain_id(None);
+
+    let mut tx = TxLegacy {
+        chain_id: None,
+        gas_price: provider.get_gas_price().await.unwrap(),
+        gas_limit: 21_000,
+        to: TxKind::Call(Address::random()),
+        ..Default::default()
+    };
+    let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+    let mut encoded = Vec::new();
+    tx.into_signed(signature).eip2718_encode(&mut encoded);
+
+    let err = provider.send_raw_transaction(&encoded).await.unwrap_err();
+    assert!(err.to_string().contains("only replay-protected (EIP-155)"), "{err}");
+
+    // replay protected legacy transactions are accepted
+    let tx = TransactionRequest::default()
+        .from(wallet.address())
+        .with_to(Address::random())
+        .with_gas_price(provider.get_gas_price().await.unwrap());
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_cap_transaction_gas() {
+    let (api, handle) = spawn(NodeConfig::test()).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    api.anvil_set_transaction_gas_cap(U256::from(100_000)).await.unwrap();
+
+    let tx = TransactionRequest::default()
+        .with_from(from)
+        .with_to(Address::random())
+        .with_gas_limit(100_001);
+    let err = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap_err();
+    assert!(err.to_string().contains("transaction gas cap"), "{err}");
+
+    let tx = TransactionRequest::default()
+        .with_from(from)
+        .with_to(Address::random())
+        .with_gas_limit(100_000);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_delay_transaction_inclusion() {
+    let (_api, handle) =
+        spawn(NodeConfig::test().with_inclusion_delay(Duration::from_secs(2))).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(Address::random());
+    let pending = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+    let hash = *pending.tx_hash();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(provider.get_transaction_receipt(hash).await.unwrap().is_none());
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let receipt = provider.get_transaction_receipt(hash).await.unwrap().unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delayed_transactions_are_validated_by_the_pool() {
+    let config = NodeConfig::test()
+        .with_inclusion_delay(Duration::from_secs(2))
+        .with_queue_future_nonce(false);
+    let (_api, handle) = spawn(config).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(Address::random()).with_nonce(5);
+    let err = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap_err();
+    assert!(err.to_string().contains("nonce too high"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_delay_raw_transaction_inclusion() {
+    let (_api, handle) =
+        spawn(NodeConfig::test().with_inclusion_delay(Duration::from_secs(2))).await;
+    let provider = handle.http_provider();
+    let wallet = handle.dev_wallets().next().unwrap();
+
+    let mut tx = TxLegacy {
+        chain_id: Some(provider.get_chain_id().await.unwrap()),
+        gas_price: provider.get_gas_price().await.unwrap(),
+        gas_limit: 21_000,
+        to: TxKind::Call(Address::random()),
+        ..Default::default()
+    };
+    let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+    let mut encoded = Vec::new();
+    tx.into_signed(signature).eip2718_encode(&mut encoded);
+
+    let pending = provider.send_raw_transaction(&encoded).await.unwrap();
+    let hash = *pending.tx_hash();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(provider.get_transaction_receipt(hash).await.unwrap().is_none());
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let receipt = provider.get_transaction_receipt(hash).await.unwrap().unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_cap_transaction_fee() {
+    let cap = U256::from(10u128.pow(18));
+    let (_api, handle) = spawn(NodeConfig::test().with_max_fee_per_tx(cap)).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    // 21_000 * 100_000 gwei = 2.1 ether
+    let tx = TransactionRequest::default()
+        .with_from(from)
+        .with_to(Address::random())
+        .with_gas_limit(21_000)
+        .with_max_fee_per_gas(100_000_000_000_000)
+        .with_max_priority_fee_per_gas(1);
+    let err = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap_err();
+    assert!(err.to_string().contains("exceeds the configured cap"), "{err}");
+
+    let tx = TransactionRequest::default()
+        .with_from(from)
+        .with_to(Address::random())
+        .with_gas_limit(21_000)
+        .with_max_fee_per_gas(10_000_000_000)
+        .with_max_priority_fee_per_gas(1);
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+    assert!(receipt.status());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn can_send_transaction_webhook() {
+    // mock endpoint forwarding every payload it receives
+    let (payload_tx, mut payloads) = tokio::sync::mpsc::unbounded_channel();
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post(move |axum::Json(payload): axum::Json<serde_json::Value>| {
+            let _ = payload_tx.send(payload);
+            async {}
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let (_api, handle) = spawn(NodeConfig::test().with_tx_webhook(url)).await;
+    let provider = handle.http_provider();
+    let from = handle.dev_accounts().next().unwrap();
+
+    let tx = TransactionRequest::default().with_from(from).with_to(Address::random());
+    let receipt = provider
+        .send_transaction(WithOtherFields::new(tx))
+        .await
+        .unwrap()
+        .get_receipt()
+        .await
+        .unwrap();
+
+    let mut events = Vec::new();
+    for _ in 0..2 {
+        let payload = timeout(Duration::from_secs(10), payloads.recv()).await.unwrap().unwrap();
+        assert_eq!(payload["transactionHash"], receipt.transaction_hash.to_string());
+        events.push(payload);
+    }
+    events.sort_by_key(|payload| payload["event"].as_str().unwrap().to_string());
+    assert_eq!(events[0]["event"], "mined");
+    assert_eq!(events[0]["blockNumber"], receipt.block_number.unwrap());
+    assert_eq!(events[1]["event"], "received");
+    assert!(events[1].get("blockNumber").is_none());
+}
@@ -6,6 +6,7 @@
 use alloy_rpc_types::TransactionRequest;
 use alloy_serde::WithOtherFields;
 use anvil::{NodeConfig, spawn};
+use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn geth_txpool() {
@@ -54,3 +55,29 @@ async fn geth_txpool() {
         assert!(content.contains_key(&nonce.to_string()));
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn evicts_expired_transactions() {
+    let (api, handle) = spawn(NodeConfig::test().with_tx_ttl(Duration::from_millis(500))).await;
+    let provider = handle.http_provider();
+
+    api.anvil_set_auto_mine(false).await.unwrap();
+
+    let account = provider.get_accounts().await.unwrap().remove(0);
+    let tx = TransactionRequest::default().with_to(account).with_from(account);
+
+    // one ready transaction and one that's queued because of a nonce gap
+    let ready = provider.send_transaction(WithOtherFields::new(tx.clone())).await.unwrap();
+    let _ = provider.send_transaction(WithOtherFields::new(tx.with_nonce(2))).await.unwrap();
+
+    let status = provider.txpool_status().await.unwrap();
+    assert_eq!(status.pending, 1);
+    assert_eq!(status.queued, 1);
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    let status = provider.txpool_status().await.unwrap();
+    assert_eq!(status.pending, 0);
+    assert_eq!(status.queued, 0);
+    assert!(provider.get_transaction_by_hash(*ready.tx_hash()).await.unwrap().is_none());
+}
@@ -1,9 +1,11 @@
 //! Provider-related instantiation and usage utilities.
 
+pub mod offline_transport;
 pub mod runtime_transport;
 
 use crate::{
-    ALCHEMY_FREE_TIER_CUPS, REQUEST_TIMEOUT, provider::runtime_transport::RuntimeTransportBuilder,
+    ALCHEMY_FREE_TIER_CUPS, REQUEST_TIMEOUT,
+    provider::{offline_transport::OfflineTransport, runtime_transport::RuntimeTransportBuilder},
 };
 use alloy_provider::{
     Identity, ProviderBuilder as AlloyProviderBuilder, RootProvider,
@@ -81,6 +83,12 @@ pub fn try_get_http_provider(builder: impl AsRef<str>) -> Result<RetryProvider>
     ProviderBuilder::new(builder.as_ref()).build()
 }
 
+/// Constructs a provider without a remote endpoint, every request fails with an error naming
+/// `reason` instead of being sent anywhere.
+pub fn get_offline_provider(reason: impl Into<String>) -> RetryProvider {
+    RootProvider::new(ClientBuilder::default().transport(OfflineTransport::new(reason), true))
+}
+
 /// Helper type to construct a `RetryProvider`
 #[derive(Debug)]
 pub struct ProviderBuilder {
@@ -98,6 +106,12 @@ pub struct ProviderBuilder {
     is_local: bool,
     /// Whether to accept invalid certificates.
     accept_invalid_certs: bool,
+    /// The maximum number of concurrent HTTP requests, if limited.
+    max_concurrent_requests: Option<usize>,
+    /// Whether to use HTTP/2 with prior knowledge for `http://` connections.
+    http2: bool,
+    /// Whether requests fail right away once the endpoint couldn't be reached.
+    fail_fast_when_offline: bool,
 }
 
 impl ProviderBuilder {
@@ -148,6 +162,9 @@ pub fn new(url_str: &str) -> Self {
             headers: vec![],
             is_local,
             accept_invalid_certs: false,
+            max_concurrent_requests: None,
+            http2: false,
+            fail_fast_when_offline: false,
         }
     }
 
@@ -251,6 +268,35 @@ pub fn accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
         self
     }
 
+    /// Sets the maximum number of concurrent HTTP requests. At most as many idle connections are
+    /// kept open per host.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Sets the maximum number of concurrent HTTP requests. If `None`, defaults to the already-set
+    /// value.
+    pub fn maybe_max_concurrent_requests(mut self, max_concurrent_requests: Option<usize>) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.or(self.max_concurrent_requests);
+        self
+    }
+
+    /// Sets whether to use HTTP/2 with prior knowledge for `http://` connections, multiplexing
+    /// concurrent requests over a single connection. The endpoint must support HTTP/2.
+    /// `https://` connections negotiate the protocol via ALPN regardless.
+    pub fn http2(mut self, http2: bool) -> Self {
+        self.http2 = http2;
+        self
+    }
+
+    /// Sets whether every request fails right away once a request couldn't reach the endpoint,
+    /// instead of each request waiting for the timeout.
+    pub fn fail_fast_when_offline(mut self, fail_fast_when_offline: bool) -> Self {
+        self.fail_fast_when_offline = fail_fast_when_offline;
+        self
+    }
+
     /// Constructs the `RetryProvider` taking all configs into account.
     pub fn build(self) -> Result<RetryProvider> {
         let Self {
@@ -264,6 +310,9 @@ pub fn build(self) -> Result<RetryProvider> {
             headers,
             is_local,
             accept_invalid_certs,
+            max_concurrent_requests,
+            http2,
+            fail_fast_when_offline,
         } = self;
         let url = url?;
 
@@ -275,6 +324,9 @@ pub fn build(self) -> Result<RetryProvider> {
             .with_headers(headers)
             .with_jwt(jwt)
             .accept_invalid_certs(accept_invalid_certs)
+            .with_max_concurrent_requests(max_concurrent_requests)
+            .with_http2(http2)
+            .with_fail_fast_when_offline(fail_fast_when_offline)
             .build();
         let client = ClientBuilder::default().layer(retry_layer).transport(transport, is_local);
 
@@ -309,6 +361,9 @@ pub fn build_with_wallet(self, wallet: EthereumWallet) -> Result<RetryProviderWi
             headers,
             is_local,
             accept_invalid_certs,
+            max_concurrent_requests,
+            http2,
+            fail_fast_when_offline,
         } = self;
         let url = url?;
 
@@ -320,6 +375,9 @@ pub fn build_with_wallet(self, wallet: EthereumWallet) -> Result<RetryProviderWi
             .with_headers(headers)
             .with_jwt(jwt)
             .accept_invalid_certs(accept_invalid_certs)
+            .with_max_concurrent_requests(max_concurrent_requests)
+            .with_http2(http2)
+            .with_fail_fast_when_offline(fail_fast_when_offline)
             .build();
 
         let client = ClientBuilder::default().layer(retry_layer).transport(transport, is_local);
@@ -364,6 +422,7 @@ fn resolve_path(path: &Path) -> Result<PathBuf, ()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_provider::Provider;
 
     #[test]
     fn can_auto_correct_missing_prefix() {
@@ -373,4 +432,49 @@ fn can_auto_correct_missing_prefix() {
         let url = builder.url.unwrap();
         assert_eq!(url, Url::parse("http://localhost:8545").unwrap());
     }
+
+    #[tokio::test]
+    async fn max_concurrent_requests_bounds_in_flight_requests() {
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+        let handler = {
+            let (active, max_active) = (active.clone(), max_active.clone());
+            axum::routing::post(move |axum::Json(req): axum::Json<serde_json::Value>| {
+                let (active, max_active) = (active.clone(), max_active.clone());
+                async move {
+                    let current = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_active.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let id = &req["id"];
+                    axum::Json(serde_json::json!({"jsonrpc": "2.0", "id": id, "result": "0x1"}))
+                }
+            })
+        };
+        let server = tokio::spawn(async move {
+            axum::serve(listener, handler.into_make_service()).await.unwrap()
+        });
+
+        let provider = ProviderBuilder::new(&url).max_concurrent_requests(2).build().unwrap();
+        let requests = (0..8)
+            .map(|_| {
+                let provider = provider.clone();
+                tokio::spawn(async move { provider.get_block_number().await })
+            })
+            .collect::<Vec<_>>();
+        for request in requests {
+            assert_eq!(request.await.unwrap().unwrap(), 1);
+        }
+        assert_eq!(max_active.load(Ordering::SeqCst), 2);
+
+        server.abort();
+    }
 }
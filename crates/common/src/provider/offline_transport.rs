@@ -0,0 +1,41 @@
+//! Transport without a remote endpoint that fails every request.
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// A transport that isn't connected to any endpoint.
+///
+/// Every request fails with an error naming why there is no endpoint, which is used for providers
+/// of nodes that must never reach the network, e.g. a fork loaded from a state dump.
+#[derive(Clone, Debug)]
+pub struct OfflineTransport {
+    /// Why there is no endpoint, included in the error of every request.
+    reason: Arc<str>,
+}
+
+impl OfflineTransport {
+    /// Creates a new transport that fails every request because of `reason`.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self { reason: reason.into().into() }
+    }
+}
+
+impl tower::Service<RequestPacket> for OfflineTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: RequestPacket) -> Self::Future {
+        let err = TransportErrorKind::custom_str(&format!("no remote endpoint: {}", self.reason));
+        Box::pin(async move { Err(err) })
+    }
+}
@@ -12,9 +12,17 @@
 use alloy_transport_ipc::IpcConnect;
 use alloy_transport_ws::WsConnect;
 use reqwest::header::{HeaderName, HeaderValue};
-use std::{fmt, path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    fmt,
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use tower::Service;
 use url::Url;
 
@@ -82,6 +90,16 @@ pub struct RuntimeTransport {
     timeout: std::time::Duration,
     /// Whether to accept invalid certificates.
     accept_invalid_certs: bool,
+    /// The maximum number of concurrent HTTP requests, if limited.
+    max_concurrent_requests: Option<usize>,
+    /// Bounds the number of concurrent HTTP requests to `max_concurrent_requests`.
+    connection_limit: Option<Arc<Semaphore>>,
+    /// Whether to use HTTP/2 with prior knowledge for `http://` connections.
+    http2: bool,
+    /// Whether requests fail without being sent once a request couldn't reach the endpoint.
+    fail_fast_when_offline: bool,
+    /// Set once a request couldn't reach the endpoint.
+    offline: Arc<AtomicBool>,
 }
 
 /// A builder for [RuntimeTransport].
@@ -92,6 +110,9 @@ pub struct RuntimeTransportBuilder {
     jwt: Option<String>,
     timeout: std::time::Duration,
     accept_invalid_certs: bool,
+    max_concurrent_requests: Option<usize>,
+    http2: bool,
+    fail_fast_when_offline: bool,
 }
 
 impl RuntimeTransportBuilder {
@@ -103,6 +124,9 @@ pub fn new(url: Url) -> Self {
             jwt: None,
             timeout: REQUEST_TIMEOUT,
             accept_invalid_certs: false,
+            max_concurrent_requests: None,
+            http2: false,
+            fail_fast_when_offline: false,
         }
     }
 
@@ -130,6 +154,29 @@ pub fn accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
         self
     }
 
+    /// Set the maximum number of concurrent HTTP requests. At most as many idle connections are
+    /// kept open per host.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: Option<usize>) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+
+    /// Set whether to use HTTP/2 with prior knowledge for `http://` connections, which multiplexes
+    /// concurrent requests over a single connection. `https://` connections negotiate the protocol
+    /// via ALPN regardless.
+    pub fn with_http2(mut self, http2: bool) -> Self {
+        self.http2 = http2;
+        self
+    }
+
+    /// Set whether every request fails right away, without being sent, once a request couldn't
+    /// reach the endpoint, instead of waiting for the timeout again. Error responses of the
+    /// endpoint, e.g. rate limits, don't count as unreachable.
+    pub fn with_fail_fast_when_offline(mut self, fail_fast_when_offline: bool) -> Self {
+        self.fail_fast_when_offline = fail_fast_when_offline;
+        self
+    }
+
     /// Builds the [RuntimeTransport] and returns it in a disconnected state.
     /// The runtime transport will then connect when the first request happens.
     pub fn build(self) -> RuntimeTransport {
@@ -140,6 +187,13 @@ pub fn build(self) -> RuntimeTransport {
             jwt: self.jwt,
             timeout: self.timeout,
             accept_invalid_certs: self.accept_invalid_certs,
+            max_concurrent_requests: self.max_concurrent_requests,
+            connection_limit: self
+                .max_concurrent_requests
+                .map(|size| Arc::new(Semaphore::new(size))),
+            http2: self.http2,
+            fail_fast_when_offline: self.fail_fast_when_offline,
+            offline: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -167,6 +221,12 @@ pub fn reqwest_client(&self) -> Result<reqwest::Client, RuntimeTransportError> {
             .timeout(self.timeout)
             .tls_built_in_root_certs(self.url.scheme() == "https")
             .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(max_concurrent_requests) = self.max_concurrent_requests {
+            client_builder = client_builder.pool_max_idle_per_host(max_concurrent_requests);
+        }
+        if self.http2 && self.url.scheme() == "http" {
+            client_builder = client_builder.http2_prior_knowledge();
+        }
         let mut headers = reqwest::header::HeaderMap::new();
 
         // If there's a JWT, add it to the headers if we can decode it.
@@ -247,6 +307,12 @@ async fn connect_ipc(&self) -> Result<InnerTransport, RuntimeTransportError> {
     pub fn request(&self, req: RequestPacket) -> TransportFut<'static> {
         let this = self.clone();
         Box::pin(async move {
+            if this.fail_fast_when_offline && this.offline.load(Ordering::Relaxed) {
+                return Err(TransportErrorKind::custom_str(
+                    "remote endpoint is offline, the request was not sent",
+                ));
+            }
+
             let mut inner = this.inner.read().await;
             if inner.is_none() {
                 drop(inner);
@@ -261,12 +327,37 @@ pub fn request(&self, req: RequestPacket) -> TransportFut<'static> {
             }
 
             // SAFETY: We just checked that the inner transport exists.
-            match inner.clone().expect("must've been initialized") {
+            let transport = inner.clone().expect("must've been initialized");
+            drop(inner);
+
+            // Hold a permit while an HTTP request is in flight, so that at most
+            // `max_concurrent_requests` requests are in flight at a time.
+            let _permit = match (&transport, &this.connection_limit) {
+                (InnerTransport::Http(_), Some(limit)) => {
+                    Some(limit.clone().acquire_owned().await.map_err(TransportErrorKind::custom)?)
+                }
+                _ => None,
+            };
+
+            let res = match transport {
                 InnerTransport::Http(mut http) => http.call(req),
                 InnerTransport::Ws(mut ws) => ws.call(req),
                 InnerTransport::Ipc(mut ipc) => ipc.call(req),
             }
-            .await
+            .await;
+
+            // the endpoint responding with an error, e.g. an HTTP error status, means it's online
+            if this.fail_fast_when_offline
+                && let Err(TransportError::Transport(
+                    err @ (TransportErrorKind::Custom(_) | TransportErrorKind::BackendGone),
+                )) = &res
+            {
+                this.offline.store(true, Ordering::Relaxed);
+                return Err(TransportErrorKind::custom_str(&format!(
+                    "remote endpoint is offline: {err}"
+                )));
+            }
+            res
         })
     }
 
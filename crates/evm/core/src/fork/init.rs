@@ -1,4 +1,4 @@
-use crate::{AsEnvMut, Env, EvmEnv, utils::apply_chain_and_block_specific_env_changes};
+use crate::{AsEnvMut, Env, EvmEnv, utils::apply_chain_and_block_specific_env_changes_for_chain};
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{Address, U256};
 use alloy_provider::{Network, Provider, network::BlockResponse};
@@ -6,9 +6,56 @@
 use eyre::WrapErr;
 use foundry_common::NON_ARCHIVE_NODE_WARNING;
 use revm::context::{BlockEnv, CfgEnv, TxEnv};
+use std::{future::Future, time::Duration};
+
+/// Retry policy for the requests made while initializing a forked environment.
+///
+/// The default makes a single attempt per request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// How many times a failed request is retried
+    pub max_retries: u32,
+    /// How long to wait before the first retry
+    pub initial_backoff: Duration,
+    /// Factor the backoff grows by after every retry
+    pub backoff_multiplier: u32,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_retries: 0, initial_backoff: Duration::from_secs(1), backoff_multiplier: 2 }
+    }
+}
+
+impl RetryConfig {
+    /// Runs the request produced by `f` until it succeeds or the retries are exhausted, waiting
+    /// an exponentially growing backoff between attempts.
+    pub async fn run<T, E, F, Fut>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Err(_) if attempt < self.max_retries => {
+                    attempt += 1;
+                    trace!(target: "fork::init", attempt, ?backoff, "retrying failed request");
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(self.backoff_multiplier);
+                }
+                res => return res,
+            }
+        }
+    }
+}
 
 /// Initializes a REVM block environment based on a forked
 /// ethereum provider.
+///
+/// Every request to the provider is retried according to the given [`RetryConfig`].
+#[expect(clippy::too_many_arguments)]
 pub async fn environment<N: Network, P: Provider<N>>(
     provider: &P,
     memory_limit: u64,
@@ -17,33 +64,48 @@ pub async fn environment<N: Network, P: Provider<N>>(
     pin_block: Option<u64>,
     origin: Address,
     disable_block_gas_limit: bool,
+    retry: RetryConfig,
 ) -> eyre::Result<(Env, N::BlockResponse)> {
     let block_number = if let Some(pin_block) = pin_block {
         pin_block
     } else {
-        provider.get_block_number().await.wrap_err("failed to get latest block number")?
+        retry
+            .run(|| async { provider.get_block_number().await })
+            .await
+            .wrap_err("failed to get latest block number")?
     };
-    let (fork_gas_price, rpc_chain_id, block) = tokio::try_join!(
-        provider.get_gas_price(),
-        provider.get_chain_id(),
-        provider.get_block_by_number(BlockNumberOrTag::Number(block_number))
-    )?;
-    let block = if let Some(block) = block {
-        block
-    } else {
-        if let Ok(latest_block) = provider.get_block_number().await {
-            // If the `eth_getBlockByNumber` call succeeds, but returns null instead of
-            // the block, and the block number is less than equal the latest block, then
-            // the user is forking from a non-archive node with an older block number.
-            if block_number <= latest_block {
-                error!("{NON_ARCHIVE_NODE_WARNING}");
+    let (fork_gas_price, rpc_chain_id, block) = tokio::join!(
+        retry.run(|| async { provider.get_gas_price().await }),
+        retry.run(|| async { provider.get_chain_id().await }),
+        // a missing block is retried as well since it may not have propagated to every node
+        // behind the endpoint yet
+        retry.run(|| async {
+            provider
+                .get_block_by_number(BlockNumberOrTag::Number(block_number))
+                .await
+                .map_err(Some)
+                .and_then(|block| block.ok_or(None))
+        })
+    );
+    let (fork_gas_price, rpc_chain_id) = (fork_gas_price?, rpc_chain_id?);
+    let block = match block {
+        Ok(block) => block,
+        Err(Some(err)) => return Err(err.into()),
+        Err(None) => {
+            if let Ok(latest_block) = provider.get_block_number().await {
+                // If the `eth_getBlockByNumber` call succeeds, but returns null instead of
+                // the block, and the block number is less than equal the latest block, then
+                // the user is forking from a non-archive node with an older block number.
+                if block_number <= latest_block {
+                    error!("{NON_ARCHIVE_NODE_WARNING}");
+                }
+                eyre::bail!(
+                    "failed to get block for block number: {block_number}; \
+                     latest block number: {latest_block}"
+                );
             }
-            eyre::bail!(
-                "failed to get block for block number: {block_number}; \
-                 latest block number: {latest_block}"
-            );
+            eyre::bail!("failed to get block for block number: {block_number}")
         }
-        eyre::bail!("failed to get block for block number: {block_number}")
     };
 
     let cfg = configure_env(
@@ -75,7 +137,12 @@ pub async fn environment<N: Network, P: Provider<N>>(
         },
     };
 
-    apply_chain_and_block_specific_env_changes::<N>(env.as_env_mut(), &block);
+    // the chain specifics are those of the remote chain, even if its chain id is overridden
+    apply_chain_and_block_specific_env_changes_for_chain::<N>(
+        rpc_chain_id,
+        env.as_env_mut(),
+        &block,
+    );
 
     Ok((env, block))
 }
@@ -94,3 +161,114 @@ pub fn configure_env(chain_id: u64, memory_limit: u64, disable_block_gas_limit:
     cfg.disable_nonce_check = true;
     cfg
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload};
+    use alloy_network::Ethereum;
+    use alloy_provider::RootProvider;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::{TransportError, TransportFut};
+    use serde_json::value::RawValue;
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        task::{Context, Poll},
+    };
+
+    /// Transport that answers the first `failures` requests with a rate limit error.
+    #[derive(Clone)]
+    struct FlakyTransport {
+        failures: Arc<AtomicUsize>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl tower::Service<RequestPacket> for FlakyTransport {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: RequestPacket) -> Self::Future {
+            let RequestPacket::Single(req) = req else { panic!("unexpected batch request") };
+            self.requests.fetch_add(1, Ordering::SeqCst);
+
+            let payload = if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                ResponsePayload::Failure(ErrorPayload {
+                    code: 429,
+                    message: "Too Many Requests".into(),
+                    data: None,
+                })
+            } else {
+                let result = match req.method() {
+                    "eth_gasPrice" => serde_json::json!("0x3b9aca00"),
+                    "eth_chainId" => serde_json::json!("0x1"),
+                    "eth_getBlockByNumber" => {
+                        let mut block =
+                            alloy_rpc_types::Block::<alloy_rpc_types::Transaction>::default();
+                        block.header.inner.number = 1;
+                        block.header.inner.gas_limit = 30_000_000;
+                        serde_json::to_value(block).unwrap()
+                    }
+                    method => panic!("unexpected request {method}"),
+                };
+                ResponsePayload::Success(RawValue::from_string(result.to_string()).unwrap())
+            };
+            let response = Response { id: req.id().clone(), payload };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        }
+    }
+
+    fn flaky_provider(failures: usize) -> (RootProvider<Ethereum>, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let transport = FlakyTransport {
+            failures: Arc::new(AtomicUsize::new(failures)),
+            requests: requests.clone(),
+        };
+        (RootProvider::new(RpcClient::new(transport, true)), requests)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn environment_retries_rate_limited_requests() {
+        let retry = RetryConfig {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(10),
+            backoff_multiplier: 2,
+        };
+        let (provider, requests) = flaky_provider(2);
+        let (env, block) =
+            environment(&provider, 0, None, None, Some(1), Address::ZERO, false, retry)
+                .await
+                .unwrap();
+        assert_eq!(block.header.number, 1);
+        assert_eq!(env.evm_env.cfg_env.chain_id, 1);
+        assert_eq!(env.tx.gas_price, 1_000_000_000);
+        // three requests plus the two that were rate limited
+        assert_eq!(requests.load(Ordering::SeqCst), 5);
+
+        // without retries the first rate limited request aborts the initialization
+        let (provider, _) = flaky_provider(2);
+        let res = environment(
+            &provider,
+            0,
+            None,
+            None,
+            Some(1),
+            Address::ZERO,
+            false,
+            Default::default(),
+        )
+        .await;
+        assert!(res.is_err());
+    }
+}
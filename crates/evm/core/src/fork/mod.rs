@@ -2,7 +2,7 @@
 use crate::Env;
 
 mod init;
-pub use init::{configure_env, environment};
+pub use init::{RetryConfig, configure_env, environment};
 
 pub mod database;
 
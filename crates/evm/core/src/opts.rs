@@ -132,6 +132,7 @@ pub async fn fork_evm_env(&self, fork_url: &str) -> eyre::Result<(crate::Env, An
             self.fork_block_number,
             self.sender,
             self.disable_block_gas_limit,
+            Default::default(),
         )
         .await
         .wrap_err_with(|| {
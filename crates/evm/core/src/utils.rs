@@ -31,51 +31,57 @@ pub fn apply_chain_and_block_specific_env_changes<N: Network>(
     env: EnvMut<'_>,
     block: &N::BlockResponse,
 ) {
-    use NamedChain::*;
-
-    if let Ok(chain) = NamedChain::try_from(env.cfg.chain_id) {
-        let block_number = block.header().number();
-
-        match chain {
-            Mainnet => {
-                // after merge difficulty is supplanted with prevrandao EIP-4399
-                if block_number >= 15_537_351u64 {
-                    env.block.difficulty = env.block.prevrandao.unwrap_or_default().into();
-                }
+    let chain_id = env.cfg.chain_id;
+    apply_chain_and_block_specific_env_changes_for_chain::<N>(chain_id, env, block)
+}
 
-                return;
-            }
-            BinanceSmartChain | BinanceSmartChainTestnet => {
-                // https://github.com/foundry-rs/foundry/issues/9942
-                // As far as observed from the source code of bnb-chain/bsc, the `difficulty` field
-                // is still in use and returned by the corresponding opcode but `prevrandao`
-                // (`mixHash`) is always zero, even though bsc adopts the newer EVM
-                // specification. This will confuse revm and causes emulation
-                // failure.
-                env.block.prevrandao = Some(env.block.difficulty.into());
-                return;
+/// Same as [`apply_chain_and_block_specific_env_changes`], but detects the chain specifics from
+/// `chain_id` instead of the chain id of the env.
+///
+/// This is used when the env's chain id is overridden, but the block was fetched from a chain
+/// with a different id whose specifics still apply.
+pub fn apply_chain_and_block_specific_env_changes_for_chain<N: Network>(
+    chain_id: ChainId,
+    env: EnvMut<'_>,
+    block: &N::BlockResponse,
+) {
+    match ChainQuirks::of(chain_id) {
+        ChainQuirks::Mainnet => {
+            // after merge difficulty is supplanted with prevrandao EIP-4399
+            if block.header().number() >= 15_537_351u64 {
+                env.block.difficulty = env.block.prevrandao.unwrap_or_default().into();
             }
-            Moonbeam | Moonbase | Moonriver | MoonbeamDev | Rsk | RskTestnet => {
-                if env.block.prevrandao.is_none() {
-                    // <https://github.com/foundry-rs/foundry/issues/4232>
-                    env.block.prevrandao = Some(B256::random());
-                }
+
+            return;
+        }
+        ChainQuirks::Bsc => {
+            // https://github.com/foundry-rs/foundry/issues/9942
+            // As far as observed from the source code of bnb-chain/bsc, the `difficulty` field
+            // is still in use and returned by the corresponding opcode but `prevrandao`
+            // (`mixHash`) is always zero, even though bsc adopts the newer EVM
+            // specification. This will confuse revm and causes emulation
+            // failure.
+            env.block.prevrandao = Some(env.block.difficulty.into());
+            return;
+        }
+        ChainQuirks::MissingPrevrandao => {
+            if env.block.prevrandao.is_none() {
+                // <https://github.com/foundry-rs/foundry/issues/4232>
+                env.block.prevrandao = Some(B256::random());
             }
-            c if c.is_arbitrum() => {
-                // on arbitrum `block.number` is the L1 block which is included in the
-                // `l1BlockNumber` field
-                if let Some(l1_block_number) = block
-                    .other_fields()
-                    .and_then(|other| other.get("l1BlockNumber").cloned())
-                    .and_then(|l1_block_number| {
-                        serde_json::from_value::<U256>(l1_block_number).ok()
-                    })
-                {
-                    env.block.number = l1_block_number.to();
-                }
+        }
+        ChainQuirks::Arbitrum => {
+            // on arbitrum `block.number` is the L1 block which is included in the
+            // `l1BlockNumber` field
+            if let Some(l1_block_number) = block
+                .other_fields()
+                .and_then(|other| other.get("l1BlockNumber").cloned())
+                .and_then(|l1_block_number| serde_json::from_value::<U256>(l1_block_number).ok())
+            {
+                env.block.number = l1_block_number.to();
             }
-            _ => {}
         }
+        ChainQuirks::None => {}
     }
 
     // if difficulty is `0` we assume it's past merge
@@ -84,6 +90,37 @@ pub fn apply_chain_and_block_specific_env_changes<N: Network>(
     }
 }
 
+/// Chain specifics [`apply_chain_and_block_specific_env_changes`] adjusts the env for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChainQuirks {
+    /// Difficulty is replaced by prevrandao after the merge block
+    Mainnet,
+    /// Prevrandao is always zero, the difficulty is used instead
+    Bsc,
+    /// Prevrandao may be missing from blocks
+    MissingPrevrandao,
+    /// `block.number` is the L1 block number
+    Arbitrum,
+    /// No chain specifics
+    None,
+}
+
+impl ChainQuirks {
+    /// Returns the specifics of the chain.
+    fn of(chain_id: ChainId) -> Self {
+        use NamedChain::*;
+        match NamedChain::try_from(chain_id) {
+            Ok(Mainnet) => Self::Mainnet,
+            Ok(BinanceSmartChain | BinanceSmartChainTestnet) => Self::Bsc,
+            Ok(Moonbeam | Moonbase | Moonriver | MoonbeamDev | Rsk | RskTestnet) => {
+                Self::MissingPrevrandao
+            }
+            Ok(chain) if chain.is_arbitrum() => Self::Arbitrum,
+            _ => Self::None,
+        }
+    }
+}
+
 /// Derive the blob base fee update fraction based on the chain and timestamp by checking the
 /// hardfork.
 pub fn get_blob_base_fee_update_fraction(chain_id: ChainId, timestamp: u64) -> u64 {